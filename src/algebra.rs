@@ -0,0 +1,59 @@
+//! Álgebra linear densa usada nos métodos de Newton (fluxo de potência e
+//! equilíbrio pós-falta).
+
+/// Resolve `a·x = b` por eliminação de Gauss com pivotamento parcial; `None`
+/// se `a` for singular.
+pub(crate) fn resolver_linear(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for k in 0..n {
+        let pivo = (k..n).max_by(|&i, &j| a[i][k].abs().total_cmp(&a[j][k].abs()))?;
+        if a[pivo][k].abs() < 1e-14 {
+            return None;
+        }
+        a.swap(k, pivo);
+        b.swap(k, pivo);
+        let (acima, abaixo) = a.split_at_mut(k + 1);
+        let linha_pivo = &acima[k];
+        for (i, linha) in abaixo.iter_mut().enumerate() {
+            let fator = linha[k] / linha_pivo[k];
+            if fator == 0.0 {
+                continue;
+            }
+            for (aij, akj) in linha[k..].iter_mut().zip(&linha_pivo[k..]) {
+                *aij -= fator * akj;
+            }
+            b[k + 1 + i] -= fator * b[k];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for k in (0..n).rev() {
+        let soma: f64 = (k + 1..n).map(|j| a[k][j] * x[j]).sum();
+        x[k] = (b[k] - soma) / a[k][k];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sistema_com_pivotamento() {
+        // Pivô nulo na primeira linha
+        let a = vec![
+            vec![0.0, 2.0, 1.0],
+            vec![1.0, 1.0, 0.0],
+            vec![2.0, 0.0, 3.0],
+        ];
+        let x = resolver_linear(a, vec![7.0, 3.0, 11.0]).unwrap();
+        for (xi, esperado) in x.iter().zip([1.0, 2.0, 3.0]) {
+            assert!((xi - esperado).abs() < 1e-12, "{:?}", x);
+        }
+    }
+
+    #[test]
+    fn matriz_singular() {
+        let a = vec![vec![1.0, 2.0], vec![2.0, 4.0]];
+        assert_eq!(resolver_linear(a, vec![1.0, 2.0]), None);
+    }
+}
@@ -1,39 +1,156 @@
+//! Método das áreas iguais.
+
 use std::f64::consts::PI;
 
+use crate::curva::PowerAngleCurve;
+
+/// Áreas do critério das áreas iguais para a sequência falta → abertura → religamento.
+///
+/// Retorna `(Área 1, Área 2, Área 3)`: aceleração durante a falta (δ0 → CRA),
+/// aceleração/desaceleração com a linha aberta (CRA → CRR) e desaceleração após
+/// o religamento (CRR → δm). As integrais são calculadas em forma fechada
+/// (ver [`PowerAngleCurve::area_aceleracao`]).
 pub fn calcular_areas_melhorado(
-    pe1: f64, // Pe máximo pré-falta
-    pe2: f64, // Pe máximo durante falta
-    pe3: f64, // Pe máximo pós-falta
-    pm: f64,  // Potência mecânica
-    cra: f64, // Limite inferior de critério de estabilidade
-    crr: f64, // Limite superior de critério de estabilidade
+    pe1: PowerAngleCurve, // Curva pré-falta
+    pe2: PowerAngleCurve, // Curva durante falta
+    pe3: PowerAngleCurve, // Curva pós-falta
+    pm: f64,              // Potência mecânica
+    cra: f64,             // Limite inferior de critério de estabilidade
+    crr: f64,             // Limite superior de critério de estabilidade
 ) -> (f64, f64, f64) {
     // Interseções (em radianos)
-    let intersecoes_pe1 = encontrar_intersecoes(pe1, pm);
+    let intersecoes_pe1 = encontrar_intersecoes(&pe1, pm);
     let delta_n_ini_rad = intersecoes_pe1.0;
     let delta_m_rad = intersecoes_pe1.1;
 
-    // Usando integração por Simpson ou Trapézio com mais pontos
-    let num_pontos = 10_000; // Aumentar precisão
-
     // ÁREA 1: Entre delta_n_ini e CRA, Integrate[pm-pe2,{x,delta_n_ini,cra}]
-    let area1 = integrar_simpson(delta_n_ini_rad, cra, num_pontos, |x| {
-        pm - calcular_potencia_eletrica(x, pe2)
-    });
+    let area1 = pe2.area_aceleracao(pm, delta_n_ini_rad, cra);
 
     // ÁREA 2: Entre CRA e CRR, Integrate[pm-pe3,{x,cra,crr}]
-    let area2 = integrar_simpson(cra, crr, num_pontos, |x| {
-        pm - calcular_potencia_eletrica(x, pe3)
-    });
+    let area2 = pe3.area_aceleracao(pm, cra, crr);
 
     // ÁREA 3: Entre CRR e delta_m, Integrate[pe1-pm,{x,crr,delta_m}]
-    let area3 = integrar_simpson(crr, delta_m_rad, num_pontos, |x| {
-        calcular_potencia_eletrica(x, pe1) - pm
-    });
+    let area3 = -pe1.area_aceleracao(pm, crr, delta_m_rad);
 
     (area1, area2, area3)
 }
 
+/// Área de aceleração `∫(Pm - Pe(δ)) dδ` para uma curva potência-ângulo
+/// arbitrária, por Simpson com 10.000 subintervalos. Para uma
+/// [`PowerAngleCurve`], prefira a forma fechada
+/// [`PowerAngleCurve::area_aceleracao`].
+pub fn area_aceleracao_curva<F>(pm: f64, pe: F, de: f64, ate: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    integrar_simpson(de, ate, 10_000, |x| pm - pe(x))
+}
+
+/// Ângulo crítico de abertura pelo critério das áreas iguais, para falta
+/// (Pe2) eliminada pela abertura da linha (Pe3) sem religamento.
+///
+/// Resolve `A(δ0 → δc, Pe2) + A(δc → δmax, Pe3) = 0`, com δ0 o equilíbrio
+/// estável de Pe1, δmax o equilíbrio instável de Pe3 e `A` a área de
+/// aceleração ([`PowerAngleCurve::area_aceleracao`]). Retorna `None` quando a
+/// rede pré-falta ou a pós-abertura não tem equilíbrio (Pe1 ou Pe3 não atinge
+/// Pm) ou quando não há ângulo crítico (sistema estável com a falta mantida ou instável com
+/// abertura imediata).
+pub fn angulo_critico_abertura(
+    pe1: PowerAngleCurve,
+    pe2: PowerAngleCurve,
+    pe3: PowerAngleCurve,
+    pm: f64,
+) -> Option<f64> {
+    let delta_max = pe3.intersecoes(pm)?.1;
+    let delta_0 = pe1.intersecoes(pm)?.0;
+    resolver_raiz(
+        |delta_c| {
+            pe2.area_aceleracao(pm, delta_0, delta_c) + pe3.area_aceleracao(pm, delta_c, delta_max)
+        },
+        |delta_c| pe3.potencia(delta_c) - pe2.potencia(delta_c),
+        delta_0,
+        delta_max,
+    )
+}
+
+/// Ângulo crítico de religamento para a abertura em `cra`, na sequência
+/// falta (Pe2) → linha aberta (Pe3) → religamento (Pe1).
+///
+/// Resolve `A(δ0 → CRA, Pe2) + A(CRA → CRR, Pe3) + A(CRR → δm, Pe1) = 0`
+/// (Área 1 + Área 2 = Área 3 de [`calcular_areas_melhorado`]), com δm o
+/// equilíbrio instável de Pe1. Religar antes desse ângulo mantém a estabilidade.
+/// Retorna `None` quando não há raiz em `[CRA, δm]` (instável mesmo com
+/// religamento imediato, ou estável sem religamento).
+pub fn angulo_critico_religamento(
+    pe1: PowerAngleCurve,
+    pe2: PowerAngleCurve,
+    pe3: PowerAngleCurve,
+    pm: f64,
+    cra: f64,
+) -> Option<f64> {
+    let (delta_0, delta_m) = pe1.intersecoes(pm)?;
+    if cra >= delta_m {
+        return None;
+    }
+    let area1 = pe2.area_aceleracao(pm, delta_0, cra);
+    resolver_raiz(
+        |crr| area1 + pe3.area_aceleracao(pm, cra, crr) + pe1.area_aceleracao(pm, crr, delta_m),
+        |crr| pe1.potencia(crr) - pe3.potencia(crr),
+        cra,
+        delta_m,
+    )
+}
+
+// Raiz de f em [a, b] por Newton protegido: passos de Newton que saem do
+// intervalo com troca de sinal são substituídos por bisseção. `None` se não
+// há troca de sinal ou se f não é finita nos extremos ou na raiz
+fn resolver_raiz<F, D>(f: F, df: D, mut a: f64, mut b: f64) -> Option<f64>
+where
+    F: Fn(f64) -> f64,
+    D: Fn(f64) -> f64,
+{
+    let fa = f(a);
+    let fb = f(b);
+    if !fa.is_finite() || !fb.is_finite() {
+        return None;
+    }
+    if fa == 0.0 {
+        return Some(a);
+    }
+    if fb == 0.0 {
+        return Some(b);
+    }
+    if fa.signum() == fb.signum() {
+        return None;
+    }
+
+    let sinal_a = fa.signum();
+    let mut x = 0.5 * (a + b);
+    for _ in 0..100 {
+        let fx = f(x);
+        if fx == 0.0 {
+            break;
+        }
+        if fx.signum() == sinal_a {
+            a = x;
+        } else {
+            b = x;
+        }
+        let newton = x - fx / df(x);
+        let proximo = if newton > a && newton < b {
+            newton
+        } else {
+            0.5 * (a + b)
+        };
+        let convergiu = (proximo - x).abs() <= 1e-14 * x.abs().max(1.0);
+        x = proximo;
+        if convergiu || b - a <= 1e-14 {
+            break;
+        }
+    }
+    x.is_finite().then_some(x)
+}
+
 // Implementação da integração por Simpson
 fn integrar_simpson<F>(a: f64, b: f64, n: usize, f: F) -> f64
 where
@@ -43,7 +160,7 @@ where
         return 0.0;
     }
 
-    let n = if n % 2 == 0 { n } else { n + 1 }; // Garantir que n seja par
+    let n = if n.is_multiple_of(2) { n } else { n + 1 }; // Garantir que n seja par
     let h = (b - a) / n as f64;
 
     let mut soma = f(a) + f(b);
@@ -60,10 +177,16 @@ where
     soma * h / 3.0
 }
 
-// Para debug - verificar os valores nos pontos críticos
-#[allow(dead_code)]
-pub fn debug_valores(pe1: f64, pe2: f64, pe3: f64, pm: f64, cra: f64, crr: f64) {
-    let intersecoes = encontrar_intersecoes(pe1, pm);
+/// Para debug - verificar os valores nos pontos críticos.
+pub fn debug_valores(
+    pe1: PowerAngleCurve,
+    pe2: PowerAngleCurve,
+    pe3: PowerAngleCurve,
+    pm: f64,
+    cra: f64,
+    crr: f64,
+) {
+    let intersecoes = encontrar_intersecoes(&pe1, pm);
     let delta_n_ini = intersecoes.0;
     let delta_m = intersecoes.1;
 
@@ -81,35 +204,96 @@ pub fn debug_valores(pe1: f64, pe2: f64, pe3: f64, pm: f64, cra: f64, crr: f64)
     println!("Em delta_n_ini:");
     println!(
         "  Pe2: {:.4}, Pm: {:.4}, Diferença: {:.4}",
-        calcular_potencia_eletrica(delta_n_ini, pe2),
+        pe2.potencia(delta_n_ini),
         pm,
-        pm - calcular_potencia_eletrica(delta_n_ini, pe2)
+        pm - pe2.potencia(delta_n_ini)
     );
 
     println!("Em CRA:");
     println!(
         "  Pe2: {:.4}, Pe3: {:.4}, Pm: {:.4}",
-        calcular_potencia_eletrica(cra, pe2),
-        calcular_potencia_eletrica(cra, pe3),
+        pe2.potencia(cra),
+        pe3.potencia(cra),
         pm
     );
 
     println!("Em CRR:");
     println!(
         "  Pe1: {:.4}, Pe3: {:.4}, Pm: {:.4}",
-        calcular_potencia_eletrica(crr, pe1),
-        calcular_potencia_eletrica(crr, pe3),
+        pe1.potencia(crr),
+        pe3.potencia(crr),
         pm
     );
 }
 
-// Função auxiliar - assumindo que você já tem esta implementada
-fn calcular_potencia_eletrica(angulo: f64, pe_max: f64) -> f64 {
-    pe_max * angulo.sin()
+// Interseções da curva com Pm (NaN se a curva não atinge Pm)
+fn encontrar_intersecoes(curva: &PowerAngleCurve, pm: f64) -> (f64, f64) {
+    curva.intersecoes(pm).unwrap_or((f64::NAN, f64::NAN))
 }
 
-// Função auxiliar - assumindo que você já tem esta implementada
-fn encontrar_intersecoes(pe_max: f64, pm: f64) -> (f64, f64) {
-    let sin_inv = (pm / pe_max).asin();
-    (sin_inv, PI - sin_inv)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PM: f64 = 1.0;
+
+    fn curvas() -> (PowerAngleCurve, PowerAngleCurve, PowerAngleCurve) {
+        (1.8.into(), 0.6.into(), 1.4.into())
+    }
+
+    #[test]
+    fn angulo_critico_de_abertura_em_forma_fechada() {
+        let (pe1, pe2, pe3) = curvas();
+        let delta_0 = (PM / pe1.pmax).asin();
+        let delta_max = PI - (PM / pe3.pmax).asin();
+        // cos δc = [Pm·(δmax - δ0) + P3·cos δmax - P2·cos δ0] / (P3 - P2)
+        let esperado = ((PM * (delta_max - delta_0) + pe3.pmax * delta_max.cos()
+            - pe2.pmax * delta_0.cos())
+            / (pe3.pmax - pe2.pmax))
+            .acos();
+        let delta_c = angulo_critico_abertura(pe1, pe2, pe3, PM).unwrap();
+        assert!(
+            (delta_c - esperado).abs() < 1e-12,
+            "{} != {}",
+            delta_c,
+            esperado
+        );
+    }
+
+    #[test]
+    fn angulo_critico_de_religamento_equilibra_as_areas() {
+        let (pe1, pe2, pe3) = curvas();
+        // Abertura tardia: instável sem religamento
+        let cra = 1.5;
+        let crr = angulo_critico_religamento(pe1, pe2, pe3, PM, cra).unwrap();
+        let (area1, area2, area3) = calcular_areas_melhorado(pe1, pe2, pe3, PM, cra, crr);
+        assert!((area1 + area2 - area3).abs() < 1e-10);
+    }
+
+    #[test]
+    fn areas_em_forma_fechada_coincidem_com_simpson() {
+        let curva = PowerAngleCurve {
+            pc: 0.1,
+            pmax: 1.5,
+            gamma: -0.05,
+        };
+        let simpson = area_aceleracao_curva(PM, |d| curva.potencia(d), 0.4, 2.1);
+        assert!((curva.area_aceleracao(PM, 0.4, 2.1) - simpson).abs() < 1e-10);
+    }
+
+    #[test]
+    fn pm_fora_do_alcance_das_curvas_nao_tem_angulo_critico() {
+        let (_, pe2, pe3) = curvas();
+        // Pe1 não atinge Pm: sem equilíbrio pré-falta
+        assert_eq!(angulo_critico_abertura(0.8.into(), pe2, pe3, PM), None);
+        assert_eq!(
+            angulo_critico_religamento(0.8.into(), pe2, pe3, PM, 0.5),
+            None
+        );
+        // Pe3 não atinge Pm: sem equilíbrio após a abertura
+        assert_eq!(
+            angulo_critico_abertura(1.8.into(), pe2, 0.8.into(), PM),
+            None
+        );
+    }
 }
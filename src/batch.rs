@@ -0,0 +1,305 @@
+//! Execução em lote de uma pasta de arquivos de caso.
+
+use std::any::Any;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::case::Case;
+use crate::error::{Error, Result};
+use crate::estabilidade::Veredito;
+use crate::estudo::{ResumoEstudo, executar_estudo};
+use crate::multimaquina::executar_multimaquina;
+
+/// Resultado de uma execução em lote.
+#[derive(Debug)]
+pub struct ResultadoLote {
+    /// Estudos concluídos, na ordem dos arquivos de caso.
+    pub resumos: Vec<ResumoEstudo>,
+    /// Casos multimáquinas concluídos, na ordem dos arquivos de caso.
+    pub multimaquina: Vec<ResumoMultimaquina>,
+    /// Casos que falharam: (nome do caso, erro).
+    pub falhas: Vec<(String, Error)>,
+}
+
+/// Resumo de um caso multimáquinas no lote; as colunas do método das áreas
+/// iguais não se aplicam a ele.
+#[derive(Debug, Clone)]
+pub struct ResumoMultimaquina {
+    /// Nome do caso.
+    pub nome: String,
+    /// Tempo de abertura simulado (s), da seção do caso ou encontrado pelo
+    /// PSO.
+    pub tab: f64,
+    /// Tempo de religamento simulado (s).
+    pub tr: f64,
+    /// Veredito de estabilidade.
+    pub veredito: Veredito,
+    /// Maior separação angular entre dois geradores (rad).
+    pub separacao_max: f64,
+    /// Tempo total de execução do caso.
+    pub tempo: Duration,
+}
+
+// Resultado de um caso do lote
+enum EstudoLote {
+    Smib(ResumoEstudo),
+    Multimaquina(ResumoMultimaquina),
+}
+
+/// Lista os arquivos de caso (`.toml` e `.json`) de um diretório, em ordem alfabética.
+pub fn listar_casos(pasta_casos: &Path) -> Result<Vec<PathBuf>> {
+    let mut casos: Vec<PathBuf> = fs::read_dir(pasta_casos)
+        .map_err(Error::io(pasta_casos))?
+        .filter_map(|entrada| entrada.ok().map(|e| e.path()))
+        .filter(|caminho| {
+            caminho.is_file()
+                && matches!(
+                    caminho.extension().and_then(|e| e.to_str()),
+                    Some("toml") | Some("json")
+                )
+        })
+        .collect();
+    casos.sort();
+    Ok(casos)
+}
+
+/// Executa todos os casos de `pasta_casos`, cada um em `pasta_saida/<nome_do_caso>/`,
+/// e grava o resumo comparativo em `resumo.csv` e `resumo.md`.
+///
+/// Os casos rodam em paralelo, até o número de núcleos disponíveis. As buscas
+/// do PSO usam o caso ativo de [`crate::objective_function`] e por isso são
+/// executadas uma de cada vez (cada uma já avalia as partículas em paralelo,
+/// com `Config::parallelize`); simulação final, áreas, gráficos e relatórios
+/// de casos diferentes se sobrepõem a elas.
+pub fn executar_lote(
+    pasta_casos: &Path,
+    pasta_saida: &str,
+    should_open_images: bool,
+) -> Result<ResultadoLote> {
+    let init = Instant::now();
+    let casos = listar_casos(pasta_casos)?;
+    if casos.is_empty() {
+        return Err(Error::LoteVazio(pasta_casos.to_path_buf()));
+    }
+    fs::create_dir_all(pasta_saida).map_err(Error::io(pasta_saida))?;
+
+    // Cada trabalhador pega o próximo caso ainda não executado
+    let proximo = AtomicUsize::new(0);
+    let trabalhadores = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(casos.len());
+    let mut estudos: Vec<(usize, String, Result<EstudoLote>)> = thread::scope(|escopo| {
+        let trabalhos: Vec<_> = (0..trabalhadores)
+            .map(|_| {
+                escopo.spawn(|| {
+                    let mut concluidos = Vec::new();
+                    loop {
+                        let i = proximo.fetch_add(1, Ordering::Relaxed);
+                        let Some(caminho) = casos.get(i) else {
+                            return concluidos;
+                        };
+                        let nome = caminho
+                            .file_stem()
+                            .map(|s| s.to_string_lossy().to_string())
+                            .unwrap_or_default();
+                        println!("\n=== LOTE: CASO {}/{} ({}) ===", i + 1, casos.len(), nome);
+                        // Um pânico vira a falha do caso, sem perder os demais
+                        let estudo = panic::catch_unwind(AssertUnwindSafe(|| {
+                            executar_caso(&nome, caminho, pasta_saida, should_open_images)
+                        }))
+                        .unwrap_or_else(|e| Err(Error::Panico(mensagem_panico(e.as_ref()))));
+                        concluidos.push((i, nome, estudo));
+                    }
+                })
+            })
+            .collect();
+        trabalhos
+            .into_iter()
+            .flat_map(|trabalho| {
+                trabalho
+                    .join()
+                    .expect("pânicos dos casos são capturados pelo trabalhador")
+            })
+            .collect()
+    });
+    estudos.sort_by_key(|(i, _, _)| *i);
+
+    let mut resultado = ResultadoLote {
+        resumos: Vec::new(),
+        multimaquina: Vec::new(),
+        falhas: Vec::new(),
+    };
+    for (_, nome, estudo) in estudos {
+        match estudo {
+            Ok(EstudoLote::Smib(resumo)) => resultado.resumos.push(resumo),
+            Ok(EstudoLote::Multimaquina(resumo)) => resultado.multimaquina.push(resumo),
+            Err(e) => {
+                eprintln!("Caso '{}' falhou: {}", nome, e);
+                resultado.falhas.push((nome, e));
+            }
+        }
+    }
+
+    let caminho_csv = format!("{}/resumo.csv", pasta_saida);
+    let caminho_md = format!("{}/resumo.md", pasta_saida);
+    fs::write(&caminho_csv, resumo_csv(&resultado)).map_err(Error::io(&caminho_csv))?;
+    fs::write(&caminho_md, resumo_markdown(&resultado)).map_err(Error::io(&caminho_md))?;
+
+    println!("\n=== FIM DO LOTE ===");
+    println!(
+        "{} caso(s) concluído(s), {} falha(s) em {:.2?}",
+        resultado.resumos.len() + resultado.multimaquina.len(),
+        resultado.falhas.len(),
+        init.elapsed()
+    );
+    println!("Resumo salvo em: {} e {}", caminho_csv, caminho_md);
+
+    Ok(resultado)
+}
+
+// Executa um caso em `pasta_saida/<nome>/`
+fn executar_caso(
+    nome: &str,
+    caminho: &Path,
+    pasta_saida: &str,
+    should_open_images: bool,
+) -> Result<EstudoLote> {
+    let init = Instant::now();
+    let case = Case::carregar(caminho)?;
+    let pasta_caso = format!("{}/{}", pasta_saida, nome);
+    if case.multimaquina.is_none() {
+        return executar_estudo(nome, &case, Some(caminho), &pasta_caso, should_open_images)
+            .map(EstudoLote::Smib);
+    }
+    let resultado = executar_multimaquina(&case, Some(caminho), &pasta_caso, should_open_images)?;
+    Ok(EstudoLote::Multimaquina(ResumoMultimaquina {
+        nome: nome.to_string(),
+        tab: resultado.tab,
+        tr: resultado.tr,
+        veredito: resultado.veredito,
+        separacao_max: resultado.separacao_max,
+        tempo: init.elapsed(),
+    }))
+}
+
+/// Tabela comparativa em CSV (ângulos em graus, tempo em segundos). Nos casos
+/// multimáquinas, as colunas do método das áreas iguais são `n/a` e o ângulo
+/// máximo é a maior separação angular entre dois geradores.
+pub fn resumo_csv(resultado: &ResultadoLote) -> String {
+    let mut csv = String::from(
+        "caso,tab_s,tr_s,cra_graus,crr_graus,area1,area2,area3,diferenca_areas,estavel,angulo_max_graus,tempo_execucao_s\n",
+    );
+    for r in &resultado.resumos {
+        csv.push_str(&format!(
+            "{},{:.6},{:.6},{},{},{},{},{},{},{},{:.4},{:.3}\n",
+            r.nome,
+            r.tab,
+            r.tr,
+            opcional(r.cra.map(f64::to_degrees), 4),
+            opcional(r.crr.map(f64::to_degrees), 4),
+            opcional(r.area1, 6),
+            opcional(r.area2, 6),
+            opcional(r.area3, 6),
+            opcional(r.diferenca, 6),
+            r.estabilidade.estavel(),
+            r.estabilidade.angulo_max_primeira_oscilacao.to_degrees(),
+            r.tempo.as_secs_f64()
+        ));
+    }
+    for r in &resultado.multimaquina {
+        csv.push_str(&format!(
+            "{},{:.6},{:.6},n/a,n/a,n/a,n/a,n/a,n/a,{},{:.4},{:.3}\n",
+            r.nome,
+            r.tab,
+            r.tr,
+            r.veredito == Veredito::Estavel,
+            r.separacao_max.to_degrees(),
+            r.tempo.as_secs_f64()
+        ));
+    }
+    csv
+}
+
+/// Tabela comparativa em Markdown, com os casos multimáquinas ao final,
+/// seguida da lista de casos que falharam.
+pub fn resumo_markdown(resultado: &ResultadoLote) -> String {
+    let mut md = String::from(
+        "| Caso | tab (s) | tr (s) | CRA (°) | CRR (°) | Área 1 | Área 2 | Área 3 | Diferença | Estabilidade | δmax (°) | Tempo (s) |\n\
+         |------|--------:|-------:|--------:|--------:|-------:|-------:|-------:|----------:|--------------|---------:|----------:|\n",
+    );
+    for r in &resultado.resumos {
+        md.push_str(&format!(
+            "| {} | {:.4} | {:.4} | {} | {} | {} | {} | {} | {} | {} | {:.4} | {:.2} |\n",
+            r.nome,
+            r.tab,
+            r.tr,
+            opcional(r.cra.map(f64::to_degrees), 4),
+            opcional(r.crr.map(f64::to_degrees), 4),
+            opcional(r.area1, 4),
+            opcional(r.area2, 4),
+            opcional(r.area3, 4),
+            opcional(r.diferenca, 4),
+            r.estabilidade.veredito,
+            r.estabilidade.angulo_max_primeira_oscilacao.to_degrees(),
+            r.tempo.as_secs_f64()
+        ));
+    }
+    for r in &resultado.multimaquina {
+        md.push_str(&format!(
+            "| {} | {:.4} | {:.4} | n/a | n/a | n/a | n/a | n/a | n/a | {} | {:.4} | {:.2} |\n",
+            r.nome,
+            r.tab,
+            r.tr,
+            r.veredito,
+            r.separacao_max.to_degrees(),
+            r.tempo.as_secs_f64()
+        ));
+    }
+    if !resultado.multimaquina.is_empty() {
+        md.push_str(
+            "\nCasos multimáquinas: o método das áreas iguais não se aplica (n/a), e δmax é a \
+             maior separação angular entre dois geradores.\n",
+        );
+    }
+    if !resultado.falhas.is_empty() {
+        md.push_str("\n**Casos com falha:**\n\n");
+        for (nome, erro) in &resultado.falhas {
+            md.push_str(&format!(
+                "- `{}`: {}\n",
+                nome,
+                erro.to_string().replace('\n', " ")
+            ));
+        }
+    }
+    md
+}
+
+// Mensagem de um pânico (`&str` ou `String`, como em `panic!`)
+fn mensagem_panico(e: &(dyn Any + Send)) -> String {
+    e.downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| e.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "pânico sem mensagem".to_string())
+}
+
+// Valor com `casas` decimais, ou `n/a` quando ausente
+fn opcional(valor: Option<f64>, casas: usize) -> String {
+    valor.map_or("n/a".to_string(), |v| format!("{:.*}", casas, v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panico_vira_mensagem_de_erro() {
+        let e = panic::catch_unwind(|| panic!("caso {} inválido", 3)).unwrap_err();
+        assert_eq!(mensagem_panico(e.as_ref()), "caso 3 inválido");
+        let e = panic::catch_unwind(|| panic!("sem formatação")).unwrap_err();
+        assert_eq!(mensagem_panico(e.as_ref()), "sem formatação");
+    }
+}
@@ -0,0 +1,40 @@
+//! Blocos de controle comuns às excitatrizes ([`crate::excitacao`]), aos
+//! reguladores de velocidade ([`crate::turbina`]) e aos estabilizadores
+//! ([`crate::estabilizador`]).
+
+use crate::error::{Error, Result};
+
+// Bloco avanço-atraso (1 + s·TA)/(1 + s·TB) com entrada `u` e estado `x`:
+// retorna a saída e a derivada do estado (TB nulo: ganho unitário)
+pub(crate) fn avanco_atraso(u: f64, x: f64, ta: f64, tb: f64) -> (f64, f64) {
+    if tb <= 0.0 {
+        return (u, 0.0);
+    }
+    (x + ta / tb * (u - x), (u - x) / tb)
+}
+
+// Derivada de um estado com limite sem acúmulo entre `min` e `max`
+pub(crate) fn sem_acumulo(x: f64, derivada: f64, min: f64, max: f64) -> f64 {
+    if (x >= max && derivada > 0.0) || (x <= min && derivada < 0.0) {
+        0.0
+    } else {
+        derivada
+    }
+}
+
+// Verifica se o valor de equilíbrio de `nome` está dentro dos limites do
+// equipamento (`dono`, por exemplo "da excitatriz")
+pub(crate) fn limite(nome: &str, valor: f64, min: f64, max: f64, dono: &str) -> Result<()> {
+    if (min..=max).contains(&valor) {
+        Ok(())
+    } else {
+        Err(Error::Configuracao(format!(
+            "{} = {:.4} p.u. no equilíbrio pré-falta está fora dos limites {} [{:.4}, {:.4}]",
+            nome, valor, dono, min, max
+        )))
+    }
+}
+
+pub(crate) fn um() -> f64 {
+    1.0
+}
@@ -0,0 +1,740 @@
+//! Modelo do sistema e arquivo de caso (TOML ou JSON).
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::curva::PowerAngleCurve;
+use crate::energia::{margem_energia, verificar_modelo};
+use crate::error::{Error, Result};
+use crate::estabilizador::StabilizerSettings;
+use crate::eventos::Evento;
+use crate::excitacao::ExciterSettings;
+use crate::maquina::modelo_maquina;
+use crate::matpower::importar_matpower;
+use crate::multimaquina::{EventoRede, MultiMachineSystem};
+use crate::psse::importar_psse;
+use crate::rede::{ResultadoRede, SmibNetwork};
+use crate::turbina::{FastValving, GovernorSettings};
+use crate::values::*;
+
+/// Definição de um estudo (caso) carregada de um arquivo TOML ou JSON.
+///
+/// Os valores padrão reproduzem as constantes de [`crate::values`], de modo que
+/// um arquivo de caso só precisa declarar o que difere delas.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Case {
+    /// Parâmetros do sistema (seção `[sistema]`).
+    pub sistema: SystemParams,
+    /// Rede SMIB da qual Pe1, Pe2 e Pe3 são calculadas (seção `[rede]`); quando
+    /// presente, substitui as curvas de `[sistema]` (ver [`Case::aplicar_rede`]).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rede: Option<SmibNetwork>,
+    /// Modelo dinâmico do gerador (seção `[maquina]`).
+    pub maquina: MachineSettings,
+    /// Configurações da integração numérica (seção `[integrador]`).
+    pub integrador: IntegratorSettings,
+    /// Configurações do PSO (seção `[pso]`).
+    pub pso: PsoSettings,
+    /// Configurações da busca do tempo crítico de abertura (seção `[cct]`).
+    pub cct: CctSettings,
+    /// Critérios de estabilidade da trajetória (seção `[estabilidade]`).
+    pub estabilidade: StabilitySettings,
+    /// Sequência de eventos de chaveamento (`[[eventos]]`); vazia equivale à
+    /// sequência clássica falta → abertura em `tab` → religamento em `tr`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub eventos: Vec<Evento>,
+    /// Sistema multimáquinas (seção `[multimaquina]`); quando presente, o caso
+    /// é simulado por [`crate::multimaquina::executar_multimaquina`] em vez do
+    /// estudo máquina x barra infinita.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multimaquina: Option<MultiMachineSystem>,
+    /// Sistema multimáquinas importado de um caso do MATPOWER (seção
+    /// `[matpower]`); ao carregar o caso, preenche `multimaquina`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matpower: Option<ImportacaoMatpower>,
+    /// Sistema multimáquinas importado de arquivos RAW e DYR do PSS/E (seção
+    /// `[psse]`); ao carregar o caso, preenche `multimaquina`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub psse: Option<ImportacaoPsse>,
+}
+
+/// Importação de um caso do MATPOWER (ver [`crate::matpower`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ImportacaoMatpower {
+    /// Arquivo `.m`, relativo ao arquivo de caso.
+    pub arquivo: PathBuf,
+    /// Arquivo TOML com os dados dinâmicos dos geradores, relativo ao arquivo
+    /// de caso.
+    pub dinamica: PathBuf,
+    /// Instante de abertura (s) para eventos com `apos = "tab"`.
+    #[serde(default)]
+    pub tab: f64,
+    /// Instante de religamento (s) para eventos com `apos = "tr"`.
+    #[serde(default)]
+    pub tr: f64,
+    /// Busca `tab` e `tr` com o PSO, nos limites de `[pso]`, em vez de usar os
+    /// valores acima (ver [`crate::objective_function`]).
+    #[serde(default)]
+    pub otimizar: bool,
+    /// Eventos de rede (`[[matpower.eventos]]`), com os ramos indexados pelas
+    /// linhas de `mpc.branch` a partir de 0.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub eventos: Vec<EventoRede>,
+}
+
+/// Importação de arquivos do PSS/E (ver [`crate::psse`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ImportacaoPsse {
+    /// Arquivo RAW (revisão 33), relativo ao arquivo de caso.
+    pub raw: PathBuf,
+    /// Arquivo DYR com os modelos dos geradores, relativo ao arquivo de caso.
+    pub dyr: PathBuf,
+    /// Instante de abertura (s) para eventos com `apos = "tab"`.
+    #[serde(default)]
+    pub tab: f64,
+    /// Instante de religamento (s) para eventos com `apos = "tr"`.
+    #[serde(default)]
+    pub tr: f64,
+    /// Busca `tab` e `tr` com o PSO, nos limites de `[pso]`, em vez de usar os
+    /// valores acima (ver [`crate::objective_function`]).
+    #[serde(default)]
+    pub otimizar: bool,
+    /// Eventos de rede (`[[psse.eventos]]`), com os ramos indexados na ordem
+    /// do RAW (linhas seguidas dos transformadores) a partir de 0.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub eventos: Vec<EventoRede>,
+}
+
+/// Parâmetros do sistema máquina x barra infinita.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SystemParams {
+    /// Curva potência-ângulo pré-falta.
+    pub pe1: PowerAngleCurve,
+    /// Curva potência-ângulo durante falta.
+    pub pe2: PowerAngleCurve,
+    /// Curva potência-ângulo abertura mono/bifásica.
+    pub pe3: PowerAngleCurve,
+    /// Potência mecânica pré-falta (constante sem regulador de velocidade).
+    pub pm: f64,
+    /// Frequência em Hz.
+    pub f: f64,
+    /// Constante de inércia do gerador.
+    pub h: f64,
+    /// Coeficiente de amortecimento.
+    pub d: f64,
+    /// Velocidade angular inicial.
+    pub delta_w_ini: f64,
+}
+
+/// Modelo dinâmico do gerador no estudo máquina x barra infinita.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModeloMaquina {
+    /// FEM constante atrás de `x'd` (`"classico"`).
+    #[default]
+    Classico,
+    /// Decaimento de fluxo de eixo direto, com `E'q` como estado e tensão de
+    /// campo constante (`"um_eixo"`; ver [`crate::um_eixo`]).
+    UmEixo,
+    /// Dinâmica transitória dos eixos direto e em quadratura, com `E'q` e
+    /// `E'd` como estados (`"dois_eixos"`; ver [`crate::dois_eixos`]).
+    DoisEixos,
+    /// Dinâmica transitória e subtransitória dos dois eixos, semelhante ao
+    /// GENROU sem saturação (`"sexta_ordem"`; ver [`crate::subtransitorio`]).
+    SextaOrdem,
+}
+
+impl std::fmt::Display for ModeloMaquina {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModeloMaquina::Classico => write!(f, "clássico"),
+            ModeloMaquina::UmEixo => write!(f, "um eixo"),
+            ModeloMaquina::DoisEixos => write!(f, "dois eixos"),
+            ModeloMaquina::SextaOrdem => write!(f, "sexta ordem"),
+        }
+    }
+}
+
+/// Modelo dinâmico do gerador e parâmetros além de `x'd` (que vem de
+/// `[rede]`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MachineSettings {
+    /// Modelo do gerador (`"classico"`, `"um_eixo"`, `"dois_eixos"` ou
+    /// `"sexta_ordem"`).
+    pub modelo: ModeloMaquina,
+    /// Reatância síncrona de eixo direto `Xd` (p.u.); obrigatória a partir do
+    /// modelo de um eixo.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xd: Option<f64>,
+    /// Constante de tempo transitória de eixo direto em circuito aberto
+    /// `T'd0` (s); obrigatória a partir do modelo de um eixo.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub td0_transitoria: Option<f64>,
+    /// Reatância síncrona de eixo em quadratura `Xq` (p.u.); obrigatória a
+    /// partir do modelo de dois eixos.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xq: Option<f64>,
+    /// Reatância transitória de eixo em quadratura `x'q` (p.u.); obrigatória
+    /// a partir do modelo de dois eixos.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xq_transitoria: Option<f64>,
+    /// Constante de tempo transitória de eixo em quadratura em circuito
+    /// aberto `T'q0` (s); obrigatória a partir do modelo de dois eixos.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tq0_transitoria: Option<f64>,
+    /// Reatância subtransitória de eixo direto `x''d` (p.u.); obrigatória no
+    /// modelo de sexta ordem.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xd_subtransitoria: Option<f64>,
+    /// Reatância subtransitória de eixo em quadratura `x''q` (p.u.; padrão:
+    /// `xd_subtransitoria`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xq_subtransitoria: Option<f64>,
+    /// Constante de tempo subtransitória de eixo direto em circuito aberto
+    /// `T''d0` (s); obrigatória no modelo de sexta ordem.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub td0_subtransitoria: Option<f64>,
+    /// Constante de tempo subtransitória de eixo em quadratura em circuito
+    /// aberto `T''q0` (s); obrigatória no modelo de sexta ordem.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tq0_subtransitoria: Option<f64>,
+    /// Sistema de excitação (subseção `[maquina.excitatriz]`); se ausente, a
+    /// tensão de campo fica constante no valor pré-falta.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excitatriz: Option<ExciterSettings>,
+    /// Estabilizador de sistemas de potência (subseção
+    /// `[maquina.estabilizador]`), que atua sobre a excitatriz.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estabilizador: Option<StabilizerSettings>,
+    /// Regulador de velocidade e turbina (subseção `[maquina.regulador]`); se
+    /// ausente, a potência mecânica fica constante entre os eventos.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regulador: Option<GovernorSettings>,
+    /// Válvula rápida das turbinas a vapor (subseção
+    /// `[maquina.valvula_rapida]`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valvula_rapida: Option<FastValving>,
+}
+
+impl MachineSettings {
+    /// Parâmetros informados, com o nome usado nos relatórios.
+    pub fn parametros(&self) -> Vec<(&'static str, f64)> {
+        [
+            ("Xd", self.xd),
+            ("T'd0", self.td0_transitoria),
+            ("Xq", self.xq),
+            ("x'q", self.xq_transitoria),
+            ("T'q0", self.tq0_transitoria),
+            ("x''d", self.xd_subtransitoria),
+            ("x''q", self.xq_subtransitoria),
+            ("T''d0", self.td0_subtransitoria),
+            ("T''q0", self.tq0_subtransitoria),
+        ]
+        .into_iter()
+        .filter_map(|(nome, valor)| valor.map(|v| (nome, v)))
+        .collect()
+    }
+}
+
+/// Método de integração numérica.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetodoIntegracao {
+    /// Runge-Kutta de 4ª ordem com passo fixo `delta_t`.
+    #[default]
+    Rk4,
+    /// Dormand–Prince 5(4) com passo adaptativo (ver [`crate::rk45::Rk45`]).
+    Rk45,
+}
+
+impl std::fmt::Display for MetodoIntegracao {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetodoIntegracao::Rk4 => write!(f, "rk4"),
+            MetodoIntegracao::Rk45 => write!(f, "rk45"),
+        }
+    }
+}
+
+/// Configurações da integração numérica.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IntegratorSettings {
+    /// Método de integração (`"rk4"` ou `"rk45"`).
+    pub metodo: MetodoIntegracao,
+    /// Tempo máximo de simulação (s).
+    pub t_max: f64,
+    /// Passo de integração (s); no RK45, passo inicial.
+    pub delta_t: f64,
+    /// Tolerância absoluta do RK45.
+    pub tolerancia_abs: f64,
+    /// Tolerância relativa do RK45.
+    pub tolerancia_rel: f64,
+    /// Maior passo do RK45 (s).
+    pub passo_max: f64,
+    /// Intervalo de amostragem da trajetória no RK45 (s), obtido pela saída
+    /// densa; se ausente, a trajetória registra os passos aceitos.
+    pub intervalo_saida: Option<f64>,
+}
+
+/// Configurações do PSO (ver [`crate::pso_config`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PsoSettings {
+    /// Limites `[tab_min, tab_max]` (s).
+    pub limites_tab: (f64, f64),
+    /// Limites `[tr_min, tr_max]` (s).
+    pub limites_tr: (f64, f64),
+    /// Coeficiente cognitivo.
+    pub c1: f64,
+    /// Coeficiente social.
+    pub c2: f64,
+    /// Tamanho da população.
+    pub population_size: usize,
+    /// Número máximo de iterações (padrão do pso_rs se ausente).
+    pub t_max: Option<usize>,
+    /// Critério de parada sobre a função objetivo.
+    pub tolerancia: f64,
+    /// Função objetivo (`"areas"` ou `"energia"`).
+    pub objetivo: ObjetivoPso,
+}
+
+/// Função objetivo do PSO (ver [`crate::objective_function`]).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ObjetivoPso {
+    /// Diferença entre as áreas de aceleração e desaceleração, com a
+    /// simulação completa até `t_max`.
+    #[default]
+    Areas,
+    /// Margem da função de energia transitória no último chaveamento (ver
+    /// [`crate::energia`]); integra só até o religamento e requer o modelo
+    /// clássico sem regulador de velocidade.
+    Energia,
+}
+
+impl std::fmt::Display for ObjetivoPso {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObjetivoPso::Areas => write!(f, "áreas iguais"),
+            ObjetivoPso::Energia => write!(f, "função de energia"),
+        }
+    }
+}
+
+/// Configurações da busca do tempo crítico de abertura (ver [`crate::cct`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CctSettings {
+    /// Intervalo inicial `[estável, instável]` da bisseção em `tab` (s).
+    pub limites: (f64, f64),
+    /// Largura final do intervalo da bisseção (s).
+    pub tolerancia: f64,
+    /// Instante de religamento (s); se ausente, a linha não é religada.
+    pub tr: Option<f64>,
+}
+
+/// Critérios de estabilidade aplicados a cada simulação (ver
+/// [`crate::estabilidade`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StabilitySettings {
+    /// Ângulo do rotor (graus) acima do qual a máquina é considerada fora de
+    /// sincronismo.
+    pub limite_angulo_graus: f64,
+    /// Interrompe a simulação assim que a instabilidade é certa.
+    pub interromper: bool,
+}
+
+impl Default for SystemParams {
+    fn default() -> Self {
+        SystemParams {
+            pe1: PE1.into(),
+            pe2: PE2.into(),
+            pe3: PE3.into(),
+            pm: PM,
+            f: F,
+            h: H,
+            d: D,
+            delta_w_ini: DELTA_W_INI,
+        }
+    }
+}
+
+impl Default for IntegratorSettings {
+    fn default() -> Self {
+        IntegratorSettings {
+            metodo: MetodoIntegracao::Rk4,
+            t_max: T_MAX,
+            delta_t: DELTA_T,
+            tolerancia_abs: 1e-8,
+            tolerancia_rel: 1e-6,
+            passo_max: 0.05,
+            intervalo_saida: None,
+        }
+    }
+}
+
+impl Default for PsoSettings {
+    fn default() -> Self {
+        PsoSettings {
+            limites_tab: (0.15, 0.15 + 1e-10),
+            limites_tr: (0.15, 5.0),
+            c1: 2.05,
+            c2: 2.05,
+            population_size: 1000,
+            t_max: None,
+            tolerancia: 1e-4,
+            objetivo: ObjetivoPso::Areas,
+        }
+    }
+}
+
+impl Default for CctSettings {
+    fn default() -> Self {
+        CctSettings {
+            limites: (0.0, 1.0),
+            tolerancia: 1e-4,
+            tr: None,
+        }
+    }
+}
+
+impl Default for StabilitySettings {
+    fn default() -> Self {
+        StabilitySettings {
+            limite_angulo_graus: 180.0,
+            interromper: false,
+        }
+    }
+}
+
+impl SystemParams {
+    /// Ângulo inicial de equilíbrio pré-falta: interseção estável de Pe1 com
+    /// Pm (`asin(Pm / Pe1)` na curva senoidal); `NaN` se Pe1 não atinge Pm.
+    pub fn delta_n_ini(&self) -> f64 {
+        self.pe1
+            .intersecoes(self.pm)
+            .map_or(f64::NAN, |(delta, _)| delta)
+    }
+}
+
+impl Case {
+    /// Carrega um caso a partir de um arquivo `.toml` ou `.json` (decidido pela
+    /// extensão), aplica a seção `[rede]` e importa a seção `[matpower]` ou
+    /// `[psse]`, se houver. O PSO multimáquinas (`otimizar = true`) exige
+    /// `t_max` em `[pso]`.
+    pub fn carregar(caminho: &Path) -> Result<Case> {
+        let conteudo = fs::read_to_string(caminho).map_err(Error::io(caminho))?;
+        let case: std::result::Result<Case, String> =
+            match caminho.extension().and_then(|e| e.to_str()) {
+                Some("json") => serde_json::from_str(&conteudo).map_err(|e| e.to_string()),
+                _ => toml::from_str(&conteudo).map_err(|e| e.to_string()),
+            };
+        let mut case = case.map_err(|mensagem| Error::Caso {
+            caminho: caminho.to_path_buf(),
+            mensagem,
+        })?;
+        // Montar o modelo do gerador verifica também a inicialização da
+        // excitatriz e do regulador
+        case.aplicar_rede()
+            .and_then(|_| case.validar_maquina())
+            .and_then(|_| modelo_maquina(&case).map(|_| ()))
+            .map_err(|e| Error::Caso {
+                caminho: caminho.to_path_buf(),
+                mensagem: e.to_string(),
+            })?;
+        let secoes = [
+            case.multimaquina.is_some(),
+            case.matpower.is_some(),
+            case.psse.is_some(),
+        ];
+        if secoes.iter().filter(|&&presente| presente).count() > 1 {
+            return Err(Error::Caso {
+                caminho: caminho.to_path_buf(),
+                mensagem: "as seções [multimaquina], [matpower] e [psse] são exclusivas"
+                    .to_string(),
+            });
+        }
+        let pasta = caminho.parent().unwrap_or(Path::new(""));
+        if let Some(importacao) = &case.matpower {
+            let mut sistema = importar_matpower(
+                &pasta.join(&importacao.arquivo),
+                &pasta.join(&importacao.dinamica),
+            )?;
+            sistema.tab = importacao.tab;
+            sistema.tr = importacao.tr;
+            sistema.otimizar = importacao.otimizar;
+            sistema.eventos = importacao.eventos.clone();
+            case.multimaquina = Some(sistema);
+        }
+        if let Some(importacao) = &case.psse {
+            let mut sistema =
+                importar_psse(&pasta.join(&importacao.raw), &pasta.join(&importacao.dyr))?;
+            sistema.tab = importacao.tab;
+            sistema.tr = importacao.tr;
+            sistema.otimizar = importacao.otimizar;
+            sistema.eventos = importacao.eventos.clone();
+            case.multimaquina = Some(sistema);
+        }
+        // A separação máxima salta na fronteira de estabilidade, então a
+        // função objetivo multimáquinas em geral não atinge a tolerância
+        if case.multimaquina.as_ref().is_some_and(|s| s.otimizar) && case.pso.t_max.is_none() {
+            return Err(Error::Caso {
+                caminho: caminho.to_path_buf(),
+                mensagem: "otimizar = true requer t_max em [pso]: no sistema multimáquinas a \
+                           tolerância em geral não encerra a busca"
+                    .to_string(),
+            });
+        }
+        Ok(case)
+    }
+
+    /// Substitui Pe1, Pe2 e Pe3 de `[sistema]` pelas curvas calculadas da rede
+    /// SMIB com o carregamento `pm`, se o caso tiver `[rede]`.
+    pub fn aplicar_rede(&mut self) -> Result<Option<ResultadoRede>> {
+        let Some(rede) = &self.rede else {
+            return Ok(None);
+        };
+        let resultado = rede.calcular(self.sistema.pm)?;
+        self.sistema.pe1 = resultado.pe1;
+        self.sistema.pe2 = resultado.pe2;
+        self.sistema.pe3 = resultado.pe3;
+        Ok(Some(resultado))
+    }
+
+    /// Verifica se o modelo do gerador em `[maquina]` tem os dados de que
+    /// precisa: a partir do modelo de um eixo, a seção `[rede]`, `Xd > x'd` e
+    /// `T'd0 > 0`; a partir do de dois eixos, `Xq ≥ x'q > 0` e `T'q0 > 0`; no
+    /// de sexta ordem, `0 < x''d < x'd`, `0 < x''q < x'q` e constantes de
+    /// tempo subtransitórias positivas. A excitatriz, se houver, requer um
+    /// modelo com enrolamento de campo (não clássico) e parâmetros válidos; o
+    /// estabilizador, uma excitatriz e parâmetros válidos; o regulador e a
+    /// válvula rápida, parâmetros válidos, e a válvula rápida, uma turbina a
+    /// vapor. O objetivo `"energia"` do PSO requer o modelo clássico sem
+    /// regulador nem válvula rápida e uma rede final com ponto de equilíbrio.
+    pub fn validar_maquina(&self) -> Result<()> {
+        let maquina = &self.maquina;
+        let modelo = maquina.modelo;
+        if self.pso.objetivo == ObjetivoPso::Energia {
+            if verificar_modelo(self).is_err() {
+                return Err(Error::Configuracao(
+                    "objetivo = \"energia\" em [pso] requer o modelo clássico sem \
+                     [maquina.regulador] nem [maquina.valvula_rapida]"
+                        .to_string(),
+                ));
+            }
+            // A rede final, após todos os eventos, não depende da partícula
+            let (tab, tr) = (self.pso.limites_tab.0, self.pso.limites_tr.1);
+            if margem_energia(self, tab, tr).is_err() {
+                return Err(Error::Configuracao(
+                    "objetivo = \"energia\" em [pso] requer que a rede após o último \
+                     chaveamento tenha ponto de equilíbrio (curva que atinge Pm)"
+                        .to_string(),
+                ));
+            }
+        }
+        if let Some(regulador) = &maquina.regulador {
+            regulador.validar()?;
+        }
+        if let Some(valvula) = &maquina.valvula_rapida {
+            valvula.validar()?;
+            if maquina.regulador.as_ref().is_some_and(|r| !r.vapor()) {
+                return Err(Error::Configuracao(
+                    "[maquina.valvula_rapida] requer uma turbina a vapor".to_string(),
+                ));
+            }
+        }
+        if let Some(excitatriz) = &maquina.excitatriz {
+            if modelo == ModeloMaquina::Classico {
+                return Err(Error::Configuracao(
+                    "[maquina.excitatriz] requer um modelo do gerador com enrolamento de campo \
+                     (um_eixo, dois_eixos ou sexta_ordem)"
+                        .to_string(),
+                ));
+            }
+            excitatriz.validar()?;
+        }
+        if let Some(estabilizador) = &maquina.estabilizador {
+            if maquina.excitatriz.is_none() {
+                return Err(Error::Configuracao(
+                    "[maquina.estabilizador] requer [maquina.excitatriz]".to_string(),
+                ));
+            }
+            estabilizador.validar()?;
+        }
+        if modelo == ModeloMaquina::Classico {
+            return Ok(());
+        }
+        let Some(rede) = &self.rede else {
+            return Err(Error::Configuracao(format!(
+                "o modelo {} requer a seção [rede]",
+                modelo
+            )));
+        };
+        let exigir = |nome: &str, valor: Option<f64>| {
+            valor.ok_or_else(|| {
+                Error::Configuracao(format!("o modelo {} requer {} em [maquina]", modelo, nome))
+            })
+        };
+        let verificar = |valido: bool, condicao: &str| {
+            if valido {
+                Ok(())
+            } else {
+                Err(Error::Configuracao(format!(
+                    "o modelo {} requer {} em [maquina]",
+                    modelo, condicao
+                )))
+            }
+        };
+
+        let xd_transitoria = rede.xd_transitoria;
+        let xd = exigir("xd", maquina.xd)?;
+        let td0 = exigir("td0_transitoria", maquina.td0_transitoria)?;
+        verificar(
+            xd > xd_transitoria,
+            "xd maior que o xd_transitoria de [rede]",
+        )?;
+        verificar(td0 > 0.0, "td0_transitoria positivo")?;
+        if modelo == ModeloMaquina::UmEixo {
+            return Ok(());
+        }
+
+        let xq = exigir("xq", maquina.xq)?;
+        let xq_transitoria = exigir("xq_transitoria", maquina.xq_transitoria)?;
+        let tq0 = exigir("tq0_transitoria", maquina.tq0_transitoria)?;
+        verificar(
+            xq >= xq_transitoria && xq_transitoria > 0.0,
+            "0 < xq_transitoria <= xq",
+        )?;
+        verificar(tq0 > 0.0, "tq0_transitoria positivo")?;
+        if modelo == ModeloMaquina::DoisEixos {
+            return Ok(());
+        }
+
+        let xd_sub = exigir("xd_subtransitoria", maquina.xd_subtransitoria)?;
+        let xq_sub = maquina.xq_subtransitoria.unwrap_or(xd_sub);
+        let td0_sub = exigir("td0_subtransitoria", maquina.td0_subtransitoria)?;
+        let tq0_sub = exigir("tq0_subtransitoria", maquina.tq0_subtransitoria)?;
+        verificar(
+            xd_sub > 0.0 && xd_sub < xd_transitoria,
+            "0 < xd_subtransitoria < xd_transitoria",
+        )?;
+        verificar(
+            xq_sub > 0.0 && xq_sub < xq_transitoria,
+            "0 < xq_subtransitoria < xq_transitoria",
+        )?;
+        verificar(
+            td0_sub > 0.0 && tq0_sub > 0.0,
+            "td0_subtransitoria e tq0_subtransitoria positivos",
+        )
+    }
+
+    /// Copia o arquivo de caso sem alterações para a pasta de saída e retorna o
+    /// caminho da cópia. Os arquivos importados por `[matpower]` e `[psse]` são
+    /// copiados junto, no mesmo caminho relativo, para que a cópia possa ser
+    /// carregada de novo; os que ficam fora da pasta do caso (caminho absoluto
+    /// ou com `..`) vão para a pasta de saída, e a cópia do caso é regravada
+    /// apontando para eles. Sem arquivo de origem (caso padrão), grava o caso
+    /// serializado em `caso.toml`.
+    pub fn copiar_para(&self, origem: Option<&Path>, pasta_saida: &Path) -> Result<PathBuf> {
+        let Some(origem) = origem else {
+            let destino = pasta_saida.join("caso.toml");
+            self.gravar(&destino)?;
+            return Ok(destino);
+        };
+        let destino = pasta_saida.join(origem.file_name().unwrap_or("caso.toml".as_ref()));
+        let pasta_origem = origem.parent().unwrap_or(Path::new(""));
+        let mut copia = self.clone();
+        let mut regravar = false;
+        for arquivo in copia.arquivos_importados() {
+            let interno = arquivo
+                .components()
+                .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+            let relativo = if interno {
+                arquivo.clone()
+            } else {
+                regravar = true;
+                PathBuf::from(arquivo.file_name().unwrap_or_default())
+            };
+            let alvo = pasta_saida.join(&relativo);
+            if let Some(pasta) = alvo.parent() {
+                fs::create_dir_all(pasta).map_err(Error::io(pasta))?;
+            }
+            let fonte = pasta_origem.join(&*arquivo);
+            fs::copy(&fonte, &alvo).map_err(Error::io(&fonte))?;
+            *arquivo = relativo;
+        }
+        if regravar {
+            // O sistema importado é refeito a partir dos arquivos ao carregar
+            copia.multimaquina = None;
+            copia.gravar(&destino)?;
+        } else {
+            fs::copy(origem, &destino).map_err(Error::io(&destino))?;
+        }
+        Ok(destino)
+    }
+
+    // Arquivos referenciados pelas seções de importação, relativos ao arquivo
+    // de caso
+    fn arquivos_importados(&mut self) -> Vec<&mut PathBuf> {
+        let mut arquivos = Vec::new();
+        if let Some(importacao) = &mut self.matpower {
+            arquivos.push(&mut importacao.arquivo);
+            arquivos.push(&mut importacao.dinamica);
+        }
+        if let Some(importacao) = &mut self.psse {
+            arquivos.push(&mut importacao.raw);
+            arquivos.push(&mut importacao.dyr);
+        }
+        arquivos
+    }
+
+    // Grava o caso serializado em JSON ou TOML, conforme a extensão de `destino`
+    fn gravar(&self, destino: &Path) -> Result<()> {
+        let conteudo = match destino.extension().and_then(|e| e.to_str()) {
+            Some("json") => serde_json::to_string_pretty(self).map_err(|e| e.to_string()),
+            _ => toml::to_string_pretty(self).map_err(|e| e.to_string()),
+        };
+        let conteudo = conteudo.map_err(|mensagem| Error::Caso {
+            caminho: destino.to_path_buf(),
+            mensagem,
+        })?;
+        fs::write(destino, conteudo).map_err(Error::io(destino))
+    }
+}
+
+// Caso padrão sem amortecimento e com Pe3 = 1,6 p.u., comum aos testes: com a
+// abertura definitiva, o critério das áreas iguais é exato
+#[cfg(test)]
+pub(crate) fn caso_sem_amortecimento() -> Case {
+    let mut case = Case::default();
+    case.sistema.pe3 = 1.6.into();
+    case.sistema.d = 0.0;
+    case
+}
+
+// Caso com a rede SMIB do README e o gerador da seção `maquina` (TOML da seção
+// `[maquina]` e das subseções), com as curvas da rede aplicadas
+#[cfg(test)]
+pub(crate) fn caso_com_rede(maquina: &str) -> Case {
+    let mut case: Case = toml::from_str(&format!(
+        "[rede]\n\
+         xd_transitoria = 0.3\n\
+         xt = 0.2\n\
+         x_linhas = [0.3, 0.3]\n\
+         q = 0.074\n\
+         posicao_falta = 0.5\n\
+         {}",
+        maquina
+    ))
+    .unwrap();
+    case.aplicar_rede().unwrap();
+    case
+}
@@ -0,0 +1,327 @@
+//! Tempo crítico de abertura (CCT) por bisseção sobre a simulação no tempo.
+//!
+//! O instante de abertura `tab` é bissectado entre um valor estável e um
+//! instável, usando [`sim_pet_time`] com a sequência de eventos do caso e o
+//! religamento em `cct.tr` (ou sem religamento), e o veredito de
+//! [`crate::estabilidade`]. O resultado é comparado com o
+//! ângulo crítico analítico do critério das áreas iguais
+//! ([`angulo_critico_abertura`]) e, no modelo clássico, com a estimativa do
+//! método direto ([`estimar_cct`]).
+//!
+//! Em casos multimáquinas ([`cct_multimaquina`]), a mesma bisseção usa
+//! [`sim_multimaquina`] e o veredito pela separação angular entre os
+//! geradores, sem comparação analítica.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use crate::area::angulo_critico_abertura;
+use crate::case::{Case, CctSettings};
+use crate::energia::{EstimativaCct, estimar_cct};
+use crate::error::{Error, Result};
+use crate::estabilidade::Veredito;
+use crate::eventos;
+use crate::maquina::modelo_maquina;
+use crate::multimaquina::sim_multimaquina;
+use crate::sim_per_time::{primeiro_cruzamento, sim_pet_time};
+use crate::swing::DELTA;
+
+/// Resultado da busca do tempo crítico de abertura.
+#[derive(Debug, Clone)]
+pub struct ResultadoCct {
+    /// Tempo crítico de abertura (s): maior `tab` estável encontrado.
+    pub cct: f64,
+    /// Intervalo final da bisseção `[estável, instável]` (s).
+    pub intervalo: (f64, f64),
+    /// Ângulo do rotor na abertura em `cct` (rad).
+    pub angulo_critico: f64,
+    /// Ângulo crítico de abertura pelo critério das áreas iguais (rad), quando
+    /// existe (ver [`angulo_critico_abertura`]).
+    pub angulo_critico_analitico: Option<f64>,
+    /// Instante em que a trajetória com falta mantida atinge o ângulo crítico
+    /// analítico (s).
+    pub cct_analitico: Option<f64>,
+    /// Estimativa pela função de energia transitória, quando o caso a admite
+    /// e a rede após a abertura tem equilíbrio (ver [`estimar_cct`]).
+    pub cct_energia: Option<EstimativaCct>,
+    /// Número de simulações realizadas.
+    pub simulacoes: usize,
+}
+
+/// Resultado da busca do tempo crítico de abertura de um caso multimáquinas.
+#[derive(Debug, Clone)]
+pub struct ResultadoCctMultimaquina {
+    /// Tempo crítico de abertura (s): maior `tab` estável encontrado.
+    pub cct: f64,
+    /// Intervalo final da bisseção `[estável, instável]` (s).
+    pub intervalo: (f64, f64),
+    /// Maior separação angular entre dois geradores com abertura em `cct`
+    /// (rad).
+    pub separacao_max: f64,
+    /// Número de simulações realizadas.
+    pub simulacoes: usize,
+}
+
+/// Busca o tempo crítico de abertura do caso por bisseção em `tab`, dentro de
+/// `cct.limites` e até a largura `cct.tolerancia`.
+///
+/// A comparação com o critério das áreas iguais usa Pe1, Pe2 e Pe3 do sistema
+/// e só é exata para a sequência padrão sem religamento e sem amortecimento.
+pub fn critical_clearing_time(case: &Case) -> Result<ResultadoCct> {
+    let config = &case.cct;
+    let tr = config.tr.unwrap_or(f64::INFINITY);
+    // Simulações instáveis param assim que a perda de sincronismo é certa
+    let mut case_busca = case.clone();
+    case_busca.estabilidade.interromper = true;
+    let mut simulacoes = 0;
+    // O limite estável só cresce: o último ângulo guardado é o do CCT
+    let mut angulo_critico = f64::NAN;
+    let (estavel, instavel) = bissectar(config, |tab| {
+        simulacoes += 1;
+        let sim = sim_pet_time(&case_busca, tab, tr)?;
+        let perdeu = !sim.estabilidade.estavel();
+        if !perdeu && let Some(cra) = sim.cra {
+            angulo_critico = cra;
+        }
+        Ok(perdeu)
+    })?;
+
+    let sistema = &case.sistema;
+    let angulo_critico_analitico =
+        angulo_critico_abertura(sistema.pe1, sistema.pe2, sistema.pe3, sistema.pm);
+    // Instante correspondente na trajetória com a falta mantida
+    let cct_analitico = angulo_critico_analitico.and_then(|delta_c| {
+        simulacoes += 1;
+        instante_do_angulo(case, delta_c)
+    });
+    let cct_energia = estimar_cct(case).ok();
+    if cct_energia.is_some() {
+        simulacoes += 1;
+    }
+
+    Ok(ResultadoCct {
+        cct: estavel,
+        intervalo: (estavel, instavel),
+        angulo_critico,
+        angulo_critico_analitico,
+        cct_analitico,
+        cct_energia,
+        simulacoes,
+    })
+}
+
+/// Busca o tempo crítico de abertura de um caso multimáquinas por bisseção
+/// em `tab`, como [`critical_clearing_time`], com o religamento em `cct.tr`
+/// (ou sem religamento) aplicado aos eventos com `apos = "tr"`. A simulação é
+/// instável quando a separação angular entre dois geradores passa de
+/// `limite_angulo_graus`.
+pub fn cct_multimaquina(case: &Case) -> Result<ResultadoCctMultimaquina> {
+    let config = &case.cct;
+    let tr = config.tr.unwrap_or(f64::INFINITY);
+    let mut case_busca = case.clone();
+    case_busca.estabilidade.interromper = true;
+    let mut simulacoes = 0;
+    let mut separacao_max = f64::NAN;
+    let (estavel, instavel) = bissectar(config, |tab| {
+        simulacoes += 1;
+        let sim = sim_multimaquina(&case_busca, tab, tr)?;
+        let perdeu = sim.veredito != Veredito::Estavel;
+        if !perdeu {
+            separacao_max = sim.separacao_max;
+        }
+        Ok(perdeu)
+    })?;
+    Ok(ResultadoCctMultimaquina {
+        cct: estavel,
+        intervalo: (estavel, instavel),
+        separacao_max,
+        simulacoes,
+    })
+}
+
+// Bisseção de `tab` em `cct.limites` até a largura `cct.tolerancia`, com
+// `perdeu(tab)` indicando a perda de sincronismo. Retorna o intervalo final
+// `[estável, instável]`
+fn bissectar(
+    config: &CctSettings,
+    mut perdeu: impl FnMut(f64) -> Result<bool>,
+) -> Result<(f64, f64)> {
+    let (mut estavel, mut instavel) = config.limites;
+    if perdeu(estavel)? {
+        return Err(Error::Cct(format!(
+            "sistema instável mesmo com abertura em {:.4}s",
+            estavel
+        )));
+    }
+    if !perdeu(instavel)? {
+        return Err(Error::Cct(format!(
+            "sistema estável com abertura em {:.4}s; aumente o limite superior",
+            instavel
+        )));
+    }
+    while instavel - estavel > config.tolerancia {
+        let tab = 0.5 * (estavel + instavel);
+        if perdeu(tab)? {
+            instavel = tab;
+        } else {
+            estavel = tab;
+        }
+    }
+    Ok((estavel, instavel))
+}
+
+/// Calcula o CCT do caso e grava o relatório em `pasta_saida/cct.txt`, ao
+/// lado da cópia do caso.
+pub fn executar_cct(
+    case: &Case,
+    caminho_caso: Option<&Path>,
+    pasta_saida: &str,
+) -> Result<ResultadoCct> {
+    relatorio(
+        case,
+        caminho_caso,
+        pasta_saida,
+        critical_clearing_time,
+        |r| {
+            let mut texto = format!(
+                "CCT: {:.4}s (intervalo [{:.6}, {:.6}]s, {} simulações)\n\
+             Ângulo crítico simulado: {:.4}° / {:.6} rad\n",
+                r.cct,
+                r.intervalo.0,
+                r.intervalo.1,
+                r.simulacoes,
+                r.angulo_critico.to_degrees(),
+                r.angulo_critico
+            );
+            texto += &match (r.angulo_critico_analitico, r.cct_analitico) {
+                (Some(delta_c), tempo) => format!(
+                    "Ângulo crítico (áreas iguais): {:.4}° / {:.6} rad -> tempo: {}\n",
+                    delta_c.to_degrees(),
+                    delta_c,
+                    tempo.map_or("não atingido".to_string(), |t| format!("{:.4}s", t))
+                ),
+                (None, _) => "Ângulo crítico (áreas iguais): inexistente\n".to_string(),
+            };
+            texto += &match r.cct_energia {
+                Some(EstimativaCct {
+                    cct: Some(cct),
+                    angulo_critico: Some(delta_c),
+                    ..
+                }) => format!(
+                    "CCT (função de energia): {:.4}s (ângulo {:.4}° / {:.6} rad)\n",
+                    cct,
+                    delta_c.to_degrees(),
+                    delta_c
+                ),
+                Some(_) => "CCT (função de energia): energia crítica não atingida\n".to_string(),
+                None => "CCT (função de energia): indisponível\n".to_string(),
+            };
+            texto
+        },
+    )
+}
+
+/// Calcula o CCT de um caso multimáquinas ([`cct_multimaquina`]) e grava o
+/// relatório em `pasta_saida/cct.txt`, ao lado da cópia do caso.
+pub fn executar_cct_multimaquina(
+    case: &Case,
+    caminho_caso: Option<&Path>,
+    pasta_saida: &str,
+) -> Result<ResultadoCctMultimaquina> {
+    relatorio(case, caminho_caso, pasta_saida, cct_multimaquina, |r| {
+        format!(
+            "CCT: {:.4}s (intervalo [{:.6}, {:.6}]s, {} simulações)\n\
+             Separação angular máxima em CCT: {:.4}° / {:.6} rad\n",
+            r.cct,
+            r.intervalo.0,
+            r.intervalo.1,
+            r.simulacoes,
+            r.separacao_max.to_degrees(),
+            r.separacao_max
+        )
+    })
+}
+
+// Copia o caso para `pasta_saida`, executa a busca e grava `cct.txt` com o
+// cabeçalho, a descrição do resultado ou a falha da busca
+fn relatorio<T>(
+    case: &Case,
+    caminho_caso: Option<&Path>,
+    pasta_saida: &str,
+    buscar: impl FnOnce(&Case) -> Result<T>,
+    descrever: impl FnOnce(&T) -> String,
+) -> Result<T> {
+    fs::create_dir_all(pasta_saida).map_err(Error::io(pasta_saida))?;
+    let destino = case.copiar_para(caminho_caso, Path::new(pasta_saida))?;
+    println!("Caso copiado para: {}", destino.display());
+    let caminho = format!("{}/cct.txt", pasta_saida);
+    let mut arquivo = fs::File::create(&caminho).map_err(Error::io(&caminho))?;
+
+    let mut texto = String::from("=== TEMPO CRÍTICO DE ABERTURA (CCT) ===\n");
+    texto += &match case.cct.tr {
+        Some(tr) => format!("Religamento em: {:.4}s\n", tr),
+        None => "Sem religamento\n".to_string(),
+    };
+    let resultado = buscar(case);
+    if let Ok(r) = &resultado {
+        texto += &descrever(r);
+    }
+
+    // A falha da busca vai só para o arquivo: quem chamou já a reporta
+    print!("{}", texto);
+    if let Err(e) = &resultado {
+        texto += &format!("{}\n", e);
+    }
+    arquivo
+        .write_all(texto.as_bytes())
+        .map_err(Error::io(&caminho))?;
+    resultado
+}
+
+// Primeiro instante em que o ângulo da trajetória com a falta mantida atinge
+// `delta`, refinado dentro do passo que o contém
+fn instante_do_angulo(case: &Case, delta: f64) -> Option<f64> {
+    let mut maquina = modelo_maquina(case).ok()?;
+    let x = maquina.estado_inicial(&case.sistema);
+    let chaveamentos =
+        eventos::resolver(&case.eventos, &case.sistema, f64::INFINITY, f64::INFINITY);
+    primeiro_cruzamento(maquina.as_mut(), x, &chaveamentos, &case.integrador, |x| {
+        x[DELTA] - delta
+    })
+    .map(|(t, _)| t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::case::MetodoIntegracao;
+    use crate::case::caso_sem_amortecimento;
+
+    #[test]
+    fn bissecao_coincide_com_as_areas_iguais() {
+        for metodo in [MetodoIntegracao::Rk4, MetodoIntegracao::Rk45] {
+            let mut case = caso_sem_amortecimento();
+            case.integrador.metodo = metodo;
+            let r = critical_clearing_time(&case).unwrap();
+            let delta_c = r.angulo_critico_analitico.unwrap();
+            let t_c = r.cct_analitico.unwrap();
+            // O instante analítico fica no intervalo final da bisseção
+            assert!(
+                r.intervalo.0 - 1e-4 <= t_c && t_c <= r.intervalo.1 + 1e-4,
+                "{:?}: {} fora de {:?}",
+                metodo,
+                t_c,
+                r.intervalo
+            );
+            assert!((r.angulo_critico - delta_c).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn limite_inferior_instavel_e_rejeitado() {
+        let mut case = caso_sem_amortecimento();
+        case.cct.limites = (0.3, 1.0);
+        assert!(matches!(critical_clearing_time(&case), Err(Error::Cct(_))));
+    }
+}
@@ -0,0 +1,99 @@
+//! Aritmética de números complexos usada nos fasores e matrizes de admitância.
+
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+/// Número complexo `re + j·im`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct Complexo {
+    pub(crate) re: f64,
+    pub(crate) im: f64,
+}
+
+impl Complexo {
+    pub(crate) const ZERO: Complexo = Complexo { re: 0.0, im: 0.0 };
+
+    pub(crate) fn new(re: f64, im: f64) -> Self {
+        Complexo { re, im }
+    }
+
+    // Fasor de módulo `modulo` e ângulo `angulo` (rad)
+    pub(crate) fn polar(modulo: f64, angulo: f64) -> Self {
+        Complexo::new(modulo * angulo.cos(), modulo * angulo.sin())
+    }
+
+    pub(crate) fn conj(self) -> Self {
+        Complexo::new(self.re, -self.im)
+    }
+
+    pub(crate) fn abs(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    pub(crate) fn arg(self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    // Inverso multiplicativo; infinito para zero
+    pub(crate) fn inv(self) -> Self {
+        let modulo2 = self.re * self.re + self.im * self.im;
+        Complexo::new(self.re / modulo2, -self.im / modulo2)
+    }
+}
+
+impl Add for Complexo {
+    type Output = Complexo;
+    fn add(self, outro: Complexo) -> Complexo {
+        Complexo::new(self.re + outro.re, self.im + outro.im)
+    }
+}
+
+impl Sub for Complexo {
+    type Output = Complexo;
+    fn sub(self, outro: Complexo) -> Complexo {
+        Complexo::new(self.re - outro.re, self.im - outro.im)
+    }
+}
+
+impl Mul for Complexo {
+    type Output = Complexo;
+    fn mul(self, outro: Complexo) -> Complexo {
+        Complexo::new(
+            self.re * outro.re - self.im * outro.im,
+            self.re * outro.im + self.im * outro.re,
+        )
+    }
+}
+
+impl Mul<f64> for Complexo {
+    type Output = Complexo;
+    fn mul(self, k: f64) -> Complexo {
+        Complexo::new(self.re * k, self.im * k)
+    }
+}
+
+impl Div for Complexo {
+    type Output = Complexo;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, outro: Complexo) -> Complexo {
+        self * outro.inv()
+    }
+}
+
+impl Neg for Complexo {
+    type Output = Complexo;
+    fn neg(self) -> Complexo {
+        Complexo::new(-self.re, -self.im)
+    }
+}
+
+impl AddAssign for Complexo {
+    fn add_assign(&mut self, outro: Complexo) {
+        *self = *self + outro;
+    }
+}
+
+impl SubAssign for Complexo {
+    fn sub_assign(&mut self, outro: Complexo) {
+        *self = *self - outro;
+    }
+}
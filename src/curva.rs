@@ -0,0 +1,128 @@
+//! Curva potência-ângulo `Pe(δ) = Pc + Pmax·sin(δ - γ)`.
+//!
+//! Redes reduzidas com cargas em derivação ou condutância de transferência
+//! resultam em um termo constante `Pc = E²·G` e em uma defasagem `γ`; a curva
+//! clássica `Pmax·sin(δ)` é o caso `Pc = γ = 0`.
+
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+use std::fmt;
+
+/// Curva potência-ângulo `Pe(δ) = pc + pmax·sin(δ - gamma)`.
+///
+/// No arquivo de caso, aceita tanto uma amplitude (`pe1 = 1.83333`) quanto a
+/// curva completa (`pe1 = { pmax = 1.8, gamma = 0.05, pc = 0.1 }`, com `gamma`
+/// em radianos).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(from = "CurvaArquivo", into = "CurvaArquivo")]
+pub struct PowerAngleCurve {
+    /// Termo constante `E²·G` (p.u.).
+    pub pc: f64,
+    /// Amplitude da parcela senoidal (p.u.).
+    pub pmax: f64,
+    /// Defasagem γ (rad).
+    pub gamma: f64,
+}
+
+impl PowerAngleCurve {
+    /// Curva clássica `pmax·sin(δ)`.
+    pub fn senoidal(pmax: f64) -> Self {
+        PowerAngleCurve {
+            pc: 0.0,
+            pmax,
+            gamma: 0.0,
+        }
+    }
+
+    /// Potência elétrica no ângulo `delta` (rad).
+    pub fn potencia(&self, delta: f64) -> f64 {
+        self.pc + self.pmax * (delta - self.gamma).sin()
+    }
+
+    /// Derivada `dPe/dδ` (potência sincronizante).
+    pub fn derivada(&self, delta: f64) -> f64 {
+        self.pmax * (delta - self.gamma).cos()
+    }
+
+    /// Maior potência elétrica da curva, `pc + |pmax|`.
+    pub fn maximo(&self) -> f64 {
+        self.pc + self.pmax.abs()
+    }
+
+    /// Área de aceleração `∫(Pm - Pe(δ)) dδ` de `de` até `ate`, em forma
+    /// fechada. É negativa onde a curva desacelera (`Pe(δ) > Pm`).
+    pub fn area_aceleracao(&self, pm: f64, de: f64, ate: f64) -> f64 {
+        (pm - self.pc) * (ate - de)
+            + self.pmax * ((ate - self.gamma).cos() - (de - self.gamma).cos())
+    }
+
+    /// Interseções com a reta `Pm`: equilíbrio estável δs e instável δu, com
+    /// `δs = γ + asin((Pm - Pc)/Pmax)` e `δu = γ + π - asin((Pm - Pc)/Pmax)`.
+    /// Retorna `None` quando a curva não atinge `Pm`.
+    pub fn intersecoes(&self, pm: f64) -> Option<(f64, f64)> {
+        let razao = (pm - self.pc) / self.pmax;
+        if self.pmax <= 0.0 || razao.abs() > 1.0 {
+            return None;
+        }
+        let arco = razao.asin();
+        Some((self.gamma + arco, self.gamma + PI - arco))
+    }
+}
+
+impl From<f64> for PowerAngleCurve {
+    fn from(pmax: f64) -> Self {
+        PowerAngleCurve::senoidal(pmax)
+    }
+}
+
+impl fmt::Display for PowerAngleCurve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.pc == 0.0 && self.gamma == 0.0 {
+            return write!(f, "{:.5}", self.pmax);
+        }
+        write!(
+            f,
+            "{:.5} + {:.5}·sin(δ - {:.4}°)",
+            self.pc,
+            self.pmax,
+            self.gamma.to_degrees()
+        )
+    }
+}
+
+// Representação no arquivo de caso: amplitude ou curva completa
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum CurvaArquivo {
+    Amplitude(f64),
+    Completa {
+        pmax: f64,
+        #[serde(default)]
+        gamma: f64,
+        #[serde(default)]
+        pc: f64,
+    },
+}
+
+impl From<CurvaArquivo> for PowerAngleCurve {
+    fn from(curva: CurvaArquivo) -> Self {
+        match curva {
+            CurvaArquivo::Amplitude(pmax) => PowerAngleCurve::senoidal(pmax),
+            CurvaArquivo::Completa { pmax, gamma, pc } => PowerAngleCurve { pc, pmax, gamma },
+        }
+    }
+}
+
+impl From<PowerAngleCurve> for CurvaArquivo {
+    fn from(curva: PowerAngleCurve) -> Self {
+        if curva.pc == 0.0 && curva.gamma == 0.0 {
+            CurvaArquivo::Amplitude(curva.pmax)
+        } else {
+            CurvaArquivo::Completa {
+                pmax: curva.pmax,
+                gamma: curva.gamma,
+                pc: curva.pc,
+            }
+        }
+    }
+}
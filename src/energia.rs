@@ -0,0 +1,329 @@
+//! Função de energia transitória (método direto de Lyapunov) do modelo
+//! clássico.
+//!
+//! Na rede pós-falta com curva `Pe(δ)` e potência mecânica `Pm`, a energia
+//! transitória é
+//!
+//! `V(δ, Δω) = ½·M·Δω² - ∫(Pm - Pe(u)) du`, de δs até δ,
+//!
+//! soma da energia cinética e da energia potencial em relação ao equilíbrio
+//! estável δs. A energia crítica é a energia potencial no equilíbrio instável
+//! de controle: δu (`π - asin(Pm/Pe)` na curva senoidal) quando o rotor
+//! avança, ou `δu - 2π` quando recua. Com energia abaixo da crítica e o
+//! ângulo entre os dois equilíbrios instáveis, o sistema pós-falta é estável
+//! sem que sua trajetória precise ser integrada.
+//!
+//! O amortecimento `D` não entra em `V`: na rede pós-falta ele só dissipa
+//! energia, então o método direto é conservador quando `D > 0`. Sem
+//! amortecimento, a margem de energia é `Área 3 - (Área 1 + Área 2)` do
+//! critério das áreas iguais, e o CCT estimado coincide com o tempo do ângulo
+//! crítico analítico.
+
+use std::f64::consts::PI;
+
+use crate::case::{Case, ModeloMaquina};
+use crate::curva::PowerAngleCurve;
+use crate::error::{Error, Result};
+use crate::eventos::{self, Chaveamento};
+use crate::maquina::MachineModel;
+use crate::sim_per_time::{Avanco, Registro, primeiro_cruzamento};
+use crate::swing::{DELTA, DELTA_W, SwingEquation};
+
+/// Função de energia transitória da equação de oscilação em uma rede.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FuncaoEnergia {
+    /// Coeficiente de inércia `M = H / (π·f)`.
+    pub m: f64,
+    /// Potência mecânica (p.u.).
+    pub pm: f64,
+    /// Curva potência-ângulo da rede.
+    pub pe: PowerAngleCurve,
+    /// Equilíbrio estável δs (rad), referência da energia potencial.
+    pub delta_s: f64,
+    /// Equilíbrio instável δu (rad) à frente de δs.
+    pub delta_u: f64,
+}
+
+impl FuncaoEnergia {
+    /// Função de energia da equação de oscilação na rede e na potência
+    /// mecânica em vigor em `swing`.
+    ///
+    /// # Errors
+    ///
+    /// Se a curva da rede não atinge `Pm` (rede sem ponto de equilíbrio).
+    pub fn new(swing: &SwingEquation) -> Result<Self> {
+        let (delta_s, delta_u) = swing.pe.intersecoes(swing.pm).ok_or_else(|| {
+            Error::Configuracao(format!(
+                "a curva {} p.u. não atinge Pm = {:.4} p.u.: rede pós-falta sem equilíbrio",
+                swing.pe, swing.pm
+            ))
+        })?;
+        Ok(FuncaoEnergia {
+            m: swing.m,
+            pm: swing.pm,
+            pe: swing.pe,
+            delta_s,
+            delta_u,
+        })
+    }
+
+    /// Energia cinética `½·M·Δω²`.
+    pub fn cinetica(&self, delta_w: f64) -> f64 {
+        0.5 * self.m * delta_w * delta_w
+    }
+
+    /// Energia potencial `-∫(Pm - Pe) dδ` de δs até `delta`.
+    pub fn potencial(&self, delta: f64) -> f64 {
+        -self.pe.area_aceleracao(self.pm, self.delta_s, delta)
+    }
+
+    /// Energia transitória `V(δ, Δω)`.
+    pub fn energia(&self, delta: f64, delta_w: f64) -> f64 {
+        self.cinetica(delta_w) + self.potencial(delta)
+    }
+
+    /// Equilíbrio instável de controle para o rotor no ângulo `delta` com
+    /// desvio de velocidade `delta_w`: δu quando avança, `δu - 2π` quando
+    /// recua.
+    pub fn equilibrio_controle(&self, delta: f64, delta_w: f64) -> f64 {
+        if delta_w > 0.0 || (delta_w == 0.0 && delta >= self.delta_s) {
+            self.delta_u
+        } else {
+            self.delta_u - 2.0 * PI
+        }
+    }
+
+    /// Energia crítica: energia potencial no equilíbrio instável de controle
+    /// (ver [`FuncaoEnergia::equilibrio_controle`]).
+    pub fn critica(&self, delta: f64, delta_w: f64) -> f64 {
+        self.potencial(self.equilibrio_controle(delta, delta_w))
+    }
+
+    /// `true` se `delta` está entre os equilíbrios instáveis `δu - 2π` e δu.
+    pub fn dentro(&self, delta: f64) -> bool {
+        self.delta_u - 2.0 * PI < delta && delta < self.delta_u
+    }
+}
+
+/// Margem de energia no início da rede pós-falta.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MargemEnergia {
+    /// Função de energia da rede pós-falta.
+    pub funcao: FuncaoEnergia,
+    /// Instante do último chaveamento (s).
+    pub instante: f64,
+    /// Ângulo do rotor no último chaveamento (rad).
+    pub delta: f64,
+    /// Desvio de velocidade no último chaveamento (rad/s).
+    pub delta_w: f64,
+    /// Energia cinética no último chaveamento.
+    pub cinetica: f64,
+    /// Energia potencial no último chaveamento.
+    pub potencial: f64,
+    /// Energia crítica.
+    pub critica: f64,
+    /// Margem `Vcr - V`; negativa quando a energia supera a crítica.
+    pub margem: f64,
+}
+
+impl MargemEnergia {
+    /// Margem em relação à energia crítica.
+    pub fn margem_normalizada(&self) -> f64 {
+        self.margem / self.critica
+    }
+
+    /// `true` se o método direto garante a estabilidade: energia abaixo da
+    /// crítica com o ângulo entre os equilíbrios instáveis.
+    pub fn estavel(&self) -> bool {
+        self.margem > 0.0 && self.funcao.dentro(self.delta)
+    }
+}
+
+/// Tempo crítico de abertura estimado pela função de energia.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EstimativaCct {
+    /// Função de energia da rede após a abertura.
+    pub funcao: FuncaoEnergia,
+    /// Primeiro instante em que a energia da trajetória em falta atinge a
+    /// crítica (s); `None` se não a atinge até `t_max`.
+    pub cct: Option<f64>,
+    /// Ângulo do rotor em `cct` (rad).
+    pub angulo_critico: Option<f64>,
+}
+
+/// Verifica se o caso admite a função de energia: modelo clássico, sem
+/// regulador de velocidade nem válvula rápida (potência mecânica constante
+/// entre os chaveamentos).
+pub fn verificar_modelo(case: &Case) -> Result<()> {
+    let maquina = &case.maquina;
+    if maquina.modelo != ModeloMaquina::Classico
+        || maquina.regulador.is_some()
+        || maquina.valvula_rapida.is_some()
+    {
+        return Err(Error::Configuracao(
+            "a função de energia requer o modelo clássico sem [maquina.regulador] nem \
+             [maquina.valvula_rapida]"
+                .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Margem de energia da sequência de eventos com abertura em `tab` e
+/// religamento em `tr`: a trajetória é integrada só até o último chaveamento,
+/// e a energia nesse instante é comparada com a energia crítica da rede
+/// final. Chaveamentos em `t = ∞` (religamento ausente) são ignorados.
+///
+/// # Errors
+///
+/// Se o caso não admite a função de energia (ver [`verificar_modelo`]) ou se
+/// a rede final não tem equilíbrio.
+pub fn margem_energia(case: &Case, tab: f64, tr: f64) -> Result<MargemEnergia> {
+    verificar_modelo(case)?;
+    let chaveamentos = eventos::resolver(&case.eventos, &case.sistema, tab, tr);
+    let mut maquina = SwingEquation::new(&case.sistema, case.sistema.pe1);
+    let mut x = maquina.estado_inicial(&case.sistema);
+    let mut t = 0.0;
+    let mut avanco = Avanco::new(&case.integrador);
+    for chaveamento in finitos(&chaveamentos) {
+        avanco.integrar_ate(&maquina, &mut t, &mut x, chaveamento.t, &mut SemRegistro);
+        maquina.chavear(chaveamento);
+    }
+
+    let funcao = FuncaoEnergia::new(&maquina)?;
+    let (delta, delta_w) = (x[DELTA], x[DELTA_W]);
+    let critica = funcao.critica(delta, delta_w);
+    Ok(MargemEnergia {
+        funcao,
+        instante: t,
+        delta,
+        delta_w,
+        cinetica: funcao.cinetica(delta_w),
+        potencial: funcao.potencial(delta),
+        critica,
+        margem: critica - funcao.energia(delta, delta_w),
+    })
+}
+
+/// Estima o tempo crítico de abertura sem integrar o sistema pós-falta: a
+/// trajetória com a falta mantida é integrada até que sua energia, medida
+/// pela função de energia da rede após a abertura (eventos até `tab`, sem
+/// religamento), atinja a energia crítica. O instante é refinado por
+/// bisseção dentro do passo em que a energia cruza a crítica, então não
+/// depende do espaçamento dos pontos da trajetória. Se a energia já parte
+/// acima da crítica, o CCT é zero.
+///
+/// # Errors
+///
+/// Se o caso não admite a função de energia (ver [`verificar_modelo`]) ou se
+/// a rede após a abertura não tem equilíbrio.
+pub fn estimar_cct(case: &Case) -> Result<EstimativaCct> {
+    verificar_modelo(case)?;
+    let sistema = &case.sistema;
+
+    // Rede após a abertura: todos os eventos até `tab`, com abertura imediata
+    let mut pos_falta = SwingEquation::new(sistema, sistema.pe1);
+    for chaveamento in finitos(&eventos::resolver(
+        &case.eventos,
+        sistema,
+        0.0,
+        f64::INFINITY,
+    )) {
+        pos_falta.chavear(chaveamento);
+    }
+    let funcao = FuncaoEnergia::new(&pos_falta)?;
+
+    // Trajetória com a falta mantida, com `V - Vcr` infinito fora da região
+    // entre os equilíbrios instáveis
+    let excesso = |x: &[f64]| {
+        let (delta, delta_w) = (x[DELTA], x[DELTA_W]);
+        if !funcao.dentro(delta) {
+            return f64::INFINITY;
+        }
+        funcao.energia(delta, delta_w) - funcao.critica(delta, delta_w)
+    };
+    let chaveamentos = eventos::resolver(&case.eventos, sistema, f64::INFINITY, f64::INFINITY);
+    let mut maquina = SwingEquation::new(sistema, sistema.pe1);
+    let x = maquina.estado_inicial(sistema);
+    let cruzamento = if excesso(&x) >= 0.0 {
+        Some((0.0, x))
+    } else {
+        primeiro_cruzamento(&mut maquina, x, &chaveamentos, &case.integrador, excesso)
+    };
+
+    Ok(EstimativaCct {
+        funcao,
+        cct: cruzamento.as_ref().map(|(t, _)| *t),
+        angulo_critico: cruzamento.as_ref().map(|(_, x)| x[DELTA]),
+    })
+}
+
+// Chaveamentos com instante finito
+fn finitos(chaveamentos: &[Chaveamento]) -> impl Iterator<Item = &Chaveamento> {
+    chaveamentos.iter().filter(|c| c.t.is_finite())
+}
+
+// Integração sem registro da trajetória
+struct SemRegistro;
+
+impl Registro for SemRegistro {
+    fn registrar(&mut self, _t: f64, _x: &[f64]) {}
+
+    fn encerrar(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::area::{angulo_critico_abertura, calcular_areas_melhorado};
+    use crate::case::caso_sem_amortecimento;
+    use crate::cct::critical_clearing_time;
+
+    #[test]
+    fn margem_e_a_diferenca_das_areas() {
+        let case = caso_sem_amortecimento();
+        let s = &case.sistema;
+        let (tab, tr) = (0.15, 0.35);
+        let m = margem_energia(&case, tab, tr).unwrap();
+        assert_eq!(m.instante, tr);
+        // Ângulo na abertura: religamento simultâneo à abertura
+        let cra = margem_energia(&case, tab, tab).unwrap().delta;
+        let (area1, area2, area3) =
+            calcular_areas_melhorado(s.pe1, s.pe2, s.pe3, s.pm, cra, m.delta);
+        let esperada = area3 - (area1 + area2);
+        assert!(
+            (m.margem - esperada).abs() < 1e-6,
+            "{} != {}",
+            m.margem,
+            esperada
+        );
+        // A energia cinética é o saldo das áreas de aceleração
+        assert!((m.cinetica - (area1 + area2)).abs() < 1e-6);
+        assert_eq!(m.estavel(), esperada > 0.0);
+    }
+
+    #[test]
+    fn cct_coincide_com_as_areas_iguais() {
+        // O CCT de ambos considera a abertura sem religamento
+        let case = caso_sem_amortecimento();
+        let s = &case.sistema;
+        let delta_c = angulo_critico_abertura(s.pe1, s.pe2, s.pe3, s.pm).unwrap();
+        let estimativa = estimar_cct(&case).unwrap();
+        assert!((estimativa.angulo_critico.unwrap() - delta_c).abs() < 1e-9);
+
+        let t_c = critical_clearing_time(&case)
+            .unwrap()
+            .cct_analitico
+            .unwrap();
+        assert!((estimativa.cct.unwrap() - t_c).abs() < 1e-6);
+    }
+
+    #[test]
+    fn modelo_detalhado_e_rejeitado() {
+        let mut case = caso_sem_amortecimento();
+        case.maquina.modelo = ModeloMaquina::DoisEixos;
+        assert!(matches!(estimar_cct(&case), Err(Error::Configuracao(_))));
+    }
+}
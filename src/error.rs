@@ -0,0 +1,96 @@
+//! Tipo de erro da biblioteca.
+
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use plotters::drawing::DrawingAreaErrorKind;
+
+/// Erros retornados pela API pública.
+#[derive(Debug)]
+pub enum Error {
+    /// Falha de leitura ou escrita em um arquivo ou diretório.
+    Io {
+        /// Arquivo ou diretório acessado.
+        caminho: PathBuf,
+        /// Erro de E/S original.
+        fonte: io::Error,
+    },
+    /// Arquivo de caso com conteúdo inválido (TOML ou JSON).
+    Caso {
+        /// Arquivo de caso.
+        caminho: PathBuf,
+        /// Mensagem do leitor TOML/JSON.
+        mensagem: String,
+    },
+    /// Falha ao executar o otimizador PSO.
+    Pso(String),
+    /// Rede SMIB inválida ou carregamento pré-falta impossível.
+    Rede(String),
+    /// Combinação de seções ou parâmetros do caso que o estudo não aceita.
+    Configuracao(String),
+    /// Método numérico (Newton, QR) que não convergiu.
+    Convergencia(String),
+    /// Busca do tempo crítico de abertura sem intervalo válido.
+    Cct(String),
+    /// Falha ao gerar um gráfico.
+    Grafico(String),
+    /// Diretório de lote sem arquivos de caso.
+    LoteVazio(PathBuf),
+    /// Caso do lote interrompido por um pânico (mensagem do pânico).
+    Panico(String),
+}
+
+/// Resultado com o tipo de erro da biblioteca.
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Adaptador para `map_err` que associa o caminho acessado a um erro de E/S.
+    pub fn io(caminho: impl Into<PathBuf>) -> impl FnOnce(io::Error) -> Error {
+        let caminho = caminho.into();
+        move |fonte| Error::Io { caminho, fonte }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { caminho, fonte } => {
+                write!(f, "Erro ao acessar '{}': {}", caminho.display(), fonte)
+            }
+            Error::Caso { caminho, mensagem } => write!(
+                f,
+                "Arquivo de caso inválido '{}': {}",
+                caminho.display(),
+                mensagem
+            ),
+            Error::Pso(mensagem) => write!(f, "Erro ao executar PSO: {}", mensagem),
+            Error::Rede(mensagem) => write!(f, "Rede inválida: {}", mensagem),
+            Error::Configuracao(mensagem) => write!(f, "Configuração inválida: {}", mensagem),
+            Error::Convergencia(mensagem) => write!(f, "Falha de convergência: {}", mensagem),
+            Error::Cct(mensagem) => write!(f, "Erro ao calcular o CCT: {}", mensagem),
+            Error::Grafico(mensagem) => write!(f, "Erro ao gerar gráfico: {}", mensagem),
+            Error::LoteVazio(pasta) => write!(
+                f,
+                "Nenhum arquivo de caso (.toml/.json) encontrado em '{}'",
+                pasta.display()
+            ),
+            Error::Panico(mensagem) => write!(f, "Execução interrompida: {}", mensagem),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { fonte, .. } => Some(fonte),
+            _ => None,
+        }
+    }
+}
+
+impl<E: std::error::Error + Send + Sync> From<DrawingAreaErrorKind<E>> for Error {
+    fn from(e: DrawingAreaErrorKind<E>) -> Self {
+        Error::Grafico(e.to_string())
+    }
+}
@@ -0,0 +1,231 @@
+//! Classificação de estabilidade de uma trajetória simulada.
+//!
+//! O [`Classificador`] acompanha a trajetória ponto a ponto durante a
+//! simulação e decide se a máquina perdeu o sincronismo por um dos critérios
+//! de [`CriterioInstabilidade`]. Quando a instabilidade é certa, a simulação
+//! pode ser interrompida antes de `t_max` (`interromper` em
+//! [`crate::case::StabilitySettings`]).
+//!
+//! [`amortecimento`] estima a razão de amortecimento da oscilação
+//! eletromecânica de uma trajetória estável pelo decremento logarítmico.
+
+use std::f64::consts::PI;
+use std::fmt;
+
+use crate::case::StabilitySettings;
+use crate::curva::PowerAngleCurve;
+
+/// Critério que caracterizou a perda de sincronismo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CriterioInstabilidade {
+    /// O ângulo ultrapassou, acelerando, o equilíbrio instável da curva da
+    /// rede final (`π - asin(Pm/Pe)` na curva senoidal).
+    EquilibrioInstavel,
+    /// O ângulo ultrapassou o limite `limite_angulo_graus`.
+    LimiteAngulo,
+    /// Após o último chaveamento a velocidade não voltou a passar por zero
+    /// até o fim da simulação.
+    VelocidadeNaoRetorna,
+}
+
+/// Veredito sobre o sincronismo da máquina.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Veredito {
+    /// A máquina permaneceu em sincronismo até o fim da simulação.
+    Estavel,
+    /// A máquina perdeu o sincronismo.
+    Instavel {
+        /// Instante em que a perda de sincronismo foi caracterizada (s).
+        t_perda: f64,
+        /// Critério que a caracterizou.
+        criterio: CriterioInstabilidade,
+    },
+}
+
+/// Avaliação de estabilidade de uma trajetória.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estabilidade {
+    /// Veredito.
+    pub veredito: Veredito,
+    /// Ângulo máximo da primeira oscilação (rad); em trajetórias instáveis,
+    /// ângulo na perda de sincronismo.
+    pub angulo_max_primeira_oscilacao: f64,
+    /// Instante do ângulo máximo da primeira oscilação (s).
+    pub t_angulo_max: f64,
+}
+
+impl Estabilidade {
+    /// `true` se a máquina permaneceu em sincronismo.
+    pub fn estavel(&self) -> bool {
+        self.veredito == Veredito::Estavel
+    }
+}
+
+impl fmt::Display for CriterioInstabilidade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CriterioInstabilidade::EquilibrioInstavel => {
+                write!(f, "ângulo além do equilíbrio instável")
+            }
+            CriterioInstabilidade::LimiteAngulo => write!(f, "ângulo além do limite"),
+            CriterioInstabilidade::VelocidadeNaoRetorna => {
+                write!(f, "velocidade não retorna a zero")
+            }
+        }
+    }
+}
+
+impl fmt::Display for Veredito {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Veredito::Estavel => write!(f, "estável"),
+            Veredito::Instavel { t_perda, criterio } => {
+                write!(f, "instável em {:.4}s ({})", t_perda, criterio)
+            }
+        }
+    }
+}
+
+/// Classificador aplicado ponto a ponto durante a simulação.
+#[derive(Debug, Clone)]
+pub struct Classificador {
+    limite_angulo: f64,
+    interromper: bool,
+    // Equilíbrio instável da rede atual (None se a curva não atinge Pm)
+    delta_u: Option<f64>,
+    // Rede atual é a última até t_max
+    definitiva: bool,
+    t_definitiva: f64,
+    velocidade_retornou: bool,
+    primeira_concluida: bool,
+    angulo_max: f64,
+    t_angulo_max: f64,
+    veredito: Veredito,
+}
+
+impl Classificador {
+    /// Cria o classificador a partir do estado inicial `(t, δ)`.
+    pub fn new(config: &StabilitySettings, t: f64, delta: f64) -> Self {
+        Classificador {
+            limite_angulo: config.limite_angulo_graus.to_radians(),
+            interromper: config.interromper,
+            delta_u: None,
+            definitiva: false,
+            t_definitiva: t,
+            velocidade_retornou: false,
+            primeira_concluida: false,
+            angulo_max: delta,
+            t_angulo_max: t,
+            veredito: Veredito::Estavel,
+        }
+    }
+
+    /// Informa a rede em vigor a partir de `t` (curva `pe` e potência
+    /// mecânica `pm`) e se ela é a última da simulação.
+    pub fn rede(&mut self, t: f64, pe: &PowerAngleCurve, pm: f64, definitiva: bool) {
+        self.delta_u = pe.intersecoes(pm).map(|(_, delta_u)| delta_u);
+        if definitiva && !self.definitiva {
+            self.t_definitiva = t;
+            self.velocidade_retornou = false;
+        }
+        self.definitiva = definitiva;
+    }
+
+    /// Registra o ponto `(t, δ, Δω)` da trajetória.
+    pub fn observar(&mut self, t: f64, delta: f64, delta_w: f64) {
+        if !self.primeira_concluida {
+            if delta > self.angulo_max {
+                self.angulo_max = delta;
+                self.t_angulo_max = t;
+            }
+            // A primeira oscilação termina quando a velocidade deixa de ser positiva
+            self.primeira_concluida = delta_w <= 0.0 && t > self.t_angulo_max;
+        }
+        if self.definitiva && delta_w <= 0.0 {
+            self.velocidade_retornou = true;
+        }
+
+        if self.veredito != Veredito::Estavel {
+            return;
+        }
+        if delta > self.limite_angulo {
+            self.instavel(t, CriterioInstabilidade::LimiteAngulo);
+        } else if let Some(delta_u) = self.delta_u
+            && self.definitiva
+            && delta > delta_u
+            && delta_w > 0.0
+        {
+            self.instavel(t, CriterioInstabilidade::EquilibrioInstavel);
+        }
+    }
+
+    /// `true` quando a instabilidade já é certa e a simulação pode parar.
+    pub fn encerrar(&self) -> bool {
+        self.interromper && self.veredito != Veredito::Estavel
+    }
+
+    /// Conclui a avaliação no fim da simulação.
+    pub fn concluir(mut self) -> Estabilidade {
+        if self.veredito == Veredito::Estavel && self.definitiva && !self.velocidade_retornou {
+            self.veredito = Veredito::Instavel {
+                t_perda: self.t_definitiva,
+                criterio: CriterioInstabilidade::VelocidadeNaoRetorna,
+            };
+        }
+        Estabilidade {
+            veredito: self.veredito,
+            angulo_max_primeira_oscilacao: self.angulo_max,
+            t_angulo_max: self.t_angulo_max,
+        }
+    }
+
+    // A primeira oscilação de uma trajetória instável termina na perda de sincronismo
+    fn instavel(&mut self, t: f64, criterio: CriterioInstabilidade) {
+        self.primeira_concluida = true;
+        self.veredito = Veredito::Instavel {
+            t_perda: t,
+            criterio,
+        };
+    }
+}
+
+/// Menor pico, relativo ao maior, considerado por [`amortecimento`].
+pub const PICO_MINIMO: f64 = 1e-3;
+
+/// Amortecimento da oscilação eletromecânica estimado de uma trajetória.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Amortecimento {
+    /// Razão de amortecimento `ζ` (negativa em oscilações crescentes).
+    pub razao: f64,
+    /// Frequência da oscilação (Hz).
+    pub frequencia: f64,
+    /// Número de ciclos usados na estimativa.
+    pub ciclos: usize,
+}
+
+/// Razão de amortecimento e frequência da oscilação de `sinal` (usualmente o
+/// desvio de velocidade) a partir do instante `inicio`, pelo decremento
+/// logarítmico entre o primeiro e o último pico positivo:
+/// `d = ln(A0/An)/n`, `ζ = d/√(4π² + d²)`. Picos abaixo de
+/// [`PICO_MINIMO`] vezes o maior são desprezados (oscilação já extinta).
+/// `None` com menos de dois picos.
+pub fn amortecimento(tempos: &[f64], sinal: &[f64], inicio: f64) -> Option<Amortecimento> {
+    let primeiro = tempos.iter().position(|&t| t >= inicio)?;
+    let mut picos: Vec<(f64, f64)> = (primeiro.max(1)..sinal.len().saturating_sub(1))
+        .filter(|&i| sinal[i] > 0.0 && sinal[i - 1] < sinal[i] && sinal[i] >= sinal[i + 1])
+        .map(|i| (tempos[i], sinal[i]))
+        .collect();
+    let maior = picos.iter().fold(0.0, |a: f64, &(_, p)| a.max(p));
+    picos.retain(|&(_, p)| p >= PICO_MINIMO * maior);
+    let (&(t0, a0), &(tn, an)) = (picos.first()?, picos.last()?);
+    let ciclos = picos.len() - 1;
+    if ciclos == 0 {
+        return None;
+    }
+    let decremento = (a0 / an).ln() / ciclos as f64;
+    Some(Amortecimento {
+        razao: decremento / (4.0 * PI * PI + decremento * decremento).sqrt(),
+        frequencia: ciclos as f64 / (tn - t0),
+        ciclos,
+    })
+}
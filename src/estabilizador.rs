@@ -0,0 +1,466 @@
+//! Estabilizadores de sistemas de potência (PSS) que somam o sinal `vs` à
+//! referência da excitatriz ([`crate::excitacao`]) para amortecer as
+//! oscilações eletromecânicas.
+//!
+//! O estabilizador recebe o desvio de velocidade (p.u. da velocidade
+//! síncrona) e a potência elétrica (p.u.) do gerador (ver
+//! [`SinaisEstabilizador`]). Os blocos com constante de tempo nula são
+//! ignorados (ganho unitário). Modelos (seção `[maquina.estabilizador]`,
+//! escolhidos por `modelo`):
+//!
+//! - `"pss1a"`: IEEE PSS1A de uma entrada (velocidade ou potência elétrica),
+//!   sem os filtros torcionais `A1` a `A6`: transdutor `1/(1 + s·T6)`,
+//!   avanço-atraso `(1 + s·T1)/(1 + s·T2)` e `(1 + s·T3)/(1 + s·T4)`, ganho
+//!   `KS` e washout `s·T5/(1 + s·T5)`;
+//! - `"pss2a"`: IEEE PSS2A de duas entradas (potência acelerante integrada):
+//!   washouts `TW1` e `TW2` e transdutor `T6` na velocidade, washouts `TW3` e
+//!   `TW4` e integrador `KS2/(1 + s·T7)` na potência elétrica, filtro
+//!   rastreador de rampa `[(1 + s·T8)/(1 + s·T9)^M]^N`, ganho `KS1` e
+//!   avanço-atraso `T1/T2` e `T3/T4`.
+//!
+//! A saída fica entre `VSTMIN` e `VSTMAX`.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::blocos::{avanco_atraso, um};
+use crate::error::{Error, Result};
+
+/// Sinais do gerador medidos pelo estabilizador.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SinaisEstabilizador {
+    /// Desvio de velocidade (p.u. da velocidade síncrona).
+    pub velocidade: f64,
+    /// Potência elétrica (p.u.).
+    pub potencia_eletrica: f64,
+}
+
+/// Modelo de estabilizador acoplado à excitatriz.
+pub trait StabilizerModel: fmt::Debug {
+    /// Número de estados.
+    fn dimensao(&self) -> usize;
+
+    /// Estado inicial em regime com os sinais `sinais` (saída nula).
+    fn inicializar(&self, sinais: SinaisEstabilizador) -> Vec<f64>;
+
+    /// Sinal estabilizador `vs` (p.u.) no estado `x`.
+    fn vs(&self, x: &[f64], sinais: SinaisEstabilizador) -> f64;
+
+    /// Derivadas do estado `x`.
+    fn derivada(&self, x: &[f64], sinais: SinaisEstabilizador, dxdt: &mut [f64]);
+}
+
+/// Estabilizador de `[maquina.estabilizador]`, escolhido por `modelo`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "modelo", rename_all = "snake_case")]
+pub enum StabilizerSettings {
+    /// IEEE PSS1A (`"pss1a"`).
+    Pss1a(Pss1a),
+    /// IEEE PSS2A (`"pss2a"`).
+    Pss2a(Pss2a),
+}
+
+impl StabilizerSettings {
+    /// Nome do modelo para os relatórios.
+    pub fn nome(&self) -> &'static str {
+        match self {
+            StabilizerSettings::Pss1a(_) => "PSS1A",
+            StabilizerSettings::Pss2a(_) => "PSS2A",
+        }
+    }
+
+    /// Verifica as constantes de tempo e os limites do modelo.
+    pub fn validar(&self) -> Result<()> {
+        let (valido, condicao) = match self {
+            StabilizerSettings::Pss1a(p) => (
+                p.t5 > 0.0
+                    && [p.t1, p.t2, p.t3, p.t4, p.t6].iter().all(|&t| t >= 0.0)
+                    && p.vstmin < p.vstmax,
+                "t5 positivo, t1 a t4 e t6 não negativos e vstmin < vstmax",
+            ),
+            StabilizerSettings::Pss2a(p) => (
+                p.tw1 > 0.0
+                    && p.tw3 > 0.0
+                    && p.t7 > 0.0
+                    && [p.tw2, p.tw4, p.t6, p.t8, p.t9, p.t1, p.t2, p.t3, p.t4]
+                        .iter()
+                        .all(|&t| t >= 0.0)
+                    && (1..=MAX_RAMPA).contains(&(p.n * p.m))
+                    && p.vstmin < p.vstmax,
+                "tw1, tw3 e t7 positivos, demais constantes de tempo não negativas, \
+                 1 <= n·m <= 10 e vstmin < vstmax",
+            ),
+        };
+        if valido {
+            Ok(())
+        } else {
+            Err(Error::Configuracao(format!(
+                "o estabilizador {} requer {}",
+                self.nome(),
+                condicao
+            )))
+        }
+    }
+
+    /// Modelo do estabilizador.
+    pub fn modelo(&self) -> Box<dyn StabilizerModel> {
+        match self {
+            StabilizerSettings::Pss1a(p) => Box::new(p.clone()),
+            StabilizerSettings::Pss2a(p) => Box::new(p.clone()),
+        }
+    }
+}
+
+/// Sinal de entrada do PSS1A.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntradaEstabilizador {
+    /// Desvio de velocidade (`"velocidade"`).
+    #[default]
+    Velocidade,
+    /// Potência elétrica (`"potencia"`); o ganho `KS` costuma ser negativo.
+    Potencia,
+}
+
+/// IEEE PSS1A com estado `[transdutor, avanço-atraso 1, avanço-atraso 2,
+/// washout]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Pss1a {
+    /// Sinal de entrada (padrão: `"velocidade"`).
+    #[serde(default)]
+    pub entrada: EntradaEstabilizador,
+    /// Ganho `KS` (p.u.).
+    pub ks: f64,
+    /// Constante de tempo do transdutor `T6` (s; padrão: 0).
+    #[serde(default)]
+    pub t6: f64,
+    /// Constante de tempo de avanço `T1` (s; padrão: 0).
+    #[serde(default)]
+    pub t1: f64,
+    /// Constante de tempo de atraso `T2` (s; padrão: 0).
+    #[serde(default)]
+    pub t2: f64,
+    /// Constante de tempo de avanço `T3` (s; padrão: 0).
+    #[serde(default)]
+    pub t3: f64,
+    /// Constante de tempo de atraso `T4` (s; padrão: 0).
+    #[serde(default)]
+    pub t4: f64,
+    /// Constante de tempo do washout `T5` (s).
+    pub t5: f64,
+    /// Limite superior da saída `VSTMAX` (p.u.).
+    pub vstmax: f64,
+    /// Limite inferior da saída `VSTMIN` (p.u.).
+    pub vstmin: f64,
+}
+
+impl Pss1a {
+    // Constantes de tempo dos estados, na ordem do vetor de estado
+    fn constantes(&self) -> [f64; 4] {
+        [self.t6, self.t2, self.t4, self.t5]
+    }
+
+    // Percorre os blocos, entregando a entrada de cada estado a `entrada`, e
+    // retorna a saída antes do limitador
+    fn propagar(
+        &self,
+        x: &[f64],
+        sinais: SinaisEstabilizador,
+        mut entrada: impl FnMut(usize, f64),
+    ) -> f64 {
+        let u = match self.entrada {
+            EntradaEstabilizador::Velocidade => sinais.velocidade,
+            EntradaEstabilizador::Potencia => sinais.potencia_eletrica,
+        };
+        entrada(0, u);
+        let y = atraso(u, x[0], self.t6);
+        entrada(1, y);
+        let y = avanco_atraso(y, x[1], self.t1, self.t2).0;
+        entrada(2, y);
+        let y = self.ks * avanco_atraso(y, x[2], self.t3, self.t4).0;
+        entrada(3, y);
+        lavagem(y, x[3], self.t5)
+    }
+}
+
+impl StabilizerModel for Pss1a {
+    fn dimensao(&self) -> usize {
+        4
+    }
+
+    fn inicializar(&self, sinais: SinaisEstabilizador) -> Vec<f64> {
+        regime(self.dimensao(), |x, entrada| {
+            self.propagar(x, sinais, entrada);
+        })
+    }
+
+    fn vs(&self, x: &[f64], sinais: SinaisEstabilizador) -> f64 {
+        self.propagar(x, sinais, |_, _| {})
+            .clamp(self.vstmin, self.vstmax)
+    }
+
+    fn derivada(&self, x: &[f64], sinais: SinaisEstabilizador, dxdt: &mut [f64]) {
+        let constantes = self.constantes();
+        self.propagar(x, sinais, |i, u| {
+            dxdt[i] = derivada_bloco(u, x[i], constantes[i])
+        });
+    }
+}
+
+/// IEEE PSS2A com estado `[washout TW1, washout TW2, transdutor T6, washout
+/// TW3, washout TW4, integrador T7, filtro de rampa (N·M estados),
+/// avanço-atraso 1, avanço-atraso 2]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Pss2a {
+    /// Constante de tempo do primeiro washout da velocidade `TW1` (s).
+    pub tw1: f64,
+    /// Constante de tempo do segundo washout da velocidade `TW2` (s; padrão:
+    /// 0, sem o bloco).
+    #[serde(default)]
+    pub tw2: f64,
+    /// Constante de tempo do transdutor de velocidade `T6` (s; padrão: 0).
+    #[serde(default)]
+    pub t6: f64,
+    /// Constante de tempo do primeiro washout da potência `TW3` (s).
+    pub tw3: f64,
+    /// Constante de tempo do segundo washout da potência `TW4` (s; padrão:
+    /// 0, sem o bloco).
+    #[serde(default)]
+    pub tw4: f64,
+    /// Constante de tempo do integrador da potência `T7` (s).
+    pub t7: f64,
+    /// Ganho do integrador da potência `KS2` (p.u.; usualmente `T7/(2H)`).
+    pub ks2: f64,
+    /// Ganho da potência na soma com a velocidade `KS3` (p.u.; padrão: 1).
+    #[serde(default = "um")]
+    pub ks3: f64,
+    /// Constante de tempo de avanço do filtro de rampa `T8` (s; padrão: 0).
+    #[serde(default)]
+    pub t8: f64,
+    /// Constante de tempo de atraso do filtro de rampa `T9` (s; padrão: 0,
+    /// sem o filtro).
+    #[serde(default)]
+    pub t9: f64,
+    /// Número de blocos do filtro de rampa `N` (padrão: 1).
+    #[serde(default = "n_padrao")]
+    pub n: usize,
+    /// Ordem de cada bloco do filtro de rampa `M` (padrão: 5).
+    #[serde(default = "m_padrao")]
+    pub m: usize,
+    /// Ganho do estabilizador `KS1` (p.u.).
+    pub ks1: f64,
+    /// Constante de tempo de avanço `T1` (s; padrão: 0).
+    #[serde(default)]
+    pub t1: f64,
+    /// Constante de tempo de atraso `T2` (s; padrão: 0).
+    #[serde(default)]
+    pub t2: f64,
+    /// Constante de tempo de avanço `T3` (s; padrão: 0).
+    #[serde(default)]
+    pub t3: f64,
+    /// Constante de tempo de atraso `T4` (s; padrão: 0).
+    #[serde(default)]
+    pub t4: f64,
+    /// Limite superior da saída `VSTMAX` (p.u.).
+    pub vstmax: f64,
+    /// Limite inferior da saída `VSTMIN` (p.u.).
+    pub vstmin: f64,
+}
+
+// Maior número de estados do filtro rastreador de rampa (N·M)
+const MAX_RAMPA: usize = 10;
+
+// Índice do primeiro estado do filtro rastreador de rampa do PSS2A
+const RAMPA: usize = 6;
+
+impl Pss2a {
+    // Constante de tempo do estado `i`
+    fn constante(&self, i: usize) -> f64 {
+        let rampa = self.n * self.m;
+        match i {
+            0 => self.tw1,
+            1 => self.tw2,
+            2 => self.t6,
+            3 => self.tw3,
+            4 => self.tw4,
+            5 => self.t7,
+            _ if i < RAMPA + rampa => self.t9,
+            _ if i == RAMPA + rampa => self.t2,
+            _ => self.t4,
+        }
+    }
+
+    // Percorre os blocos, entregando a entrada de cada estado a `entrada`, e
+    // retorna a saída antes do limitador
+    fn propagar(
+        &self,
+        x: &[f64],
+        sinais: SinaisEstabilizador,
+        mut entrada: impl FnMut(usize, f64),
+    ) -> f64 {
+        // Velocidade
+        entrada(0, sinais.velocidade);
+        let y = lavagem(sinais.velocidade, x[0], self.tw1);
+        entrada(1, y);
+        let y = lavagem(y, x[1], self.tw2);
+        entrada(2, y);
+        let velocidade = atraso(y, x[2], self.t6);
+        // Potência elétrica integrada
+        entrada(3, sinais.potencia_eletrica);
+        let y = lavagem(sinais.potencia_eletrica, x[3], self.tw3);
+        entrada(4, y);
+        let y = lavagem(y, x[4], self.tw4);
+        entrada(5, self.ks2 * y);
+        let potencia = atraso(self.ks2 * y, x[5], self.t7);
+        // Filtro rastreador de rampa: N blocos de um avanço-atraso seguido de
+        // M - 1 atrasos
+        let mut y = velocidade + self.ks3 * potencia;
+        let rampa = &x[RAMPA..RAMPA + self.n * self.m];
+        for (k, &estado) in rampa.iter().enumerate() {
+            entrada(RAMPA + k, y);
+            y = if k.is_multiple_of(self.m) {
+                avanco_atraso(y, estado, self.t8, self.t9).0
+            } else {
+                atraso(y, estado, self.t9)
+            };
+        }
+        let avanco = RAMPA + self.n * self.m;
+        let y = self.ks1 * (y - potencia);
+        entrada(avanco, y);
+        let y = avanco_atraso(y, x[avanco], self.t1, self.t2).0;
+        entrada(avanco + 1, y);
+        avanco_atraso(y, x[avanco + 1], self.t3, self.t4).0
+    }
+}
+
+impl StabilizerModel for Pss2a {
+    fn dimensao(&self) -> usize {
+        RAMPA + self.n * self.m + 2
+    }
+
+    fn inicializar(&self, sinais: SinaisEstabilizador) -> Vec<f64> {
+        regime(self.dimensao(), |x, entrada| {
+            self.propagar(x, sinais, entrada);
+        })
+    }
+
+    fn vs(&self, x: &[f64], sinais: SinaisEstabilizador) -> f64 {
+        self.propagar(x, sinais, |_, _| {})
+            .clamp(self.vstmin, self.vstmax)
+    }
+
+    fn derivada(&self, x: &[f64], sinais: SinaisEstabilizador, dxdt: &mut [f64]) {
+        self.propagar(x, sinais, |i, u| {
+            dxdt[i] = derivada_bloco(u, x[i], self.constante(i))
+        });
+    }
+}
+
+// Estado de regime de uma cadeia de `dimensao` blocos de primeira ordem: cada
+// estado iguala a própria entrada. Como a cadeia não tem realimentação,
+// `dimensao` passagens bastam
+fn regime(dimensao: usize, propagar: impl Fn(&[f64], &mut dyn FnMut(usize, f64))) -> Vec<f64> {
+    let mut x = vec![0.0; dimensao];
+    for _ in 0..dimensao {
+        let mut entradas = x.clone();
+        propagar(&x, &mut |i, u| entradas[i] = u);
+        x = entradas;
+    }
+    x
+}
+
+// Derivada do estado `x` de um bloco de primeira ordem com entrada `u` e
+// constante de tempo `t` (bloco ignorado com `t` nulo)
+fn derivada_bloco(u: f64, x: f64, t: f64) -> f64 {
+    if t > 0.0 { (u - x) / t } else { 0.0 }
+}
+
+// Saída do atraso 1/(1 + s·T)
+fn atraso(u: f64, x: f64, t: f64) -> f64 {
+    if t > 0.0 { x } else { u }
+}
+
+// Saída do washout s·T/(1 + s·T)
+fn lavagem(u: f64, x: f64, t: f64) -> f64 {
+    if t > 0.0 { u - x } else { u }
+}
+
+fn n_padrao() -> usize {
+    1
+}
+
+fn m_padrao() -> usize {
+    5
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn estabilizadores() -> Vec<StabilizerSettings> {
+        [
+            "modelo = \"pss1a\"\nks = 20.0\nt6 = 0.02\nt1 = 0.15\nt2 = 0.03\nt3 = 0.15\n\
+             t4 = 0.03\nt5 = 10.0\nvstmax = 0.1\nvstmin = -0.1\n",
+            "modelo = \"pss1a\"\nentrada = \"potencia\"\nks = -1.0\nt1 = 0.15\nt2 = 0.03\n\
+             t5 = 10.0\nvstmax = 0.1\nvstmin = -0.1\n",
+            "modelo = \"pss2a\"\ntw1 = 10.0\ntw2 = 10.0\ntw3 = 10.0\nt7 = 10.0\nks2 = 0.55\n\
+             t8 = 0.5\nt9 = 0.1\nks1 = 20.0\nt1 = 0.15\nt2 = 0.03\nt3 = 0.15\nt4 = 0.03\n\
+             vstmax = 0.1\nvstmin = -0.1\n",
+        ]
+        .into_iter()
+        .map(|toml| toml::from_str(toml).unwrap())
+        .collect()
+    }
+
+    #[test]
+    fn regime_tem_saida_nula() {
+        let sinais = SinaisEstabilizador {
+            velocidade: 0.0,
+            potencia_eletrica: 0.9,
+        };
+        for configuracao in estabilizadores() {
+            configuracao.validar().unwrap();
+            let estabilizador = configuracao.modelo();
+            let x = estabilizador.inicializar(sinais);
+            let mut dxdt = vec![f64::NAN; estabilizador.dimensao()];
+            estabilizador.derivada(&x, sinais, &mut dxdt);
+            assert!(
+                dxdt.iter().all(|&d| d == 0.0),
+                "{}: {:?}",
+                configuracao.nome(),
+                dxdt
+            );
+            assert_eq!(estabilizador.vs(&x, sinais), 0.0, "{}", configuracao.nome());
+        }
+    }
+
+    #[test]
+    fn saida_limitada() {
+        let regime = SinaisEstabilizador {
+            velocidade: 0.0,
+            potencia_eletrica: 0.9,
+        };
+        for configuracao in estabilizadores() {
+            let estabilizador = configuracao.modelo();
+            // Degraus de velocidade e de potência nos dois sentidos, integrados
+            // por Euler durante 0,5 s: a saída satura em VSTMIN e VSTMAX
+            let mut saidas = [-1.0, 1.0].map(|sinal| {
+                let sinais = SinaisEstabilizador {
+                    velocidade: sinal * 0.1,
+                    potencia_eletrica: 0.9 - sinal * 0.5,
+                };
+                let mut x = estabilizador.inicializar(regime);
+                let mut dxdt = vec![0.0; x.len()];
+                for _ in 0..5000 {
+                    estabilizador.derivada(&x, sinais, &mut dxdt);
+                    x.iter_mut().zip(&dxdt).for_each(|(x, d)| *x += 1e-4 * d);
+                }
+                estabilizador.vs(&x, sinais)
+            });
+            saidas.sort_by(f64::total_cmp);
+            assert_eq!(saidas, [-0.1, 0.1], "{}", configuracao.nome());
+        }
+    }
+}
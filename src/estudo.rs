@@ -0,0 +1,650 @@
+//! Execução de um estudo: PSO, simulação final, áreas iguais e gráficos.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::area::{angulo_critico_abertura, angulo_critico_religamento, calcular_areas_melhorado};
+use crate::case::{Case, MetodoIntegracao, ModeloMaquina};
+use crate::energia::{EstimativaCct, MargemEnergia, estimar_cct, margem_energia};
+use crate::error::{Error, Result};
+use crate::estabilidade::{Amortecimento, Estabilidade, amortecimento};
+use crate::eventos;
+use crate::objective_function::{definir_caso_ativo, objective_function};
+use crate::pequenos_sinais::{AnalisePequenosSinais, analisar_pequenos_sinais};
+use crate::plot::{
+    abrir_imagem, plotar_angulos_velocidades, plotar_comparacao, plotar_curva_potencia,
+    plotar_excitacao, plotar_potencia_mecanica,
+};
+use crate::pso_config::pso_config;
+use crate::sim_per_time::{ResultadoSimulacao, sim_pet_time};
+
+/// Resumo de um estudo, usado na tabela comparativa do modo em lote.
+#[derive(Debug, Clone)]
+pub struct ResumoEstudo {
+    /// Nome do estudo (pasta de saída ou nome do arquivo de caso).
+    pub nome: String,
+    /// Tempo de abertura encontrado pelo PSO (s).
+    pub tab: f64,
+    /// Tempo de religamento encontrado pelo PSO (s).
+    pub tr: f64,
+    /// Ângulo na abertura (rad); `None` se a simulação final termina antes de
+    /// `tab`.
+    pub cra: Option<f64>,
+    /// Ângulo no religamento (rad); `None` se a simulação final termina antes
+    /// de `tr`.
+    pub crr: Option<f64>,
+    /// Área de aceleração durante a falta; `None` sem CRA e CRR.
+    pub area1: Option<f64>,
+    /// Área entre a abertura e o religamento; `None` sem CRA e CRR.
+    pub area2: Option<f64>,
+    /// Área de desaceleração após o religamento; `None` sem CRA e CRR.
+    pub area3: Option<f64>,
+    /// `|(Área 1 + Área 2) - Área 3|`; `None` sem CRA e CRR.
+    pub diferenca: Option<f64>,
+    /// Avaliação de estabilidade da simulação final.
+    pub estabilidade: Estabilidade,
+    /// Tempo total de execução do estudo.
+    pub tempo: Duration,
+}
+
+/// Melhor partícula encontrada pelo PSO.
+#[derive(Debug, Clone, Copy)]
+pub struct ResultadoPso {
+    /// Tempo de abertura (s).
+    pub tab: f64,
+    /// Tempo de religamento (s).
+    pub tr: f64,
+    /// Valor da função objetivo na melhor partícula.
+    pub f_best: f64,
+}
+
+/// Busca `[tab, tr]` com o PSO para o caso informado.
+///
+/// Durante a busca, o caso é o caso ativo de [`crate::objective_function`]:
+/// chamadas simultâneas, em threads diferentes, são executadas uma de cada
+/// vez.
+pub fn otimizar(case: &Case) -> Result<ResultadoPso> {
+    let (config, terminate) = pso_config(&case.pso);
+    let _caso_ativo = definir_caso_ativo(case.clone());
+    let model = pso_rs::run(config, objective_function, terminate)
+        .map_err(|e| Error::Pso(e.to_string()))?
+        .model;
+    let x_best = model.get_x_best();
+    Ok(ResultadoPso {
+        tab: x_best[0],
+        tr: x_best[1],
+        f_best: model.get_f_best(),
+    })
+}
+
+/// Executa um estudo completo (PSO, simulação final, áreas iguais e gráficos),
+/// gravando `resultados.txt`, os gráficos e a cópia do caso em `pasta_saida`.
+pub fn executar_estudo(
+    nome: &str,
+    case: &Case,
+    caminho_caso: Option<&Path>,
+    pasta_saida: &str,
+    should_open_images: bool,
+) -> Result<ResumoEstudo> {
+    let init = Instant::now();
+    let sistema = &case.sistema;
+    let integrador = &case.integrador;
+
+    // Criar diretório de saída se não existir
+    fs::create_dir_all(pasta_saida).map_err(Error::io(pasta_saida))?;
+
+    // Copiar o arquivo de caso para a pasta de saída, garantindo a reprodutibilidade
+    let destino = case.copiar_para(caminho_caso, Path::new(pasta_saida))?;
+    println!("Caso copiado para: {}", destino.display());
+
+    println!("Saída será salva em: {}/", pasta_saida);
+
+    // Criar arquivo de resultados
+    let caminho_resultado = format!("{}/resultados.txt", pasta_saida);
+    let mut arquivo_resultado =
+        fs::File::create(&caminho_resultado).map_err(Error::io(&caminho_resultado))?;
+
+    // Função auxiliar para escrever no arquivo e na tela
+    let mut escrever = |texto: &str| {
+        print!("{}", texto);
+        if let Err(e) = write!(arquivo_resultado, "{}", texto) {
+            eprintln!("Erro ao escrever no arquivo: {}", e);
+        }
+    };
+
+    let (config, _) = pso_config(&case.pso);
+
+    let ini_msg = format!(
+        "=== INICIANDO EXECUÇÃO ===\n\
+        Parâmetros:\n\
+        - Pasta de saída: {}\n\
+        - Arquivo de caso: {}\n\
+        - Configuração PSO: {:#?}\n\
+        - Valores iniciais:\n\
+        - PM: {:.4} p.u.\n\
+        - PE1: {} p.u.\n\
+        - PE2: {} p.u.\n\
+        - PE3: {} p.u.\n\
+        - F: {:.2} Hz\n\
+        - H: {:.2} s\n\
+        - D: {:.2}\n\
+        - DELTA_W_INI: {:.4} rad/s\n\
+        - T_MAX: {:.4} s\n\
+        - MÉTODO DE INTEGRAÇÃO: {}\n\
+        - DELTA_T: {:.6} s\n{}",
+        pasta_saida,
+        caminho_caso
+            .map(|c| c.display().to_string())
+            .unwrap_or("(padrão de values.rs)".to_string()),
+        config,
+        sistema.pm,
+        sistema.pe1,
+        sistema.pe2,
+        sistema.pe3,
+        sistema.f,
+        sistema.h,
+        sistema.d,
+        sistema.delta_w_ini,
+        integrador.t_max,
+        integrador.metodo,
+        integrador.delta_t,
+        match integrador.metodo {
+            MetodoIntegracao::Rk4 => String::new(),
+            MetodoIntegracao::Rk45 => format!(
+                "- TOLERÂNCIAS: abs {:e}, rel {:e}\n- PASSO MÁXIMO: {:.6} s\n",
+                integrador.tolerancia_abs, integrador.tolerancia_rel, integrador.passo_max
+            ),
+        }
+    );
+    escrever(&ini_msg);
+    if let Some(rede) = &case.rede {
+        let rede_msg = match rede.calcular(sistema.pm) {
+            Ok(r) => format!(
+                "- REDE SMIB: E' = {:.5} p.u., delta_0 = {:.4}°\n\
+                 - FALTA {}: X2 = {}, X0 = {}, derivação = {:.5} p.u.\n\
+                 - ABERTURA {}: série = {:.5} p.u.\n\
+                 - REATÂNCIAS DE TRANSFERÊNCIA: pré-falta {:.5}, falta {:.5}, pós-abertura {:.5} p.u.\n",
+                r.e_interna,
+                r.delta_0.to_degrees(),
+                rede.tipo_falta.to_string().to_uppercase(),
+                r.x2_thevenin
+                    .map_or("-".to_string(), |x| format!("{:.5}", x)),
+                r.x0_thevenin
+                    .map_or("-".to_string(), |x| format!("{:.5}", x)),
+                r.x_derivacao,
+                rede.tipo_abertura.to_string().to_uppercase(),
+                r.x_serie_abertura,
+                r.x_pre_falta,
+                r.x_falta,
+                r.x_pos_abertura
+            ),
+            Err(e) => format!("- REDE SMIB: {}\n", e),
+        };
+        escrever(&rede_msg);
+    }
+    let parametros_maquina = case
+        .maquina
+        .parametros()
+        .iter()
+        .map(|(nome, valor)| format!("{} = {:.5}", nome, valor))
+        .collect::<Vec<_>>();
+    let maquina_msg = match case.maquina.modelo {
+        ModeloMaquina::Classico => format!("- MODELO DO GERADOR: {}\n", case.maquina.modelo),
+        _ => format!(
+            "- MODELO DO GERADOR: {} ({})\n",
+            case.maquina.modelo,
+            parametros_maquina.join(", ")
+        ),
+    };
+    escrever(&maquina_msg);
+    if let Some(excitatriz) = &case.maquina.excitatriz {
+        escrever(&format!("- EXCITATRIZ: {}\n", excitatriz.nome()));
+    }
+    if let Some(estabilizador) = &case.maquina.estabilizador {
+        escrever(&format!("- ESTABILIZADOR: {}\n", estabilizador.nome()));
+    }
+    if let Some(regulador) = &case.maquina.regulador {
+        escrever(&format!(
+            "- REGULADOR DE VELOCIDADE: {}\n",
+            regulador.nome()
+        ));
+    }
+    if let Some(valvula) = &case.maquina.valvula_rapida {
+        escrever(&format!(
+            "- VÁLVULA RÁPIDA: t = {:.4}s, abertura mínima {:.2} p.u.\n",
+            valvula.inicio, valvula.nivel
+        ));
+    }
+    escrever(&format!("- OBJETIVO DO PSO: {}\n", case.pso.objetivo));
+    escrever("=== INICIANDO PSO ===\n");
+    let ResultadoPso { tab, tr, .. } = match otimizar(case) {
+        Ok(resultado) => resultado,
+        Err(e) => {
+            escrever(&format!("{}\n", e));
+            return Err(e);
+        }
+    };
+    let resultado_pso = format!("Resultado do PSO [tab,tr]:{:?}\n", [tab, tr]);
+    escrever(&resultado_pso);
+
+    // *** SIMULAÇÃO FINAL ***
+    escrever("\n=== SIMULAÇÃO FINAL ===\n");
+    let delta_n_ini = sistema.delta_n_ini();
+    let parametros_msg = format!(
+        "Parâmetros finais: tab: {:.4}s, tr: {:.4}s, delta_n_ini: {:.6} rad -> {:.4}°\n",
+        tab,
+        tr,
+        delta_n_ini,
+        delta_n_ini.to_degrees()
+    );
+
+    escrever(&parametros_msg);
+
+    let mut eventos_msg = String::from("Sequência de eventos:\n");
+    for chaveamento in eventos::resolver(&case.eventos, sistema, tab, tr) {
+        eventos_msg += &format!("- t: {:.4}s -> Pe: {} p.u.", chaveamento.t, chaveamento.pe);
+        if let Some(pm) = chaveamento.pm {
+            eventos_msg += &format!(", Pm: {:.4} p.u.", pm);
+        }
+        eventos_msg += "\n";
+    }
+    escrever(&eventos_msg);
+    let sim_final = sim_pet_time(case, tab, tr)?;
+    let (cra_final, crr_final) = (sim_final.cra, sim_final.crr);
+    // debug_valores(sistema.pe1, sistema.pe2, sistema.pe3, sistema.pm, cra_final, crr_final);
+    let angulo = |valor: Option<f64>, ausente: &str| {
+        valor.map_or(ausente.to_string(), |v| {
+            format!("{:.4}° / {:.6} rad", v.to_degrees(), v)
+        })
+    };
+    let nao_atingido = "não atingido (simulação encerrada antes)";
+    let resultados_msg = format!(
+        "Resultados da simulação final:\nCRA: {} -> tab: {:.4}s\nCRR: {} -> tr: {:.4}s\n",
+        angulo(cra_final, nao_atingido),
+        tab,
+        angulo(crr_final, nao_atingido),
+        tr
+    );
+    escrever(&resultados_msg);
+
+    let estabilidade = sim_final.estabilidade;
+    let estabilidade_msg = format!(
+        "Estabilidade: {}\nÂngulo máximo da primeira oscilação: {:.4}° / {:.6} rad -> t: {:.4}s\n",
+        estabilidade.veredito,
+        estabilidade.angulo_max_primeira_oscilacao.to_degrees(),
+        estabilidade.angulo_max_primeira_oscilacao,
+        estabilidade.t_angulo_max
+    );
+    escrever(&estabilidade_msg);
+
+    escrever("\n=== MÉTODO DAS ÁREAS IGUAIS ===\n");
+    // As áreas só existem com os dois ângulos atingidos pela trajetória
+    let areas = match (cra_final, crr_final) {
+        (Some(cra), Some(crr)) => {
+            let (area1, area2, area3) = calcular_areas_melhorado(
+                sistema.pe1,
+                sistema.pe2,
+                sistema.pe3,
+                sistema.pm,
+                cra,
+                crr,
+            );
+            let areas_msg = format!(
+                "Áreas calculadas (método melhorado):\nÁrea 1: {:.4}, Área 2: {:.4}, Área 3: {:.4}\n\n",
+                area1, area2, area3
+            );
+            escrever(&areas_msg);
+            let diferenca = ((area1 + area2) - area3).abs();
+            escrever(&format!("Diferença das áreas: {:.4}\n", diferenca));
+            Some((area1, area2, area3, diferenca))
+        }
+        _ => {
+            escrever("Áreas indisponíveis: a simulação final terminou antes de tab ou tr\n");
+            None
+        }
+    };
+
+    // Verificação do resultado do PSO contra os ângulos críticos analíticos
+    let crr_critico = cra_final.and_then(|cra| {
+        angulo_critico_religamento(sistema.pe1, sistema.pe2, sistema.pe3, sistema.pm, cra)
+    });
+    let mut analitico_msg = format!(
+        "\nVerificação analítica (áreas iguais):\n\
+         Ângulo crítico de abertura (sem religamento): {}\n\
+         Ângulo crítico de religamento para o CRA: {}\n",
+        angulo(
+            angulo_critico_abertura(sistema.pe1, sistema.pe2, sistema.pe3, sistema.pm),
+            "inexistente"
+        ),
+        angulo(crr_critico, "inexistente")
+    );
+    if let (Some(crr_critico), Some(crr_final)) = (crr_critico, crr_final) {
+        analitico_msg += &format!(
+            "Diferença CRR (PSO - analítico): {:.4}°\n",
+            (crr_final - crr_critico).to_degrees()
+        );
+    }
+    escrever(&analitico_msg);
+
+    escrever("\n=== FUNÇÃO DE ENERGIA TRANSITÓRIA ===\n");
+    match margem_energia(case, tab, tr) {
+        Ok(margem) => escrever(&relatorio_energia(&margem, estimar_cct(case))),
+        Err(e) => escrever(&format!("Função de energia indisponível: {}\n", e)),
+    }
+
+    escrever("\n=== ANÁLISE DE PEQUENOS SINAIS ===\n");
+    match analisar_pequenos_sinais(case, tab, tr) {
+        Ok(analise) => escrever(&relatorio_pequenos_sinais(&analise)),
+        Err(e) => escrever(&format!("Análise de pequenos sinais indisponível: {}\n", e)),
+    }
+
+    // *** GERAR OS GRÁFICOS ***
+    escrever("\n=== GERANDO GRÁFICOS ===\n");
+
+    let caminho_simulacao = format!("{}/simulacao_no_tempo.png", pasta_saida);
+    let caminho_potencia = format!("{}/potencia.png", pasta_saida);
+
+    if let Err(e) = plotar_angulos_velocidades(
+        &sim_final.tempos,
+        &sim_final.angulos,
+        &sim_final.velocidades,
+        &caminho_simulacao,
+        integrador.t_max,
+    ) {
+        let erro_msg = format!("Erro ao plotar ângulos e velocidades: {}\n", e);
+        escrever(&erro_msg);
+    } else {
+        escrever("Gráfico de ângulos e velocidades gerado com sucesso!\n");
+        // CORREÇÃO: Usar should_open_images diretamente
+        if should_open_images {
+            abrir_imagem(&caminho_simulacao);
+        }
+    }
+
+    if !sim_final.tensao_terminal.is_empty() {
+        let caminho_excitacao = format!("{}/excitacao.png", pasta_saida);
+        match plotar_excitacao(
+            &sim_final.tempos,
+            &sim_final.tensao_terminal,
+            &sim_final.tensao_campo,
+            &caminho_excitacao,
+            integrador.t_max,
+        ) {
+            Ok(()) => {
+                escrever("Gráfico de tensão terminal e de campo gerado com sucesso!\n");
+                if should_open_images {
+                    abrir_imagem(&caminho_excitacao);
+                }
+            }
+            Err(e) => escrever(&format!(
+                "Erro ao plotar tensão terminal e de campo: {}\n",
+                e
+            )),
+        }
+    }
+
+    if case.maquina.regulador.is_some() || case.maquina.valvula_rapida.is_some() {
+        let caminho_pm = format!("{}/potencia_mecanica.png", pasta_saida);
+        match plotar_potencia_mecanica(
+            &sim_final.tempos,
+            &sim_final.potencia_mecanica,
+            &caminho_pm,
+            integrador.t_max,
+        ) {
+            Ok(()) => {
+                escrever("Gráfico de potência mecânica gerado com sucesso!\n");
+                if should_open_images {
+                    abrir_imagem(&caminho_pm);
+                }
+            }
+            Err(e) => escrever(&format!("Erro ao plotar potência mecânica: {}\n", e)),
+        }
+    }
+
+    if case.maquina.estabilizador.is_some() {
+        comparar_estabilizador(
+            case,
+            &sim_final,
+            tab,
+            tr,
+            pasta_saida,
+            should_open_images,
+            &mut escrever,
+        )?;
+    }
+
+    if let (Some(cra), Some(crr)) = (cra_final, crr_final) {
+        if let Err(e) = plotar_curva_potencia(
+            sistema.pe1,
+            sistema.pe2,
+            sistema.pe3,
+            sistema.pm,
+            cra,
+            crr,
+            &caminho_potencia,
+        ) {
+            let erro_msg = format!("Erro ao plotar CRA/CRR vs ângulo: {}\n", e);
+            escrever(&erro_msg);
+        } else {
+            escrever("Gráfico de CRA/CRR vs ângulo gerado com sucesso!\n");
+            // CORREÇÃO: Usar should_open_images diretamente
+            if should_open_images {
+                abrir_imagem(&caminho_potencia);
+            }
+        }
+    }
+
+    let end: Duration = init.elapsed();
+    escrever("\n=== FIM DA EXECUÇÃO ===\n");
+    let tempo_msg = format!("Tempo total de execução: {:.2?}\n", end);
+    escrever(&tempo_msg);
+
+    // Garantir que tudo seja escrito no arquivo
+    if let Err(e) = arquivo_resultado.flush() {
+        eprintln!("Erro ao finalizar escrita no arquivo: {}", e);
+    } else {
+        println!("Resultados salvos em: {}", caminho_resultado);
+    }
+
+    Ok(ResumoEstudo {
+        nome: nome.to_string(),
+        tab,
+        tr,
+        cra: cra_final,
+        crr: crr_final,
+        area1: areas.map(|a| a.0),
+        area2: areas.map(|a| a.1),
+        area3: areas.map(|a| a.2),
+        diferenca: areas.map(|a| a.3),
+        estabilidade,
+        tempo: end,
+    })
+}
+
+// Energia no último chaveamento, energia crítica, margem e CCT estimado pelo
+// método direto
+fn relatorio_energia(margem: &MargemEnergia, cct: Result<EstimativaCct>) -> String {
+    let funcao = &margem.funcao;
+    let mut relatorio = format!(
+        "Rede final: Pe = {} p.u., Pm = {:.4} p.u.\n\
+         Equilíbrio estável: {:.4}°, instável: {:.4}°\n\
+         Último chaveamento: t = {:.4}s, δ = {:.4}°, Δω = {:.6} rad/s\n\
+         Energia cinética: {:.6}, potencial: {:.6}, total: {:.6}\n\
+         Energia crítica: {:.6}\n\
+         Margem de energia: {:.6} ({:.2}% da crítica) -> {} pelo método direto\n",
+        funcao.pe,
+        funcao.pm,
+        funcao.delta_s.to_degrees(),
+        funcao.delta_u.to_degrees(),
+        margem.instante,
+        margem.delta.to_degrees(),
+        margem.delta_w,
+        margem.cinetica,
+        margem.potencial,
+        margem.cinetica + margem.potencial,
+        margem.critica,
+        margem.margem,
+        100.0 * margem.margem_normalizada(),
+        if margem.estavel() {
+            "estável"
+        } else {
+            "estabilidade não garantida"
+        }
+    );
+    relatorio += &match cct {
+        Ok(EstimativaCct {
+            cct: Some(cct),
+            angulo_critico: Some(delta_c),
+            ..
+        }) => format!(
+            "CCT estimado (sem religamento): {:.4}s (ângulo {:.4}° / {:.6} rad)\n",
+            cct,
+            delta_c.to_degrees(),
+            delta_c
+        ),
+        Ok(_) => "CCT estimado (sem religamento): energia crítica não atingida\n".to_string(),
+        Err(e) => format!("CCT estimado (sem religamento): indisponível ({})\n", e),
+    };
+    relatorio
+}
+
+// Participação relativa mínima (em relação à maior do modo) listada no relatório
+const PARTICIPACAO_MINIMA: f64 = 0.1;
+
+// Equilíbrio pós-falta, autovalores, frequências, amortecimentos e estados
+// participantes de cada modo
+fn relatorio_pequenos_sinais(analise: &AnalisePequenosSinais) -> String {
+    let mut relatorio = format!(
+        "Equilíbrio pós-falta: δ = {:.4}° / {:.6} rad\n\
+         Estados: {}\n\
+         Autovalores (do menos para o mais amortecido):\n",
+        analise.delta.to_degrees(),
+        analise.delta,
+        analise.estados.join(", ")
+    );
+    for modo in &analise.modos {
+        if modo.oscilatorio() {
+            relatorio += &format!(
+                "- λ = {:.4} ± j{:.4} 1/s -> f = {:.4} Hz, ζ = {:.2}%\n",
+                modo.real,
+                modo.imaginaria,
+                modo.frequencia,
+                100.0 * modo.amortecimento
+            );
+        } else {
+            relatorio += &format!("- λ = {:.4} 1/s\n", modo.real);
+        }
+        let maior = modo.participacao.iter().fold(0.0, |a: f64, &p| a.max(p));
+        let mut participantes: Vec<(&String, f64)> = analise
+            .estados
+            .iter()
+            .zip(modo.participacao.iter().copied())
+            .filter(|&(_, p)| p >= PARTICIPACAO_MINIMA * maior)
+            .collect();
+        participantes.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let participantes: Vec<String> = participantes
+            .iter()
+            .map(|(estado, p)| format!("{} {:.3}", estado, p))
+            .collect();
+        relatorio += &format!("  Participação: {}\n", participantes.join(", "));
+    }
+    relatorio += if analise.estavel() {
+        "Ponto de equilíbrio estável para pequenos sinais\n"
+    } else if !analise.instavel() {
+        "Ponto de equilíbrio sem amortecimento (autovalores no eixo imaginário)\n"
+    } else {
+        "Ponto de equilíbrio instável para pequenos sinais (autovalor com parte real positiva)\n"
+    };
+    relatorio
+}
+
+// Simula o caso sem o estabilizador, sobrepõe as trajetórias com e sem ele em
+// `comparacao_estabilizador.png` e compara o amortecimento da oscilação após o
+// último chaveamento
+fn comparar_estabilizador(
+    case: &Case,
+    sim_com: &ResultadoSimulacao,
+    tab: f64,
+    tr: f64,
+    pasta_saida: &str,
+    should_open_images: bool,
+    escrever: &mut impl FnMut(&str),
+) -> Result<()> {
+    escrever("\n=== COMPARAÇÃO COM E SEM ESTABILIZADOR ===\n");
+    let mut sem_estabilizador = case.clone();
+    sem_estabilizador.maquina.estabilizador = None;
+    let sim_sem = sim_pet_time(&sem_estabilizador, tab, tr)?;
+
+    let t_max = case.integrador.t_max;
+    let inicio = eventos::resolver(&case.eventos, &case.sistema, tab, tr)
+        .iter()
+        .map(|c| c.t)
+        .filter(|&t| t <= t_max)
+        .fold(0.0, f64::max);
+    let descrever = |sim: &ResultadoSimulacao| {
+        let estimativa = amortecimento(&sim.tempos, &sim.velocidades, inicio);
+        let texto = match estimativa {
+            Some(Amortecimento {
+                razao,
+                frequencia,
+                ciclos,
+            }) => format!(
+                "ζ = {:.2}% a {:.3} Hz ({} ciclos)",
+                100.0 * razao,
+                frequencia,
+                ciclos
+            ),
+            None => "amortecimento indeterminado (menos de dois picos)".to_string(),
+        };
+        (estimativa, texto)
+    };
+    let (sem, texto_sem) = descrever(&sim_sem);
+    let (com, texto_com) = descrever(sim_com);
+    let mut comparacao_msg = format!(
+        "Amortecimento após t = {:.4}s:\n\
+         - Sem estabilizador: {} / {}\n\
+         - Com estabilizador: {} / {}\n",
+        inicio, sim_sem.estabilidade.veredito, texto_sem, sim_com.estabilidade.veredito, texto_com
+    );
+    if let (Some(sem), Some(com)) = (sem, com) {
+        comparacao_msg += &format!(
+            "Melhoria da razão de amortecimento: {:+.2} pontos percentuais\n",
+            100.0 * (com.razao - sem.razao)
+        );
+    }
+    escrever(&comparacao_msg);
+
+    let caminho = format!("{}/comparacao_estabilizador.png", pasta_saida);
+    match plotar_comparacao(
+        &[
+            (
+                "Sem estabilizador",
+                &sim_sem.tempos,
+                &sim_sem.angulos,
+                &sim_sem.velocidades,
+            ),
+            (
+                "Com estabilizador",
+                &sim_com.tempos,
+                &sim_com.angulos,
+                &sim_com.velocidades,
+            ),
+        ],
+        &caminho,
+        t_max,
+    ) {
+        Ok(()) => {
+            escrever("Gráfico da comparação com e sem estabilizador gerado com sucesso!\n");
+            if should_open_images {
+                abrir_imagem(&caminho);
+            }
+        }
+        Err(e) => escrever(&format!(
+            "Erro ao plotar a comparação com e sem estabilizador: {}\n",
+            e
+        )),
+    }
+    Ok(())
+}
@@ -0,0 +1,144 @@
+//! Sequência de eventos de chaveamento da simulação no tempo.
+//!
+//! Cada evento troca a configuração da rede (curva potência-ângulo) e,
+//! opcionalmente, aplica um degrau na potência mecânica. O instante pode ser
+//! absoluto ou relativo a `tab`/`tr`, que são as variáveis otimizadas pelo PSO.
+
+use serde::{Deserialize, Serialize};
+
+use crate::case::SystemParams;
+use crate::curva::PowerAngleCurve;
+
+/// Evento de chaveamento declarado no arquivo de caso (`[[eventos]]`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Evento {
+    /// Instante do evento (s); com `apos`, atraso em relação à referência.
+    #[serde(default)]
+    pub t: f64,
+    /// Referência do instante (`"tab"` ou `"tr"`); se ausente, `t` é absoluto.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apos: Option<Referencia>,
+    /// Nova configuração da rede.
+    pub pe: Rede,
+    /// Novo valor da potência mecânica (degrau), se houver.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pm: Option<f64>,
+}
+
+/// Instante de referência de um evento.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Referencia {
+    /// Instante de abertura `tab`.
+    Tab,
+    /// Instante de religamento `tr` (nunca antes de `tab`).
+    Tr,
+}
+
+/// Configuração da rede após um evento: uma das curvas do sistema ou uma
+/// curva explícita (amplitude ou `{ pmax, gamma, pc }`).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Rede {
+    /// Curva do sistema (`"pe1"`, `"pe2"` ou `"pe3"`).
+    Configuracao(Configuracao),
+    /// Curva explícita.
+    Curva(PowerAngleCurve),
+}
+
+/// Curvas potência-ângulo definidas em [`SystemParams`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Configuracao {
+    /// Rede pré-falta.
+    Pe1,
+    /// Rede em falta.
+    Pe2,
+    /// Linha aberta (abertura mono/bifásica).
+    Pe3,
+}
+
+/// Evento com instante e valores já resolvidos para uma simulação.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Chaveamento {
+    /// Instante (s).
+    pub t: f64,
+    /// Curva potência-ângulo a partir do instante.
+    pub pe: PowerAngleCurve,
+    /// Configuração da rede do sistema correspondente, se o evento usa uma das
+    /// curvas `pe1`, `pe2` ou `pe3` (`None` para curvas explícitas).
+    pub configuracao: Option<Configuracao>,
+    /// Potência mecânica a partir do instante, se mudar.
+    pub pm: Option<f64>,
+}
+
+impl Rede {
+    /// Curva correspondente nos parâmetros do sistema.
+    pub fn curva(&self, sistema: &SystemParams) -> PowerAngleCurve {
+        match self {
+            Rede::Configuracao(Configuracao::Pe1) => sistema.pe1,
+            Rede::Configuracao(Configuracao::Pe2) => sistema.pe2,
+            Rede::Configuracao(Configuracao::Pe3) => sistema.pe3,
+            Rede::Curva(curva) => *curva,
+        }
+    }
+}
+
+/// Sequência clássica: falta em t = 0 (Pe2), abertura em `tab` (Pe3) e
+/// religamento em `tr` (Pe1). Usada quando o caso não declara eventos.
+pub fn sequencia_padrao() -> Vec<Evento> {
+    let evento = |t, apos, pe| Evento {
+        t,
+        apos,
+        pe: Rede::Configuracao(pe),
+        pm: None,
+    };
+    vec![
+        evento(0.0, None, Configuracao::Pe2),
+        evento(0.0, Some(Referencia::Tab), Configuracao::Pe3),
+        evento(0.0, Some(Referencia::Tr), Configuracao::Pe1),
+    ]
+}
+
+/// Resolve os instantes e curvas dos eventos para `tab` e `tr` dados,
+/// em ordem cronológica. Eventos simultâneos mantêm a ordem da declaração.
+///
+/// Lista vazia equivale à [`sequencia_padrao`]. Referências a `tr` usam
+/// `max(tr, tab)`, de modo que o religamento nunca precede a abertura.
+pub fn resolver(eventos: &[Evento], sistema: &SystemParams, tab: f64, tr: f64) -> Vec<Chaveamento> {
+    let padrao;
+    let eventos = if eventos.is_empty() {
+        padrao = sequencia_padrao();
+        &padrao
+    } else {
+        eventos
+    };
+
+    let mut chaveamentos: Vec<Chaveamento> = eventos
+        .iter()
+        .map(|evento| Chaveamento {
+            t: instante(evento.t, evento.apos, tab, tr),
+            pe: evento.pe.curva(sistema),
+            configuracao: match evento.pe {
+                Rede::Configuracao(configuracao) => Some(configuracao),
+                Rede::Curva(_) => None,
+            },
+            pm: evento.pm,
+        })
+        .collect();
+    // Ordenação estável: empates preservam a ordem declarada
+    chaveamentos.sort_by(|a, b| a.t.total_cmp(&b.t));
+    chaveamentos
+}
+
+// Instante absoluto de um evento em `t` após a referência `apos`; `tr` nunca
+// precede `tab`
+pub(crate) fn instante(t: f64, apos: Option<Referencia>, tab: f64, tr: f64) -> f64 {
+    let referencia = match apos {
+        None => 0.0,
+        Some(Referencia::Tab) => tab,
+        Some(Referencia::Tr) => tr.max(tab),
+    };
+    referencia + t
+}
@@ -0,0 +1,503 @@
+//! Sistemas de excitação (regulador de tensão e excitatriz) do gerador.
+//!
+//! A excitatriz recebe o módulo da tensão terminal `Vt` e o sinal
+//! estabilizador `Vs` e fornece a tensão de campo `Efd` ao modelo do gerador
+//! (ver [`crate::maquina::ControlledMachine`]). A referência `Vref` é ajustada
+//! na inicialização para que o equilíbrio pré-falta se mantenha.
+//!
+//! Modelos (seção `[maquina.excitatriz]`, escolhidos por `modelo`):
+//!
+//! - `"simples"`: excitatriz estática de primeira ordem,
+//!   `TA·dEfd/dt = KA·(Vref - Vt + Vs) - Efd`, com `Efd` entre `efd_min` e
+//!   `efd_max`;
+//! - `"st1a"`: IEEE ST1A (retificador controlado alimentado pelo terminal),
+//!   com transdutor `TR`, avanço-atraso `TC/TB`, limites de entrada
+//!   `VIMIN/VIMAX`, amplificador `KA/(1 + s·TA)` limitado a `VAMIN/VAMAX` e
+//!   teto proporcional à tensão terminal, `Vt·VRMIN ≤ Efd ≤ Vt·VRMAX`;
+//! - `"dc1a"`: IEEE DC1A (excitatriz de corrente contínua), com transdutor,
+//!   avanço-atraso, regulador `KA/(1 + s·TA)` limitado a `VRMIN/VRMAX`,
+//!   excitatriz `TE·dEfd/dt = VR - (KE + SE(Efd))·Efd` e realimentação
+//!   estabilizadora `KF·s/(1 + s·TF)`.
+//!
+//! Os limites dos blocos de atraso são do tipo sem acúmulo (*non-windup*): o
+//! estado para no limite enquanto a derivada apontar para fora dele. O
+//! carregamento da excitatriz pela corrente de campo (`KC`) é desprezado.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::blocos::{avanco_atraso, limite, sem_acumulo, um};
+use crate::error::{Error, Result};
+
+/// Modelo de sistema de excitação acoplado ao gerador.
+pub trait ExciterModel: fmt::Debug {
+    /// Número de estados.
+    fn dimensao(&self) -> usize;
+
+    /// Ajusta `Vref` ao equilíbrio com tensão de campo `efd` e tensão
+    /// terminal `vt` e retorna o estado inicial. Falha se `efd` estiver fora
+    /// dos limites.
+    fn inicializar(&mut self, efd: f64, vt: f64) -> Result<Vec<f64>>;
+
+    /// Tensão de campo `Efd` (p.u.) no estado `x` com tensão terminal `vt`.
+    fn efd(&self, x: &[f64], vt: f64) -> f64;
+
+    /// Derivadas do estado `x` com tensão terminal `vt` e sinal
+    /// estabilizador `vs`.
+    fn derivada(&self, x: &[f64], vt: f64, vs: f64, dxdt: &mut [f64]);
+}
+
+/// Sistema de excitação de `[maquina.excitatriz]`, escolhido por `modelo`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "modelo", rename_all = "snake_case")]
+pub enum ExciterSettings {
+    /// Excitatriz estática de primeira ordem (`"simples"`).
+    Simples(SimpleExciter),
+    /// IEEE ST1A (`"st1a"`).
+    St1a(St1a),
+    /// IEEE DC1A (`"dc1a"`).
+    Dc1a(Dc1a),
+}
+
+impl ExciterSettings {
+    /// Nome do modelo para os relatórios.
+    pub fn nome(&self) -> &'static str {
+        match self {
+            ExciterSettings::Simples(_) => "simples",
+            ExciterSettings::St1a(_) => "ST1A",
+            ExciterSettings::Dc1a(_) => "DC1A",
+        }
+    }
+
+    /// Verifica os ganhos, constantes de tempo e limites do modelo.
+    pub fn validar(&self) -> Result<()> {
+        let (valido, condicao) = match self {
+            ExciterSettings::Simples(e) => (
+                e.ka > 0.0 && e.ta > 0.0 && e.efd_min < e.efd_max,
+                "ka e ta positivos e efd_min < efd_max",
+            ),
+            ExciterSettings::St1a(e) => (
+                e.ka > 0.0
+                    && e.ta > 0.0
+                    && e.tr >= 0.0
+                    && e.tb >= 0.0
+                    && e.tc >= 0.0
+                    && e.vimin < e.vimax
+                    && e.vamin < e.vamax
+                    && e.vrmin < e.vrmax,
+                "ka e ta positivos, tr, tb e tc não negativos e limites mínimos menores que os \
+                 máximos",
+            ),
+            ExciterSettings::Dc1a(e) => (
+                e.ka > 0.0
+                    && e.ta > 0.0
+                    && e.te > 0.0
+                    && e.tf > 0.0
+                    && e.tr >= 0.0
+                    && e.tb >= 0.0
+                    && e.tc >= 0.0
+                    && e.kf >= 0.0
+                    && e.vrmin < e.vrmax
+                    && e.saturacao_valida(),
+                "ka, ta, te e tf positivos, tr, tb, tc e kf não negativos, vrmin < vrmax e, \
+                 com saturação, se1 e se2 positivos, 0 < e1 < e2 e se2·e2 > se1·e1",
+            ),
+        };
+        if valido {
+            Ok(())
+        } else {
+            Err(Error::Configuracao(format!(
+                "a excitatriz {} requer {}",
+                self.nome(),
+                condicao
+            )))
+        }
+    }
+
+    /// Modelo da excitatriz, ainda não inicializado.
+    pub fn modelo(&self) -> Box<dyn ExciterModel> {
+        match self {
+            ExciterSettings::Simples(e) => Box::new(e.clone()),
+            ExciterSettings::St1a(e) => Box::new(e.clone()),
+            ExciterSettings::Dc1a(e) => Box::new(e.clone()),
+        }
+    }
+}
+
+/// Excitatriz estática de primeira ordem com estado `[Efd]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SimpleExciter {
+    /// Ganho `KA`.
+    pub ka: f64,
+    /// Constante de tempo `TA` (s).
+    pub ta: f64,
+    /// Limite superior de `Efd` (p.u.).
+    pub efd_max: f64,
+    /// Limite inferior de `Efd` (p.u.).
+    pub efd_min: f64,
+    /// Referência de tensão, ajustada na inicialização.
+    #[serde(skip)]
+    pub vref: f64,
+}
+
+impl ExciterModel for SimpleExciter {
+    fn dimensao(&self) -> usize {
+        1
+    }
+
+    fn inicializar(&mut self, efd: f64, vt: f64) -> Result<Vec<f64>> {
+        limite("Efd", efd, self.efd_min, self.efd_max, "da excitatriz")?;
+        self.vref = vt + efd / self.ka;
+        Ok(vec![efd])
+    }
+
+    fn efd(&self, x: &[f64], _vt: f64) -> f64 {
+        x[0].clamp(self.efd_min, self.efd_max)
+    }
+
+    fn derivada(&self, x: &[f64], vt: f64, vs: f64, dxdt: &mut [f64]) {
+        let derivada = (self.ka * (self.vref - vt + vs) - x[0]) / self.ta;
+        dxdt[0] = sem_acumulo(x[0], derivada, self.efd_min, self.efd_max);
+    }
+}
+
+/// IEEE ST1A com estado `[Vc, avanço-atraso, VA]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct St1a {
+    /// Constante de tempo do transdutor de tensão `TR` (s; padrão: 0,02).
+    #[serde(default = "tr_padrao")]
+    pub tr: f64,
+    /// Limite superior do erro de tensão `VIMAX` (p.u.; padrão: sem limite).
+    #[serde(default = "infinito")]
+    pub vimax: f64,
+    /// Limite inferior do erro de tensão `VIMIN` (p.u.; padrão: sem limite).
+    #[serde(default = "menos_infinito")]
+    pub vimin: f64,
+    /// Constante de tempo de avanço `TC` (s; padrão: 0).
+    #[serde(default)]
+    pub tc: f64,
+    /// Constante de tempo de atraso `TB` (s; padrão: 0, sem avanço-atraso).
+    #[serde(default)]
+    pub tb: f64,
+    /// Ganho do amplificador `KA`.
+    pub ka: f64,
+    /// Constante de tempo do amplificador `TA` (s).
+    pub ta: f64,
+    /// Limite superior da saída do amplificador `VAMAX` (p.u.; padrão: sem
+    /// limite).
+    #[serde(default = "infinito")]
+    pub vamax: f64,
+    /// Limite inferior da saída do amplificador `VAMIN` (p.u.; padrão: sem
+    /// limite).
+    #[serde(default = "menos_infinito")]
+    pub vamin: f64,
+    /// Teto positivo `VRMAX` (p.u. de `Efd` por p.u. de `Vt`).
+    pub vrmax: f64,
+    /// Teto negativo `VRMIN` (p.u. de `Efd` por p.u. de `Vt`).
+    pub vrmin: f64,
+    /// Referência de tensão, ajustada na inicialização.
+    #[serde(skip)]
+    pub vref: f64,
+}
+
+impl ExciterModel for St1a {
+    fn dimensao(&self) -> usize {
+        3
+    }
+
+    fn inicializar(&mut self, efd: f64, vt: f64) -> Result<Vec<f64>> {
+        limite(
+            "Efd",
+            efd,
+            vt * self.vrmin,
+            vt * self.vrmax,
+            "da excitatriz",
+        )?;
+        limite("VA", efd, self.vamin, self.vamax, "da excitatriz")?;
+        let erro = efd / self.ka;
+        limite("VI", erro, self.vimin, self.vimax, "da excitatriz")?;
+        self.vref = vt + erro;
+        Ok(vec![vt, erro, efd])
+    }
+
+    fn efd(&self, x: &[f64], vt: f64) -> f64 {
+        x[2].clamp(vt * self.vrmin, vt * self.vrmax)
+    }
+
+    fn derivada(&self, x: &[f64], vt: f64, vs: f64, dxdt: &mut [f64]) {
+        let (vc, avanco, va) = (x[0], x[1], x[2]);
+        dxdt[0] = transdutor(vt, vc, self.tr);
+        let erro = (self.vref - vc + vs).clamp(self.vimin, self.vimax);
+        let (saida, derivada) = avanco_atraso(erro, avanco, self.tc, self.tb);
+        dxdt[1] = derivada;
+        let derivada_va = (self.ka * saida - va) / self.ta;
+        dxdt[2] = sem_acumulo(va, derivada_va, self.vamin, self.vamax);
+    }
+}
+
+/// IEEE DC1A com estado `[Vc, avanço-atraso, VR, Efd, realimentação]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Dc1a {
+    /// Constante de tempo do transdutor de tensão `TR` (s; padrão: 0,02).
+    #[serde(default = "tr_padrao")]
+    pub tr: f64,
+    /// Ganho do regulador `KA`.
+    pub ka: f64,
+    /// Constante de tempo do regulador `TA` (s).
+    pub ta: f64,
+    /// Constante de tempo de atraso `TB` (s; padrão: 0, sem avanço-atraso).
+    #[serde(default)]
+    pub tb: f64,
+    /// Constante de tempo de avanço `TC` (s; padrão: 0).
+    #[serde(default)]
+    pub tc: f64,
+    /// Limite superior da saída do regulador `VRMAX` (p.u.).
+    pub vrmax: f64,
+    /// Limite inferior da saída do regulador `VRMIN` (p.u.).
+    pub vrmin: f64,
+    /// Constante da excitatriz `KE` (padrão: 1, excitação independente).
+    #[serde(default = "um")]
+    pub ke: f64,
+    /// Constante de tempo da excitatriz `TE` (s).
+    pub te: f64,
+    /// Ganho da realimentação estabilizadora `KF` (padrão: 0).
+    #[serde(default)]
+    pub kf: f64,
+    /// Constante de tempo da realimentação estabilizadora `TF` (s; padrão:
+    /// 1).
+    #[serde(default = "um")]
+    pub tf: f64,
+    /// Tensão de campo do primeiro ponto da curva de saturação `E1` (p.u.).
+    #[serde(default)]
+    pub e1: f64,
+    /// Saturação em `E1`, `SE(E1)` (padrão: 0, sem saturação).
+    #[serde(default)]
+    pub se1: f64,
+    /// Tensão de campo do segundo ponto da curva de saturação `E2` (p.u.).
+    #[serde(default)]
+    pub e2: f64,
+    /// Saturação em `E2`, `SE(E2)` (padrão: 0, sem saturação).
+    #[serde(default)]
+    pub se2: f64,
+    /// Referência de tensão, ajustada na inicialização.
+    #[serde(skip)]
+    pub vref: f64,
+}
+
+impl Dc1a {
+    // Sem saturação (SE1 = SE2 = 0) ou com os dois pontos da curva crescentes
+    // em tensão e em SE·Efd, dos quais `saturacao` depende
+    fn saturacao_valida(&self) -> bool {
+        if self.se1 == 0.0 && self.se2 == 0.0 {
+            return true;
+        }
+        self.se1 > 0.0
+            && self.se2 > 0.0
+            && 0.0 < self.e1
+            && self.e1 < self.e2
+            && self.se2 * self.e2 > self.se1 * self.e1
+    }
+
+    // Função de saturação SE(Efd) = B·(Efd - A)²/Efd, ajustada aos pontos
+    // (E1, SE1) e (E2, SE2); nula sem dados de saturação
+    fn saturacao(&self, efd: f64) -> f64 {
+        if self.se1 <= 0.0 || self.se2 <= 0.0 || efd <= 0.0 {
+            return 0.0;
+        }
+        let razao = (self.se1 * self.e1 / (self.se2 * self.e2)).sqrt();
+        let a = (self.e1 - razao * self.e2) / (1.0 - razao);
+        let b = self.se2 * self.e2 / (self.e2 - a).powi(2);
+        if efd > a {
+            b * (efd - a).powi(2) / efd
+        } else {
+            0.0
+        }
+    }
+}
+
+impl ExciterModel for Dc1a {
+    fn dimensao(&self) -> usize {
+        5
+    }
+
+    fn inicializar(&mut self, efd: f64, vt: f64) -> Result<Vec<f64>> {
+        let vr = (self.ke + self.saturacao(efd)) * efd;
+        limite("VR", vr, self.vrmin, self.vrmax, "da excitatriz")?;
+        let erro = vr / self.ka;
+        self.vref = vt + erro;
+        Ok(vec![vt, erro, vr, efd, efd])
+    }
+
+    fn efd(&self, x: &[f64], _vt: f64) -> f64 {
+        x[3]
+    }
+
+    fn derivada(&self, x: &[f64], vt: f64, vs: f64, dxdt: &mut [f64]) {
+        let (vc, avanco, vr, efd, realimentacao) = (x[0], x[1], x[2], x[3], x[4]);
+        dxdt[0] = transdutor(vt, vc, self.tr);
+        // VF = KF·s/(1 + s·TF)·Efd = KF/TF·(Efd - estado)
+        let vf = self.kf / self.tf * (efd - realimentacao);
+        dxdt[4] = (efd - realimentacao) / self.tf;
+        let (saida, derivada) = avanco_atraso(self.vref - vc + vs - vf, avanco, self.tc, self.tb);
+        dxdt[1] = derivada;
+        let derivada_vr = (self.ka * saida - vr) / self.ta;
+        dxdt[2] = sem_acumulo(vr, derivada_vr, self.vrmin, self.vrmax);
+        dxdt[3] = (vr - (self.ke + self.saturacao(efd)) * efd) / self.te;
+    }
+}
+
+// Derivada do transdutor de tensão 1/(1 + s·TR); com TR nulo, o estado
+// acompanha a entrada rapidamente
+fn transdutor(vt: f64, vc: f64, tr: f64) -> f64 {
+    (vt - vc) / tr.max(1e-3)
+}
+
+fn tr_padrao() -> f64 {
+    0.02
+}
+
+fn infinito() -> f64 {
+    f64::INFINITY
+}
+
+fn menos_infinito() -> f64 {
+    f64::NEG_INFINITY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dc1a(saturacao: &str) -> ExciterSettings {
+        toml::from_str(&format!(
+            "modelo = \"dc1a\"\n\
+             ka = 46.0\n\
+             ta = 0.06\n\
+             vrmax = 3.0\n\
+             vrmin = -2.0\n\
+             te = 0.46\n\
+             kf = 0.1\n\
+             {}",
+            saturacao
+        ))
+        .unwrap()
+    }
+
+    fn excitatrizes() -> Vec<ExciterSettings> {
+        vec![
+            toml::from_str(
+                "modelo = \"simples\"\n\
+                 ka = 200.0\n\
+                 ta = 0.02\n\
+                 efd_max = 5.0\n\
+                 efd_min = -4.0\n",
+            )
+            .unwrap(),
+            toml::from_str(
+                "modelo = \"st1a\"\n\
+                 tc = 1.0\n\
+                 tb = 10.0\n\
+                 ka = 200.0\n\
+                 ta = 0.02\n\
+                 vamax = 6.0\n\
+                 vrmax = 5.0\n\
+                 vrmin = -4.0\n",
+            )
+            .unwrap(),
+            dc1a("e1 = 2.3\nse1 = 0.1\ne2 = 3.1\nse2 = 0.33\n"),
+        ]
+    }
+
+    #[test]
+    fn saturacao_exige_pontos_crescentes() {
+        assert!(dc1a("").validar().is_ok());
+        assert!(
+            dc1a("e1 = 2.3\nse1 = 0.1\ne2 = 3.1\nse2 = 0.33\n")
+                .validar()
+                .is_ok()
+        );
+        for invalida in [
+            // E1 acima de E2
+            "e1 = 3.1\nse1 = 0.33\ne2 = 2.3\nse2 = 0.1\n",
+            // SE·Efd decrescente
+            "e1 = 2.3\nse1 = 0.5\ne2 = 3.1\nse2 = 0.33\n",
+            // Só um dos pontos
+            "e2 = 3.1\nse2 = 0.33\n",
+        ] {
+            assert!(
+                matches!(dc1a(invalida).validar(), Err(Error::Configuracao(_))),
+                "{}",
+                invalida
+            );
+        }
+    }
+
+    #[test]
+    fn curva_de_saturacao_passa_pelos_pontos() {
+        let ExciterSettings::Dc1a(e) = dc1a("e1 = 2.3\nse1 = 0.1\ne2 = 3.1\nse2 = 0.33\n") else {
+            unreachable!()
+        };
+        assert!((e.saturacao(2.3) - 0.1).abs() < 1e-12);
+        assert!((e.saturacao(3.1) - 0.33).abs() < 1e-12);
+    }
+
+    #[test]
+    fn inicializacao_fica_em_equilibrio() {
+        let (efd, vt) = (2.0, 1.02);
+        for configuracao in excitatrizes() {
+            let mut excitatriz = configuracao.modelo();
+            let x = excitatriz.inicializar(efd, vt).unwrap();
+            let mut dxdt = vec![f64::NAN; excitatriz.dimensao()];
+            excitatriz.derivada(&x, vt, 0.0, &mut dxdt);
+            assert!(
+                dxdt.iter().all(|d| d.abs() < 1e-12),
+                "{}: {:?}",
+                configuracao.nome(),
+                dxdt
+            );
+            assert!(
+                (excitatriz.efd(&x, vt) - efd).abs() < 1e-12,
+                "{}",
+                configuracao.nome()
+            );
+        }
+    }
+
+    #[test]
+    fn limites_sem_acumulo() {
+        // Estado com limite sem acúmulo de cada modelo (Efd, VA e VR) e o seu
+        // limite superior
+        let limitados = [(0, 5.0), (2, 6.0), (2, 3.0)];
+        for (configuracao, (estado, maximo)) in excitatrizes().into_iter().zip(limitados) {
+            let nome = configuracao.nome();
+            let mut excitatriz = configuracao.modelo();
+            assert!(
+                matches!(
+                    excitatriz.inicializar(10.0, 1.0),
+                    Err(Error::Configuracao(_))
+                ),
+                "{}",
+                nome
+            );
+            let mut x = excitatriz.inicializar(2.0, 1.0).unwrap();
+            x[estado] = maximo;
+            let mut dxdt = vec![f64::NAN; excitatriz.dimensao()];
+            // No limite, o sinal que empurra para fora não altera o estado,
+            // e o que empurra para dentro o tira do limite
+            excitatriz.derivada(&x, 1.0, 1.0, &mut dxdt);
+            assert_eq!(dxdt[estado], 0.0, "{}", nome);
+            excitatriz.derivada(&x, 1.0, -1.0, &mut dxdt);
+            assert!(dxdt[estado] < 0.0, "{}", nome);
+        }
+    }
+
+    #[test]
+    fn teto_do_st1a_acompanha_a_tensao_terminal() {
+        let excitatriz = excitatrizes().swap_remove(1).modelo();
+        // VA = 6 acima do teto Vt·VRMAX = 0,5·5
+        assert_eq!(excitatriz.efd(&[0.5, 0.0, 6.0], 0.5), 2.5);
+    }
+}
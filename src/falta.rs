@@ -0,0 +1,137 @@
+//! Tipos de falta e de abertura da linha e suas reatâncias equivalentes na
+//! rede de sequência positiva.
+//!
+//! Pelas componentes simétricas, uma falta desequilibrada é vista pela rede de
+//! sequência positiva como uma reatância em derivação `Δ` no ponto de falta,
+//! formada pelas reatâncias de Thévenin de sequência negativa (`X2`) e zero
+//! (`X0`) nesse ponto e pela reatância de falta `Xf`:
+//!
+//! | Falta           | `Δ`                            |
+//! |-----------------|--------------------------------|
+//! | trifásica       | `Xf`                           |
+//! | fase-terra      | `X2 + X0 + 3·Xf`               |
+//! | fase-fase       | `X2 + Xf`                      |
+//! | fase-fase-terra | `X2 ∥ (X0 + 3·Xf)`             |
+//!
+//! Quanto maior `Δ`, menor o impacto da falta na potência transferida
+//! (`Δ = 0`: curto franco trifásico; `Δ` infinito: sem falta).
+//!
+//! Na abertura de uma ou duas fases da linha em falta (tempo morto do
+//! religamento monopolar), as fases abertas aparecem como uma reatância em
+//! série com a linha, formada pelas reatâncias de Thévenin de sequência
+//! negativa e zero vistas entre os pontos abertos:
+//!
+//! | Abertura  | Reatância série        |
+//! |-----------|------------------------|
+//! | tripolar  | infinita (linha fora)  |
+//! | monopolar | `X2 ∥ X0`              |
+//! | bipolar   | `X2 + X0`              |
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Tipo da falta aplicada na linha (campo `tipo_falta` da seção `[rede]`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TipoFalta {
+    /// Falta trifásica (`"trifasica"`).
+    #[default]
+    Trifasica,
+    /// Falta fase-terra (`"fase_terra"`).
+    FaseTerra,
+    /// Falta fase-fase (`"fase_fase"`).
+    FaseFase,
+    /// Falta fase-fase-terra (`"fase_fase_terra"`).
+    FaseFaseTerra,
+}
+
+impl TipoFalta {
+    /// `true` se a falta usa a rede de sequência negativa.
+    pub fn usa_sequencia_negativa(&self) -> bool {
+        *self != TipoFalta::Trifasica
+    }
+
+    /// `true` se a falta envolve a terra e usa a rede de sequência zero.
+    pub fn usa_sequencia_zero(&self) -> bool {
+        matches!(self, TipoFalta::FaseTerra | TipoFalta::FaseFaseTerra)
+    }
+
+    /// Reatância em derivação `Δ` inserida no ponto de falta da rede de
+    /// sequência positiva, a partir das reatâncias de Thévenin `x2` e `x0` no
+    /// ponto de falta e da reatância de falta `xf` (p.u.). As reatâncias de
+    /// sequência não usadas pelo tipo de falta são ignoradas.
+    pub fn reatancia_derivacao(&self, x2: f64, x0: f64, xf: f64) -> f64 {
+        match self {
+            TipoFalta::Trifasica => xf,
+            TipoFalta::FaseTerra => x2 + x0 + 3.0 * xf,
+            TipoFalta::FaseFase => x2 + xf,
+            TipoFalta::FaseFaseTerra => paralelo(x2, x0 + 3.0 * xf),
+        }
+    }
+}
+
+/// Fases abertas na linha em falta durante o tempo morto (campo
+/// `tipo_abertura` da seção `[rede]`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TipoAbertura {
+    /// As três fases abertas: a linha sai de operação (`"tripolar"`).
+    #[default]
+    Tripolar,
+    /// Uma fase aberta (`"monopolar"`).
+    Monopolar,
+    /// Duas fases abertas (`"bipolar"`).
+    Bipolar,
+}
+
+impl TipoAbertura {
+    /// `true` se a abertura usa as redes de sequência negativa e zero.
+    pub fn usa_sequencias(&self) -> bool {
+        *self != TipoAbertura::Tripolar
+    }
+
+    /// Reatância inserida em série com a linha na rede de sequência positiva,
+    /// a partir das reatâncias de Thévenin `x2` e `x0` entre os pontos abertos
+    /// (p.u.); infinita na abertura tripolar.
+    pub fn reatancia_serie(&self, x2: f64, x0: f64) -> f64 {
+        match self {
+            TipoAbertura::Tripolar => f64::INFINITY,
+            TipoAbertura::Monopolar => paralelo(x2, x0),
+            TipoAbertura::Bipolar => x2 + x0,
+        }
+    }
+}
+
+impl fmt::Display for TipoAbertura {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TipoAbertura::Tripolar => write!(f, "tripolar"),
+            TipoAbertura::Monopolar => write!(f, "monopolar"),
+            TipoAbertura::Bipolar => write!(f, "bipolar"),
+        }
+    }
+}
+
+impl fmt::Display for TipoFalta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TipoFalta::Trifasica => write!(f, "trifásica"),
+            TipoFalta::FaseTerra => write!(f, "fase-terra"),
+            TipoFalta::FaseFase => write!(f, "fase-fase"),
+            TipoFalta::FaseFaseTerra => write!(f, "fase-fase-terra"),
+        }
+    }
+}
+
+// Associação em paralelo; uma reatância infinita (rede aberta) não contribui
+fn paralelo(a: f64, b: f64) -> f64 {
+    if a.is_infinite() {
+        b
+    } else if b.is_infinite() {
+        a
+    } else if a + b == 0.0 {
+        0.0
+    } else {
+        a * b / (a + b)
+    }
+}
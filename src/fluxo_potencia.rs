@@ -0,0 +1,238 @@
+//! Fluxo de potência por Newton-Raphson (forma polar) para obter o ponto de
+//! operação de um sistema multimáquinas importado.
+//!
+//! As cargas `pl`/`ql` das barras são de potência constante e as derivações
+//! `gs`/`bs` entram na matriz de admitância. Os limites de potência reativa dos
+//! geradores não são considerados.
+
+use crate::algebra::resolver_linear;
+use crate::complexo::Complexo;
+use crate::error::{Error, Result};
+use crate::multimaquina::{self, MultiMachineSystem};
+
+const TOLERANCIA: f64 = 1e-8;
+const MAX_ITERACOES: usize = 30;
+
+/// Tipo da barra no fluxo de potência.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TipoBarra {
+    /// Potências ativa e reativa especificadas.
+    Pq,
+    /// Potência ativa e módulo da tensão especificados.
+    Pv,
+    /// Módulo e ângulo da tensão especificados (barra de referência).
+    Referencia,
+}
+
+// Resolve o fluxo de potência de `sistema` a partir das tensões das barras
+// (estimativa inicial; módulo especificado nas barras PV e de referência) e
+// grava o resultado: tensões das barras, `p` dos geradores da barra de
+// referência e `q` dos geradores das barras PV e de referência. Nas barras
+// com mais de um gerador, a potência ativa de referência vai para o primeiro
+// e a reativa é dividida igualmente. Retorna o número de iterações.
+pub(crate) fn resolver(sistema: &mut MultiMachineSystem, tipos: &[TipoBarra]) -> Result<usize> {
+    let y = multimaquina::matriz_admitancia(sistema)?;
+    let n = sistema.barras.len();
+    let mut v: Vec<f64> = sistema.barras.iter().map(|b| b.v).collect();
+    let mut theta: Vec<f64> = sistema
+        .barras
+        .iter()
+        .map(|b| b.theta.to_radians())
+        .collect();
+
+    // Injeções especificadas: geração menos carga
+    let mut p_esp: Vec<f64> = sistema.barras.iter().map(|b| -b.pl).collect();
+    let mut q_esp: Vec<f64> = sistema.barras.iter().map(|b| -b.ql).collect();
+    let indice = |id: usize| sistema.barras.iter().position(|b| b.id == id);
+    for gerador in &sistema.geradores {
+        let k = indice(gerador.barra)
+            .ok_or_else(|| Error::Rede(format!("barra {} inexistente", gerador.barra)))?;
+        p_esp[k] += gerador.p;
+        q_esp[k] += gerador.q;
+    }
+
+    // Incógnitas: ângulos das barras PV e PQ seguidos dos módulos das PQ
+    let angulos: Vec<usize> = (0..n)
+        .filter(|&k| tipos[k] != TipoBarra::Referencia)
+        .collect();
+    let modulos: Vec<usize> = (0..n).filter(|&k| tipos[k] == TipoBarra::Pq).collect();
+
+    let mut iteracoes = 0;
+    loop {
+        let (p, q) = injecoes(&y, &v, &theta);
+        let residuo: Vec<f64> = angulos
+            .iter()
+            .map(|&k| p_esp[k] - p[k])
+            .chain(modulos.iter().map(|&k| q_esp[k] - q[k]))
+            .collect();
+        if residuo.iter().all(|r| r.abs() < TOLERANCIA) {
+            break;
+        }
+        if iteracoes == MAX_ITERACOES {
+            return Err(Error::Convergencia(format!(
+                "fluxo de potência não convergiu em {} iterações",
+                MAX_ITERACOES
+            )));
+        }
+        iteracoes += 1;
+
+        let jacobiana = jacobiana(&y, &v, &theta, &p, &q, &angulos, &modulos);
+        let correcao = resolver_linear(jacobiana, residuo).ok_or_else(|| {
+            Error::Convergencia("jacobiana singular no fluxo de potência".to_string())
+        })?;
+        for (&k, dx) in angulos.iter().zip(&correcao) {
+            theta[k] += dx;
+        }
+        for (&k, dx) in modulos.iter().zip(&correcao[angulos.len()..]) {
+            v[k] += dx;
+        }
+    }
+
+    let (p, q) = injecoes(&y, &v, &theta);
+    for (k, barra) in sistema.barras.iter_mut().enumerate() {
+        barra.v = v[k];
+        barra.theta = theta[k].to_degrees();
+        // Geração total da barra
+        p_esp[k] = p[k] + barra.pl;
+        q_esp[k] = q[k] + barra.ql;
+    }
+    for (k, barra) in sistema.barras.iter().enumerate() {
+        let na_barra: Vec<usize> = (0..sistema.geradores.len())
+            .filter(|&i| sistema.geradores[i].barra == barra.id)
+            .collect();
+        if na_barra.is_empty() {
+            continue;
+        }
+        if tipos[k] == TipoBarra::Referencia {
+            let p_outros: f64 = na_barra[1..].iter().map(|&i| sistema.geradores[i].p).sum();
+            sistema.geradores[na_barra[0]].p = p_esp[k] - p_outros;
+        }
+        if tipos[k] != TipoBarra::Pq {
+            for &i in &na_barra {
+                sistema.geradores[i].q = q_esp[k] / na_barra.len() as f64;
+            }
+        }
+    }
+    Ok(iteracoes)
+}
+
+// Potências ativa e reativa injetadas em cada barra: S_k = V_k · conj(Σ Y_km·V_m)
+fn injecoes(y: &[Vec<Complexo>], v: &[f64], theta: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    let tensoes: Vec<Complexo> = v
+        .iter()
+        .zip(theta)
+        .map(|(&v, &theta)| Complexo::polar(v, theta))
+        .collect();
+    tensoes
+        .iter()
+        .zip(y)
+        .map(|(&vk, linha)| {
+            let corrente = linha
+                .iter()
+                .zip(&tensoes)
+                .fold(Complexo::ZERO, |soma, (&ykm, &vm)| soma + ykm * vm);
+            let s = vk * corrente.conj();
+            (s.re, s.im)
+        })
+        .unzip()
+}
+
+// Jacobiana das injeções em relação aos ângulos (`angulos`) e módulos
+// (`modulos`) das tensões, com as linhas de P seguidas das de Q
+fn jacobiana(
+    y: &[Vec<Complexo>],
+    v: &[f64],
+    theta: &[f64],
+    p: &[f64],
+    q: &[f64],
+    angulos: &[usize],
+    modulos: &[usize],
+) -> Vec<Vec<f64>> {
+    // Derivadas de (P_i, Q_i) em relação a θ_k e V_k
+    let derivadas = |i: usize, k: usize| -> [f64; 4] {
+        let (g, b) = (y[i][k].re, y[i][k].im);
+        if i == k {
+            [
+                -q[i] - b * v[i] * v[i],
+                p[i] / v[i] + g * v[i],
+                p[i] - g * v[i] * v[i],
+                q[i] / v[i] - b * v[i],
+            ]
+        } else {
+            let (sen, cos) = (theta[i] - theta[k]).sin_cos();
+            let a = g * sen - b * cos;
+            let c = g * cos + b * sen;
+            [v[i] * v[k] * a, v[i] * c, -v[i] * v[k] * c, v[i] * a]
+        }
+    };
+    let linha = |i: usize, reativa: bool| -> Vec<f64> {
+        let desloc = if reativa { 2 } else { 0 };
+        angulos
+            .iter()
+            .map(|&k| derivadas(i, k)[desloc])
+            .chain(modulos.iter().map(|&k| derivadas(i, k)[desloc + 1]))
+            .collect()
+    };
+    angulos
+        .iter()
+        .map(|&i| linha(i, false))
+        .chain(modulos.iter().map(|&i| linha(i, true)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Rede radial sem perdas 1 - 2 - 3: referência na barra 1, gerador PV na
+    // barra 2 e carga puramente ativa na barra 3
+    fn sistema() -> MultiMachineSystem {
+        toml::from_str(
+            "barras = [{ id = 1 }, { id = 2 }, { id = 3, pl = 0.5 }]\n\
+             geradores = [\n\
+                 { barra = 1, p = 0.0, h = 5.0, xd_transitoria = 0.2 },\n\
+                 { barra = 2, p = 0.2, h = 3.0, xd_transitoria = 0.3 },\n\
+             ]\n\
+             ramos = [{ de = 1, para = 2, x = 0.5 }, { de = 2, para = 3, x = 0.4 }]\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn fluxo_de_tres_barras_tem_solucao_fechada() {
+        let mut sistema = sistema();
+        let tipos = [TipoBarra::Referencia, TipoBarra::Pv, TipoBarra::Pq];
+        let iteracoes = resolver(&mut sistema, &tipos).unwrap();
+        assert!(iteracoes > 0 && iteracoes < 10);
+
+        let perto = |a: f64, b: f64| (a - b).abs() < 1e-8;
+        // Sem perdas, a referência cobre o restante da carga: 0,5 - 0,2
+        assert!(perto(sistema.geradores[0].p, 0.3));
+        // Ramo 1-2 (V1 = V2 = 1): sin(θ1 - θ2) = 0,3 · 0,5
+        let theta_2 = -(0.3_f64 * 0.5).asin();
+        assert!(perto(sistema.barras[1].theta.to_radians(), theta_2));
+        // Ramo 2-3 com Q3 = 0: V3 = cos θ23 e V3·sin θ23 / 0,4 = 0,5,
+        // logo sin(2·θ23) = 2 · 0,5 · 0,4
+        let theta_23 = (2.0_f64 * 0.5 * 0.4).asin() / 2.0;
+        assert!(perto(sistema.barras[2].v, theta_23.cos()));
+        assert!(perto(
+            sistema.barras[2].theta.to_radians(),
+            theta_2 - theta_23
+        ));
+        // O reativo gerado é o consumido pelas linhas, |V_de - V_para|² / x
+        let q_linhas = (2.0 - 2.0 * theta_2.cos()) / 0.5 + theta_23.sin().powi(2) / 0.4;
+        let q_gerado = sistema.geradores[0].q + sistema.geradores[1].q;
+        assert!(perto(q_gerado, q_linhas), "{} != {}", q_gerado, q_linhas);
+    }
+
+    #[test]
+    fn rede_desconexa_nao_converge() {
+        let mut sistema = sistema();
+        sistema.ramos[1].em_servico = false;
+        let tipos = [TipoBarra::Referencia, TipoBarra::Pv, TipoBarra::Pq];
+        assert!(matches!(
+            resolver(&mut sistema, &tipos),
+            Err(Error::Convergencia(_))
+        ));
+    }
+}
@@ -0,0 +1,126 @@
+//! Análise de estabilidade transitória de um gerador síncrono contra barra
+//! infinita, com simulação no tempo por Runge-Kutta, método das áreas iguais e
+//! otimização dos tempos de abertura (`tab`) e religamento (`tr`) por PSO.
+//!
+//! Organização da API:
+//!
+//! - Modelo do sistema e arquivo de caso: [`case`] ([`Case`], [`SystemParams`]) e [`curva`]
+//!   ([`PowerAngleCurve`]), [`rede`] ([`SmibNetwork`]) e [`falta`] ([`TipoFalta`], [`TipoAbertura`])
+//! - Simulador: [`ode`] ([`OdeSystem`], [`Integrator`], [`Rk4`]), [`rk45`] ([`Rk45`]), [`swing`]
+//!   ([`SwingEquation`]), [`runge_kutta`], [`eventos`] ([`Evento`]) e [`sim_per_time`]
+//!   ([`sim_pet_time`])
+//! - Modelos do gerador: [`maquina`] ([`MachineModel`], [`modelo_maquina`]), [`um_eixo`]
+//!   ([`FluxDecay`]), [`dois_eixos`] ([`TwoAxis`]) e [`subtransitorio`] ([`SixthOrder`]), com
+//!   sistemas de excitação em [`excitacao`] ([`ExciterModel`], [`ExciterSettings`]),
+//!   estabilizadores em [`estabilizador`] ([`StabilizerModel`], [`StabilizerSettings`]) e
+//!   reguladores de velocidade em [`turbina`] ([`GovernorModel`], [`GovernorSettings`])
+//! - Método das áreas iguais: [`area`] ([`calcular_areas_melhorado`],
+//!   [`angulo_critico_abertura`], [`angulo_critico_religamento`])
+//! - Estabilidade da trajetória: [`estabilidade`] ([`Estabilidade`], [`Veredito`])
+//! - Tempo crítico de abertura: [`cct`] ([`critical_clearing_time`], [`cct_multimaquina`])
+//! - Método direto (função de energia transitória): [`energia`] ([`margem_energia`],
+//!   [`estimar_cct`])
+//! - Pequenos sinais no equilíbrio pós-falta: [`pequenos_sinais`] ([`analisar_pequenos_sinais`])
+//! - Sistemas multimáquinas (modelo clássico): [`multimaquina`] ([`MultiMachineSystem`],
+//!   [`sim_multimaquina`]) e importação de casos do MATPOWER e do PSS/E: [`matpower`]
+//!   ([`importar_matpower`]) e [`psse`] ([`importar_psse`])
+//! - Otimizador: [`pso_config`], [`objective_function`] e [`estudo`] ([`otimizar`])
+//! - Gráficos: [`plot`]
+//! - Execução de estudos individuais e em lote: [`estudo`] e [`batch`]
+//!
+//! ```no_run
+//! use time_runge_kutta::{Case, calcular_areas_melhorado, sim_pet_time};
+//!
+//! let case = Case::default();
+//! let sim = sim_pet_time(&case, 0.15, 0.35)?;
+//! let s = &case.sistema;
+//! // CRA e CRR ausentes se a simulação termina antes de tab ou tr
+//! if let (Some(cra), Some(crr)) = (sim.cra, sim.crr) {
+//!     let (a1, a2, a3) = calcular_areas_melhorado(s.pe1, s.pe2, s.pe3, s.pm, cra, crr);
+//!     println!("Diferença das áreas: {:.4}", ((a1 + a2) - a3).abs());
+//! }
+//! # Ok::<(), time_runge_kutta::Error>(())
+//! ```
+
+#![warn(missing_docs)]
+
+mod algebra;
+pub mod area;
+pub mod batch;
+mod blocos;
+pub mod case;
+pub mod cct;
+mod complexo;
+pub mod curva;
+pub mod dois_eixos;
+pub mod energia;
+pub mod error;
+pub mod estabilidade;
+pub mod estabilizador;
+pub mod estudo;
+pub mod eventos;
+pub mod excitacao;
+pub mod falta;
+mod fluxo_potencia;
+pub mod maquina;
+pub mod matpower;
+pub mod multimaquina;
+pub mod objective_function;
+pub mod ode;
+pub mod pequenos_sinais;
+pub mod plot;
+pub mod pso_config;
+pub mod psse;
+pub mod rede;
+pub mod rk45;
+pub mod runge_kutta;
+pub mod sim_per_time;
+pub mod subtransitorio;
+pub mod swing;
+pub mod turbina;
+pub mod um_eixo;
+pub mod values;
+
+pub use area::{angulo_critico_abertura, angulo_critico_religamento, calcular_areas_melhorado};
+pub use batch::{ResultadoLote, ResumoMultimaquina, executar_lote};
+pub use case::{
+    Case, CctSettings, ImportacaoMatpower, ImportacaoPsse, IntegratorSettings, MachineSettings,
+    MetodoIntegracao, ModeloMaquina, ObjetivoPso, PsoSettings, StabilitySettings, SystemParams,
+};
+pub use cct::{
+    ResultadoCct, ResultadoCctMultimaquina, cct_multimaquina, critical_clearing_time, executar_cct,
+    executar_cct_multimaquina,
+};
+pub use curva::PowerAngleCurve;
+pub use dois_eixos::TwoAxis;
+pub use energia::{EstimativaCct, FuncaoEnergia, MargemEnergia, estimar_cct, margem_energia};
+pub use error::{Error, Result};
+pub use estabilidade::{
+    Amortecimento, CriterioInstabilidade, Estabilidade, Veredito, amortecimento,
+};
+pub use estabilizador::{
+    EntradaEstabilizador, Pss1a, Pss2a, SinaisEstabilizador, StabilizerModel, StabilizerSettings,
+};
+pub use estudo::{ResultadoPso, ResumoEstudo, executar_estudo, otimizar};
+pub use eventos::{Chaveamento, Evento, Rede};
+pub use excitacao::{Dc1a, ExciterModel, ExciterSettings, SimpleExciter, St1a};
+pub use falta::{TipoAbertura, TipoFalta};
+pub use maquina::{ControlledMachine, MachineModel, RedeGerador, modelo_maquina};
+pub use matpower::{DadosDinamicos, DinamicaGerador, importar_matpower};
+pub use multimaquina::{
+    AcaoRede, EventoRede, MultiMachineSystem, ResultadoMultimaquina, executar_multimaquina,
+    sim_multimaquina,
+};
+pub use ode::{AdaptiveIntegrator, Integrator, OdeSystem, Rk4};
+pub use pequenos_sinais::{AnalisePequenosSinais, Modo, analisar_pequenos_sinais};
+pub use psse::importar_psse;
+pub use rede::{RedeReduzida, ResultadoRede, SmibNetwork};
+pub use rk45::Rk45;
+pub use runge_kutta::runge_kutta_with_d;
+pub use sim_per_time::{ResultadoSimulacao, sim_pet_time};
+pub use subtransitorio::SixthOrder;
+pub use swing::SwingEquation;
+pub use turbina::{
+    FastValving, GovernorModel, GovernorSettings, HydroTurbine, Ieeeg1, SteamReheat, Tgov1,
+};
+pub use um_eixo::FluxDecay;
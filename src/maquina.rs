@@ -0,0 +1,570 @@
+//! Modelos do gerador contra barra infinita atrás de uma interface comum.
+//!
+//! O simulador ([`crate::sim_per_time`]) trata todos os modelos por
+//! [`MachineModel`]: o vetor de estado começa sempre por `[δ, Δω]` (ver
+//! [`crate::swing::DELTA`] e [`crate::swing::DELTA_W`]), de modo que o veredito
+//! de estabilidade e os gráficos não dependem do modelo escolhido. Os estados
+//! elétricos vêm em seguida, a partir de [`crate::um_eixo::E_Q_TRANSITORIA`].
+//!
+//! Os modelos com dinâmica de fluxo veem a rede SMIB reduzida ao nó atrás da
+//! própria reatância (`x'd` ou `x''d`) e à barra infinita ([`RedeGerador`]).
+//! Nos eixos do rotor, com o eixo d em `δ - 90°`, a FEM `(Ed, Eq)` atrás da
+//! reatância `x` da rede e a saliência `s = xq - x` dão as correntes
+//!
+//! `Id = b_propria·Eq + b_transferencia·V·cos(δ)`,
+//! `Iq = -(b_propria·Ed + b_transferencia·V·sin(δ)) / (1 + b_propria·s)`
+//!
+//! e a potência elétrica `Pe = (Ed + s·Iq)·Id + Eq·Iq`.
+//!
+//! Com `[maquina.excitatriz]` ou `[maquina.regulador]`, [`ControlledMachine`]
+//! acopla ao modelo um sistema de excitação ([`crate::excitacao`]) que
+//! fornece a tensão de campo, com o sinal de um estabilizador
+//! ([`crate::estabilizador`]), e um regulador de velocidade
+//! ([`crate::turbina`]) que fornece a potência mecânica.
+
+use std::f64::consts::{FRAC_PI_2, PI};
+use std::fmt;
+
+use crate::case::{Case, MachineSettings, ModeloMaquina, SystemParams};
+use crate::complexo::Complexo;
+use crate::curva::PowerAngleCurve;
+use crate::dois_eixos::TwoAxis;
+use crate::error::{Error, Result};
+use crate::estabilizador::{SinaisEstabilizador, StabilizerModel};
+use crate::eventos::{Chaveamento, Configuracao};
+use crate::excitacao::ExciterModel;
+use crate::ode::OdeSystem;
+use crate::rede::{RedeReduzida, ResultadoRede, SmibNetwork};
+use crate::subtransitorio::SixthOrder;
+use crate::swing::{DELTA_W, SwingEquation};
+use crate::turbina::{FastValving, GovernorModel};
+use crate::um_eixo::FluxDecay;
+
+/// Modelo dinâmico do gerador simulado por [`crate::sim_pet_time`].
+pub trait MachineModel: OdeSystem + fmt::Debug {
+    /// Estado inicial no equilíbrio pré-falta, começando por `[δ0, Δω0]`.
+    fn estado_inicial(&self, sistema: &SystemParams) -> Vec<f64>;
+
+    /// Potência mecânica (p.u.) no instante `t` e no estado `x`.
+    fn pm(&self, t: f64, x: &[f64]) -> f64;
+
+    /// Curva potência-ângulo da rede em vigor com os estados elétricos de `x`
+    /// congelados, usada pelo critério do equilíbrio instável.
+    fn curva(&self, x: &[f64]) -> PowerAngleCurve;
+
+    /// Aplica a nova rede e o degrau de potência mecânica de `chaveamento`.
+    fn chavear(&mut self, chaveamento: &Chaveamento);
+
+    /// Módulo da tensão terminal (p.u.) no estado `x`; `None` no modelo
+    /// clássico, que não representa o terminal.
+    fn tensao_terminal(&self, x: &[f64]) -> Option<f64>;
+
+    /// Tensão de campo `Efd` (p.u.) no estado `x`; `None` nos modelos sem
+    /// enrolamento de campo.
+    fn tensao_campo(&self, x: &[f64]) -> Option<f64>;
+
+    /// Potência elétrica `Pe` (p.u.) fornecida à rede em vigor no estado `x`.
+    fn potencia_eletrica(&self, x: &[f64]) -> f64;
+
+    /// Derivadas do estado `x` com a tensão de campo `efd` e a potência
+    /// mecânica `pm` no lugar das constantes do modelo (`efd` é ignorada nos
+    /// modelos sem enrolamento de campo).
+    fn derivada_entradas(&self, x: &[f64], efd: f64, pm: f64, dxdt: &mut [f64]);
+
+    /// Nomes dos estados para os relatórios, na ordem do vetor de estado.
+    fn nomes_estados(&self) -> Vec<String> {
+        NOMES_ESTADOS
+            .iter()
+            .take(self.dimensao())
+            .map(|nome| nome.to_string())
+            .collect()
+    }
+}
+
+// Estados dos modelos do gerador, na ordem comum a todos eles
+const NOMES_ESTADOS: [&str; 6] = ["δ", "Δω", "E'q", "E'd", "E''q", "E''d"];
+
+/// Modelo do gerador de `[maquina]` no equilíbrio pré-falta do caso, com o
+/// sistema de excitação de `[maquina.excitatriz]`, o estabilizador de
+/// `[maquina.estabilizador]`, o regulador de velocidade de
+/// `[maquina.regulador]` e a válvula rápida de `[maquina.valvula_rapida]`, se
+/// houver.
+pub fn modelo_maquina(case: &Case) -> Result<Box<dyn MachineModel>> {
+    let sistema = &case.sistema;
+    let configuracao = &case.maquina;
+    let maquina: Box<dyn MachineModel> = match configuracao.modelo {
+        ModeloMaquina::Classico => Box::new(SwingEquation::new(sistema, sistema.pe1)),
+        ModeloMaquina::UmEixo => Box::new(FluxDecay::new(case)?),
+        ModeloMaquina::DoisEixos => Box::new(TwoAxis::new(case)?),
+        ModeloMaquina::SextaOrdem => Box::new(SixthOrder::new(case)?),
+    };
+    if configuracao.excitatriz.is_none()
+        && configuracao.estabilizador.is_none()
+        && configuracao.regulador.is_none()
+        && configuracao.valvula_rapida.is_none()
+    {
+        return Ok(maquina);
+    }
+    Ok(Box::new(ControlledMachine::new(
+        maquina,
+        configuracao,
+        sistema,
+    )?))
+}
+
+/// Modelo do gerador com a tensão de campo fornecida por um sistema de
+/// excitação, com ou sem estabilizador, e a potência mecânica por um
+/// regulador de velocidade. O estado é o do gerador seguido dos da
+/// excitatriz, do estabilizador e do regulador.
+#[derive(Debug)]
+pub struct ControlledMachine {
+    /// Modelo do gerador.
+    pub maquina: Box<dyn MachineModel>,
+    /// Sistema de excitação, inicializado no equilíbrio pré-falta; se
+    /// ausente, a tensão de campo fica constante.
+    pub excitatriz: Option<Box<dyn ExciterModel>>,
+    /// Estabilizador, que soma o sinal `vs` à referência da excitatriz.
+    pub estabilizador: Option<Box<dyn StabilizerModel>>,
+    /// Regulador de velocidade, inicializado no equilíbrio pré-falta; se
+    /// ausente, a potência mecânica é a do gerador.
+    pub regulador: Option<Box<dyn GovernorModel>>,
+    /// Válvula rápida das turbinas a vapor.
+    pub valvula_rapida: Option<FastValving>,
+    /// Velocidade síncrona (rad/s), base do desvio de velocidade do
+    /// regulador.
+    pub omega_s: f64,
+    /// Estado inicial do gerador.
+    pub estado_maquina: Vec<f64>,
+    /// Estado inicial da excitatriz.
+    pub estado_excitatriz: Vec<f64>,
+    /// Estado inicial do estabilizador.
+    pub estado_estabilizador: Vec<f64>,
+    /// Estado inicial do regulador.
+    pub estado_regulador: Vec<f64>,
+}
+
+impl ControlledMachine {
+    /// Acopla a `maquina` a excitatriz, o estabilizador, o regulador e a
+    /// válvula rápida de `configuracao`, ajustando as referências de tensão e
+    /// de carga ao equilíbrio pré-falta de `sistema`. Falha se a excitatriz
+    /// não encontra enrolamento de campo no modelo, se há estabilizador sem
+    /// excitatriz ou se as condições iniciais violam os limites da excitatriz
+    /// ou do regulador.
+    pub fn new(
+        maquina: Box<dyn MachineModel>,
+        configuracao: &MachineSettings,
+        sistema: &SystemParams,
+    ) -> Result<Self> {
+        let mut excitatriz = configuracao.excitatriz.as_ref().map(|e| e.modelo());
+        let estabilizador = configuracao.estabilizador.as_ref().map(|e| e.modelo());
+        let mut regulador = configuracao.regulador.as_ref().map(|r| r.modelo());
+        let omega_s = 2.0 * PI * sistema.f;
+        let estado_maquina = maquina.estado_inicial(sistema);
+        let estado_excitatriz = match &mut excitatriz {
+            Some(excitatriz) => {
+                let (Some(efd), Some(vt)) = (
+                    maquina.tensao_campo(&estado_maquina),
+                    maquina.tensao_terminal(&estado_maquina),
+                ) else {
+                    return Err(Error::Configuracao(
+                        "a excitatriz requer um modelo do gerador com enrolamento de campo"
+                            .to_string(),
+                    ));
+                };
+                excitatriz.inicializar(efd, vt)?
+            }
+            None => Vec::new(),
+        };
+        let estado_estabilizador = match (&estabilizador, &excitatriz) {
+            (Some(estabilizador), Some(_)) => estabilizador.inicializar(SinaisEstabilizador {
+                velocidade: estado_maquina[DELTA_W] / omega_s,
+                potencia_eletrica: maquina.potencia_eletrica(&estado_maquina),
+            }),
+            (Some(_), None) => {
+                return Err(Error::Configuracao(
+                    "o estabilizador requer uma excitatriz".to_string(),
+                ));
+            }
+            (None, _) => Vec::new(),
+        };
+        let estado_regulador = match &mut regulador {
+            Some(regulador) => regulador.inicializar(maquina.pm(0.0, &estado_maquina))?,
+            None => Vec::new(),
+        };
+        Ok(ControlledMachine {
+            maquina,
+            excitatriz,
+            estabilizador,
+            regulador,
+            valvula_rapida: configuracao.valvula_rapida,
+            omega_s,
+            estado_maquina,
+            estado_excitatriz,
+            estado_estabilizador,
+            estado_regulador,
+        })
+    }
+
+    // Tamanhos dos estados do gerador, da excitatriz, do estabilizador e do
+    // regulador
+    fn dimensoes(&self) -> [usize; 4] {
+        [
+            self.maquina.dimensao(),
+            self.estado_excitatriz.len(),
+            self.estado_estabilizador.len(),
+            self.estado_regulador.len(),
+        ]
+    }
+
+    // Estados do gerador, da excitatriz, do estabilizador e do regulador em `x`
+    fn dividir<'a>(&self, x: &'a [f64]) -> [&'a [f64]; 4] {
+        let [maquina, excitatriz, estabilizador, _] = self.dimensoes();
+        let (x_maquina, resto) = x.split_at(maquina);
+        let (x_excitatriz, resto) = resto.split_at(excitatriz);
+        let (x_estabilizador, x_regulador) = resto.split_at(estabilizador);
+        [x_maquina, x_excitatriz, x_estabilizador, x_regulador]
+    }
+
+    // Tensão terminal e tensão de campo no estado `x`
+    fn sinais(&self, x: &[f64]) -> (f64, f64) {
+        let [x_maquina, x_excitatriz, ..] = self.dividir(x);
+        let vt = self.maquina.tensao_terminal(x_maquina).unwrap_or(0.0);
+        let efd = match &self.excitatriz {
+            Some(excitatriz) => excitatriz.efd(x_excitatriz, vt),
+            None => self.maquina.tensao_campo(x_maquina).unwrap_or(0.0),
+        };
+        (vt, efd)
+    }
+
+    // Desvio de velocidade (p.u.) no estado `x`
+    fn velocidade(&self, x: &[f64]) -> f64 {
+        x[DELTA_W] / self.omega_s
+    }
+
+    // Sinais medidos pelo estabilizador no estado `x`
+    fn sinais_estabilizador(&self, x: &[f64]) -> SinaisEstabilizador {
+        SinaisEstabilizador {
+            velocidade: self.velocidade(x),
+            potencia_eletrica: self.potencia_eletrica(x),
+        }
+    }
+
+    // Abertura das válvulas de interceptação no instante `t`
+    fn abertura(&self, t: f64) -> f64 {
+        self.valvula_rapida
+            .map_or(1.0, |valvula| valvula.abertura(t))
+    }
+}
+
+impl OdeSystem for ControlledMachine {
+    fn dimensao(&self) -> usize {
+        self.dimensoes().iter().sum()
+    }
+
+    fn derivada(&self, t: f64, x: &[f64], dxdt: &mut [f64]) {
+        let [x_maquina, x_excitatriz, x_estabilizador, x_regulador] = self.dividir(x);
+        let (d_maquina, resto) = dxdt.split_at_mut(x_maquina.len());
+        let (d_excitatriz, resto) = resto.split_at_mut(x_excitatriz.len());
+        let (d_estabilizador, d_regulador) = resto.split_at_mut(x_estabilizador.len());
+        let (vt, efd) = self.sinais(x);
+        let pm = self.pm(t, x);
+        self.maquina
+            .derivada_entradas(x_maquina, efd, pm, d_maquina);
+        let mut vs = 0.0;
+        if let Some(estabilizador) = &self.estabilizador {
+            let sinais = self.sinais_estabilizador(x);
+            vs = estabilizador.vs(x_estabilizador, sinais);
+            estabilizador.derivada(x_estabilizador, sinais, d_estabilizador);
+        }
+        if let Some(excitatriz) = &self.excitatriz {
+            excitatriz.derivada(x_excitatriz, vt, vs, d_excitatriz);
+        }
+        if let Some(regulador) = &self.regulador {
+            regulador.derivada(x_regulador, self.velocidade(x), d_regulador);
+        }
+    }
+}
+
+impl MachineModel for ControlledMachine {
+    fn estado_inicial(&self, _sistema: &SystemParams) -> Vec<f64> {
+        [
+            self.estado_maquina.as_slice(),
+            &self.estado_excitatriz,
+            &self.estado_estabilizador,
+            &self.estado_regulador,
+        ]
+        .concat()
+    }
+
+    // Sem regulador, a válvula rápida reduz toda a potência do gerador
+    fn pm(&self, t: f64, x: &[f64]) -> f64 {
+        let [x_maquina, .., x_regulador] = self.dividir(x);
+        match &self.regulador {
+            Some(regulador) => regulador.pm(x_regulador, self.velocidade(x), self.abertura(t)),
+            None => self.maquina.pm(t, x_maquina) * self.abertura(t),
+        }
+    }
+
+    fn curva(&self, x: &[f64]) -> PowerAngleCurve {
+        self.maquina.curva(self.dividir(x)[0])
+    }
+
+    // O degrau de potência mecânica altera a referência de carga do regulador
+    fn chavear(&mut self, chaveamento: &Chaveamento) {
+        self.maquina.chavear(chaveamento);
+        if let (Some(regulador), Some(pm)) = (&mut self.regulador, chaveamento.pm) {
+            regulador.definir_referencia(pm);
+        }
+    }
+
+    fn tensao_terminal(&self, x: &[f64]) -> Option<f64> {
+        self.maquina.tensao_terminal(self.dividir(x)[0])
+    }
+
+    fn potencia_eletrica(&self, x: &[f64]) -> f64 {
+        self.maquina.potencia_eletrica(self.dividir(x)[0])
+    }
+
+    fn tensao_campo(&self, x: &[f64]) -> Option<f64> {
+        self.excitatriz
+            .as_ref()
+            .map(|_| self.sinais(x).1)
+            .or_else(|| self.maquina.tensao_campo(self.dividir(x)[0]))
+    }
+
+    // As entradas vêm da excitatriz e do regulador
+    fn derivada_entradas(&self, x: &[f64], _efd: f64, _pm: f64, dxdt: &mut [f64]) {
+        self.derivada(0.0, x, dxdt);
+    }
+
+    // Estados dos controladores numerados a partir de 1
+    fn nomes_estados(&self) -> Vec<String> {
+        let [_, excitatriz, estabilizador, regulador] = self.dimensoes();
+        let controladores = [
+            ("excitatriz", excitatriz),
+            ("estabilizador", estabilizador),
+            ("regulador", regulador),
+        ];
+        let mut nomes = self.maquina.nomes_estados();
+        for (controlador, dimensao) in controladores {
+            nomes.extend((1..=dimensao).map(|k| format!("{} {}", controlador, k)));
+        }
+        nomes
+    }
+}
+
+/// Rede SMIB reduzida ao nó atrás da reatância do gerador no modelo e à
+/// barra infinita, com a configuração em vigor.
+#[derive(Debug, Clone)]
+pub struct RedeGerador {
+    /// Rede em vigor.
+    pub em_vigor: RedeReduzida,
+    /// Redes pré-falta, em falta e após a abertura (curvas `pe1`, `pe2` e
+    /// `pe3` do sistema).
+    pub configuracoes: [RedeReduzida; 3],
+    /// Tensão da barra infinita (p.u.).
+    pub v: f64,
+    /// FEM pré-falta `E'` atrás de `x'd` (p.u.), usada para converter curvas
+    /// explícitas.
+    pub e_inicial: f64,
+    /// Reatância do gerador na rede (p.u.).
+    pub x_rede: f64,
+    /// Diferença entre `x'd` e a reatância do gerador na rede (p.u.).
+    pub xd_transitoria_menos_x_rede: f64,
+}
+
+impl RedeGerador {
+    /// Rede de `smib` reduzida ao nó atrás de `x_rede`, a partir do
+    /// carregamento pré-falta já calculado em `resultado`.
+    pub fn new(smib: &SmibNetwork, resultado: &ResultadoRede, x_rede: f64) -> Result<Self> {
+        let configuracoes = smib.reduzidas(x_rede)?;
+        Ok(RedeGerador {
+            em_vigor: configuracoes[0],
+            configuracoes,
+            v: smib.v_barra_infinita,
+            e_inicial: resultado.e_interna,
+            x_rede,
+            xd_transitoria_menos_x_rede: smib.xd_transitoria - x_rede,
+        })
+    }
+
+    /// Troca a rede em vigor pela configuração do sistema ou, para uma curva
+    /// explícita, por uma ligação série com a mesma potência máxima na FEM
+    /// pré-falta (`Pmax = E'·V/X`, com `X` contado a partir de `x'd`).
+    pub fn chavear(&mut self, configuracao: Option<Configuracao>, pe: &PowerAngleCurve) {
+        self.em_vigor = match configuracao {
+            Some(Configuracao::Pe1) => self.configuracoes[0],
+            Some(Configuracao::Pe2) => self.configuracoes[1],
+            Some(Configuracao::Pe3) => self.configuracoes[2],
+            None => {
+                let x = self.e_inicial * self.v / pe.pmax - self.xd_transitoria_menos_x_rede;
+                RedeReduzida {
+                    b_propria: 1.0 / x,
+                    b_transferencia: -1.0 / x,
+                }
+            }
+        };
+    }
+
+    /// Correntes `(Id, Iq)` (p.u.) da FEM `(e_d, e_q)` atrás da reatância da
+    /// rede, com saliência `saliencia = xq - x` no eixo em quadratura.
+    pub fn correntes(&self, delta: f64, e_d: f64, e_q: f64, saliencia: f64) -> (f64, f64) {
+        let RedeReduzida {
+            b_propria,
+            b_transferencia,
+        } = self.em_vigor;
+        let (sen, cos) = delta.sin_cos();
+        let id = b_propria * e_q + b_transferencia * self.v * cos;
+        let iq =
+            -(b_propria * e_d + b_transferencia * self.v * sen) / (1.0 + b_propria * saliencia);
+        (id, iq)
+    }
+
+    /// Módulo da tensão terminal (p.u.) com a FEM `(e_d, e_q)` e a saliência
+    /// `saliencia`: `Vt = E - j·x·I`, com `E = (e_d + saliencia·Iq, e_q)`.
+    pub fn tensao_terminal(&self, delta: f64, e_d: f64, e_q: f64, saliencia: f64) -> f64 {
+        let (id, iq) = self.correntes(delta, e_d, e_q, saliencia);
+        let vd = e_d + (saliencia + self.x_rede) * iq;
+        let vq = e_q - self.x_rede * id;
+        vd.hypot(vq)
+    }
+
+    /// Curva potência-ângulo da rede em vigor com a FEM `(e_d, e_q)` fixa,
+    /// desprezando a saliência: `Pe = -b_transferencia·|E|·V·sin(δ - γ)`, com
+    /// `γ = atan2(e_d, e_q)`.
+    pub fn curva(&self, e_d: f64, e_q: f64) -> PowerAngleCurve {
+        PowerAngleCurve {
+            pc: 0.0,
+            pmax: -self.em_vigor.b_transferencia * e_d.hypot(e_q) * self.v,
+            gamma: e_d.atan2(e_q),
+        }
+    }
+}
+
+/// Potência elétrica (p.u.) da FEM `(e_d, e_q)` com as correntes `(id, iq)`
+/// e a saliência `saliencia` de [`RedeGerador::correntes`].
+pub fn potencia_eletrica(e_d: f64, e_q: f64, saliencia: f64, id: f64, iq: f64) -> f64 {
+    (e_d + saliencia * iq) * id + e_q * iq
+}
+
+// Ponto de operação pré-falta nos eixos do rotor: ângulo δ do eixo q, atrás de
+// `xq`, e correntes `(Id, Iq)` e tensões terminais `(Vd, Vq)` (p.u.)
+pub(crate) struct PontoOperacao {
+    pub(crate) delta: f64,
+    pub(crate) id: f64,
+    pub(crate) iq: f64,
+    pub(crate) vd: f64,
+    pub(crate) vq: f64,
+}
+
+// Ponto de operação de `smib` para a reatância síncrona de eixo em quadratura
+// `xq`, a partir de `E'` e δ0 do carregamento pré-falta em `resultado`
+pub(crate) fn ponto_operacao(
+    smib: &SmibNetwork,
+    resultado: &ResultadoRede,
+    xq: f64,
+) -> PontoOperacao {
+    let RedeReduzida {
+        b_propria,
+        b_transferencia,
+    } = resultado.reduzida_pre_falta;
+    let v = smib.v_barra_infinita;
+    let e = Complexo::polar(resultado.e_interna, resultado.delta_0);
+    // I = -j·(b_propria·E' + b_transferencia·V); Vt = E' - j·x'd·I
+    let corrente =
+        Complexo::new(0.0, -1.0) * (e * b_propria + Complexo::new(b_transferencia * v, 0.0));
+    let tensao = e - Complexo::new(0.0, smib.xd_transitoria) * corrente;
+    let delta = (tensao + Complexo::new(0.0, xq) * corrente).arg();
+    // Rotação para os eixos do rotor: multiplicação por e^(-j(δ - 90°))
+    let rotacao = Complexo::polar(1.0, FRAC_PI_2 - delta);
+    let (i_dq, v_dq) = (corrente * rotacao, tensao * rotacao);
+    PontoOperacao {
+        delta,
+        id: i_dq.re,
+        iq: i_dq.im,
+        vd: v_dq.re,
+        vq: v_dq.im,
+    }
+}
+
+// Rede SMIB do caso, exigida pelos modelos com dinâmica de fluxo
+pub(crate) fn rede_smib(case: &Case) -> Result<&SmibNetwork> {
+    case.rede.as_ref().ok_or_else(|| {
+        Error::Configuracao(format!(
+            "o modelo {} requer a seção [rede]",
+            case.maquina.modelo
+        ))
+    })
+}
+
+// Parâmetro obrigatório de `[maquina]`
+pub(crate) fn parametro(case: &Case, valor: Option<f64>, nome: &str) -> Result<f64> {
+    valor.ok_or_else(|| {
+        Error::Configuracao(format!(
+            "o modelo {} requer {} em [maquina]",
+            case.maquina.modelo, nome
+        ))
+    })
+}
+
+// Verifica que o modelo parte do equilíbrio pré-falta do caso: derivadas nulas
+// no estado inicial e potência elétrica igual à mecânica
+#[cfg(test)]
+pub(crate) fn verificar_equilibrio(modelo: &dyn MachineModel, case: &Case) {
+    let x = modelo.estado_inicial(&case.sistema);
+    let mut dxdt = vec![f64::NAN; modelo.dimensao()];
+    modelo.derivada(0.0, &x, &mut dxdt);
+    for (nome, derivada) in modelo.nomes_estados().iter().zip(&dxdt) {
+        assert!(derivada.abs() < 1e-9, "d{}/dt = {}", nome, derivada);
+    }
+    assert!((modelo.potencia_eletrica(&x) - case.sistema.pm).abs() < 1e-9);
+    assert!((modelo.pm(0.0, &x) - case.sistema.pm).abs() < 1e-9);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::case::caso_com_rede;
+
+    #[test]
+    fn modelo_classico_parte_do_equilibrio() {
+        let case = caso_com_rede("");
+        verificar_equilibrio(modelo_maquina(&case).unwrap().as_ref(), &case);
+    }
+
+    #[test]
+    fn controles_partem_do_equilibrio() {
+        let case = caso_com_rede(
+            "[maquina]\n\
+             modelo = \"um_eixo\"\n\
+             xd = 1.8\n\
+             td0_transitoria = 6.0\n\
+             [maquina.excitatriz]\n\
+             modelo = \"st1a\"\n\
+             ka = 200.0\n\
+             ta = 0.02\n\
+             vrmax = 7.0\n\
+             vrmin = -6.0\n\
+             [maquina.estabilizador]\n\
+             modelo = \"pss2a\"\n\
+             tw1 = 10.0\n\
+             tw3 = 10.0\n\
+             t7 = 10.0\n\
+             ks2 = 0.55\n\
+             t8 = 0.5\n\
+             t9 = 0.1\n\
+             ks1 = 20.0\n\
+             vstmax = 0.1\n\
+             vstmin = -0.1\n\
+             [maquina.regulador]\n\
+             modelo = \"reaquecimento\"\n\
+             r = 0.05\n\
+             tg = 0.2\n\
+             pmax = 1.6\n\
+             tch = 0.3\n\
+             trh = 7.0\n\
+             fhp = 0.3\n",
+        );
+        verificar_equilibrio(modelo_maquina(&case).unwrap().as_ref(), &case);
+    }
+}
@@ -0,0 +1,448 @@
+//! Importação de casos do MATPOWER (`.m`) para estudos multimáquinas.
+//!
+//! O arquivo `.m` fornece a rede e o despacho (`mpc.baseMVA`, `mpc.bus`,
+//! `mpc.gen` e `mpc.branch`, no formato da versão 2), e um arquivo TOML
+//! auxiliar fornece os dados dinâmicos de cada gerador (`H`, `D` e `x'd` na
+//! base da máquina). O ponto de operação é obtido por um fluxo de potência
+//! (Newton-Raphson) a partir das tensões do arquivo, e o resultado é
+//! o [`MultiMachineSystem`] consumido por [`crate::multimaquina`].
+//!
+//! Campos sem efeito na simulação (`gencost`, `bus_name`, `areas`, `gentype` e
+//! `genfuel`) são ignorados; qualquer outro campo, como `dcline`, é rejeitado.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use crate::fluxo_potencia::{self, TipoBarra};
+use crate::multimaquina::{Barra, Gerador, MultiMachineSystem, Ramo};
+
+// Campos aceitos e ignorados
+const CAMPOS_IGNORADOS: [&str; 5] = ["gencost", "bus_name", "areas", "gentype", "genfuel"];
+
+// Número mínimo de colunas das matrizes (formato da versão 2)
+const COLUNAS_BUS: usize = 13;
+const COLUNAS_GEN: usize = 10;
+const COLUNAS_BRANCH: usize = 11;
+
+/// Dados dinâmicos dos geradores (arquivo TOML auxiliar com `[[geradores]]`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DadosDinamicos {
+    /// Um registro por gerador em serviço, na ordem de `mpc.gen`.
+    pub geradores: Vec<DinamicaGerador>,
+}
+
+/// Dados dinâmicos de um gerador, na base da máquina (`mBase` de `mpc.gen`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DinamicaGerador {
+    /// Barra terminal, que identifica o gerador em `mpc.gen`.
+    pub barra: usize,
+    /// Constante de inércia (s).
+    pub h: f64,
+    /// Coeficiente de amortecimento.
+    #[serde(default)]
+    pub d: f64,
+    /// Reatância transitória de eixo direto `x'd` (p.u.).
+    pub xd_transitoria: f64,
+}
+
+// Valor atribuído a um campo `mpc.<campo>`
+enum Valor {
+    Escalar(f64),
+    Texto(String),
+    Matriz(Vec<Vec<f64>>),
+}
+
+/// Importa um caso do MATPOWER e os dados dinâmicos dos geradores, resolve o
+/// fluxo de potência e retorna o sistema multimáquinas sem eventos, com as
+/// grandezas na base `baseMVA` do caso.
+///
+/// Geradores e ramos fora de serviço (`status = 0`) são descartados e
+/// mantidos fora de serviço, respectivamente; a ordem de `mpc.branch` é
+/// preservada, de modo que os índices dos ramos nos eventos são as linhas da
+/// matriz a partir de 0. Barras isoladas (tipo 4) não são suportadas.
+pub fn importar_matpower(caminho_m: &Path, caminho_dinamica: &Path) -> Result<MultiMachineSystem> {
+    let erro_caso = |caminho: &Path| {
+        let caminho = caminho.to_path_buf();
+        move |mensagem: String| Error::Caso { caminho, mensagem }
+    };
+    let conteudo = fs::read_to_string(caminho_m).map_err(Error::io(caminho_m))?;
+    let (mut sistema, tipos, razoes_base) = ler_caso(&conteudo).map_err(erro_caso(caminho_m))?;
+
+    let conteudo = fs::read_to_string(caminho_dinamica).map_err(Error::io(caminho_dinamica))?;
+    let dinamica: DadosDinamicos = toml::from_str(&conteudo)
+        .map_err(|e| e.to_string())
+        .map_err(erro_caso(caminho_dinamica))?;
+    aplicar_dinamica(&mut sistema, &razoes_base, &dinamica).map_err(erro_caso(caminho_dinamica))?;
+
+    fluxo_potencia::resolver(&mut sistema, &tipos)?;
+    Ok(sistema)
+}
+
+// Converte o conteúdo do arquivo `.m` na rede com o despacho (geradores ainda
+// sem dados dinâmicos), nos tipos das barras e na razão mBase/baseMVA de cada
+// gerador
+fn ler_caso(
+    conteudo: &str,
+) -> std::result::Result<(MultiMachineSystem, Vec<TipoBarra>, Vec<f64>), String> {
+    let mut campos = ler_campos(conteudo)?;
+    match campos.remove("version") {
+        Some(Valor::Texto(versao)) if versao == "2" => {}
+        Some(Valor::Texto(versao)) => {
+            return Err(format!(
+                "mpc.version = '{}' não suportada (apenas '2')",
+                versao
+            ));
+        }
+        Some(_) => return Err("mpc.version deve ser um texto".to_string()),
+        None => return Err("campo mpc.version ausente".to_string()),
+    }
+    let base = match campos.remove("baseMVA") {
+        Some(Valor::Escalar(base)) if base > 0.0 => base,
+        Some(_) => return Err("mpc.baseMVA deve ser um número positivo".to_string()),
+        None => return Err("campo mpc.baseMVA ausente".to_string()),
+    };
+    let mut matriz = |nome: &str, colunas: usize| -> std::result::Result<Vec<Vec<f64>>, String> {
+        let linhas = match campos.remove(nome) {
+            Some(Valor::Matriz(linhas)) => linhas,
+            Some(_) => return Err(format!("mpc.{} deve ser uma matriz", nome)),
+            None => return Err(format!("campo mpc.{} ausente", nome)),
+        };
+        if let Some(i) = linhas.iter().position(|linha| linha.len() < colunas) {
+            return Err(format!(
+                "linha {} de mpc.{} com {} coluna(s); são necessárias ao menos {}",
+                i + 1,
+                nome,
+                linhas[i].len(),
+                colunas
+            ));
+        }
+        Ok(linhas)
+    };
+    let dados_barras = matriz("bus", COLUNAS_BUS)?;
+    let dados_geradores = matriz("gen", COLUNAS_GEN)?;
+    let dados_ramos = matriz("branch", COLUNAS_BRANCH)?;
+    if let Some(nome) = campos
+        .keys()
+        .find(|nome| !CAMPOS_IGNORADOS.contains(&nome.as_str()))
+    {
+        return Err(format!("campo mpc.{} não suportado", nome));
+    }
+
+    let mut barras = Vec::with_capacity(dados_barras.len());
+    let mut tipos = Vec::with_capacity(dados_barras.len());
+    for linha in &dados_barras {
+        let id = numero_barra(linha[0], "mpc.bus")?;
+        tipos.push(match linha[1] {
+            1.0 => TipoBarra::Pq,
+            2.0 => TipoBarra::Pv,
+            3.0 => TipoBarra::Referencia,
+            4.0 => return Err(format!("barra {} isolada (tipo 4) não suportada", id)),
+            t => return Err(format!("barra {} com tipo {} inválido", id, t)),
+        });
+        barras.push(Barra {
+            id,
+            v: linha[7],
+            theta: linha[8],
+            pl: linha[2] / base,
+            ql: linha[3] / base,
+            gs: linha[4] / base,
+            bs: linha[5] / base,
+        });
+    }
+
+    let mut geradores = Vec::new();
+    let mut razoes_base = Vec::new();
+    for linha in dados_geradores.iter().filter(|linha| linha[7] > 0.0) {
+        let barra = numero_barra(linha[0], "mpc.gen")?;
+        let k = barras
+            .iter()
+            .position(|b| b.id == barra)
+            .ok_or_else(|| format!("gerador na barra {} inexistente", barra))?;
+        // Tensão especificada das barras PV e de referência
+        if tipos[k] != TipoBarra::Pq {
+            barras[k].v = linha[5];
+        }
+        geradores.push(Gerador {
+            barra,
+            p: linha[1] / base,
+            q: linha[2] / base,
+            h: 0.0,
+            d: 0.0,
+            xd_transitoria: 0.0,
+        });
+        razoes_base.push(if linha[6] > 0.0 { linha[6] / base } else { 1.0 });
+    }
+    for (k, barra) in barras.iter().enumerate() {
+        let tem_gerador = geradores.iter().any(|g| g.barra == barra.id);
+        match tipos[k] {
+            // Sem gerador em serviço, a barra PV passa a PQ, como no MATPOWER
+            TipoBarra::Pv if !tem_gerador => tipos[k] = TipoBarra::Pq,
+            TipoBarra::Referencia if !tem_gerador => {
+                return Err(format!(
+                    "barra de referência {} sem gerador em serviço",
+                    barra.id
+                ));
+            }
+            _ => {}
+        }
+    }
+    match tipos
+        .iter()
+        .filter(|&&t| t == TipoBarra::Referencia)
+        .count()
+    {
+        1 => {}
+        0 => return Err("caso sem barra de referência (tipo 3)".to_string()),
+        n => {
+            return Err(format!(
+                "{} barras de referência; apenas uma é suportada",
+                n
+            ));
+        }
+    }
+
+    let mut ramos = Vec::with_capacity(dados_ramos.len());
+    for linha in &dados_ramos {
+        ramos.push(Ramo {
+            de: numero_barra(linha[0], "mpc.branch")?,
+            para: numero_barra(linha[1], "mpc.branch")?,
+            r: linha[2],
+            x: linha[3],
+            b: linha[4],
+            tap: if linha[8] == 0.0 { 1.0 } else { linha[8] },
+            defasagem: linha[9],
+            em_servico: linha[10] > 0.0,
+        });
+    }
+
+    let sistema = MultiMachineSystem {
+        tab: 0.0,
+        tr: 0.0,
+        otimizar: false,
+        barras,
+        geradores,
+        ramos,
+        eventos: Vec::new(),
+    };
+    Ok((sistema, tipos, razoes_base))
+}
+
+// Associa os dados dinâmicos aos geradores na ordem de `mpc.gen`, convertendo
+// da base da máquina para a base do sistema
+fn aplicar_dinamica(
+    sistema: &mut MultiMachineSystem,
+    razoes_base: &[f64],
+    dinamica: &DadosDinamicos,
+) -> std::result::Result<(), String> {
+    let mut restantes: Vec<&DinamicaGerador> = dinamica.geradores.iter().collect();
+    for (gerador, &razao) in sistema.geradores.iter_mut().zip(razoes_base) {
+        let i = restantes
+            .iter()
+            .position(|d| d.barra == gerador.barra)
+            .ok_or_else(|| {
+                format!(
+                    "sem dados dinâmicos para o gerador da barra {}",
+                    gerador.barra
+                )
+            })?;
+        let dados = restantes.remove(i);
+        gerador.h = dados.h * razao;
+        gerador.d = dados.d * razao;
+        gerador.xd_transitoria = dados.xd_transitoria / razao;
+    }
+    if let Some(dados) = restantes.first() {
+        return Err(format!(
+            "dados dinâmicos para a barra {} sem gerador em serviço correspondente",
+            dados.barra
+        ));
+    }
+    Ok(())
+}
+
+// Número de barra lido de uma matriz (inteiro positivo)
+fn numero_barra(valor: f64, matriz: &str) -> std::result::Result<usize, String> {
+    if valor >= 1.0 && valor.fract() == 0.0 {
+        Ok(valor as usize)
+    } else {
+        Err(format!("número de barra inválido em {}: {}", matriz, valor))
+    }
+}
+
+// Lê as atribuições `mpc.<campo> = <valor>;` do arquivo, sem os comentários
+fn ler_campos(conteudo: &str) -> std::result::Result<HashMap<String, Valor>, String> {
+    let texto: String = conteudo
+        .lines()
+        .map(|linha| linha.split('%').next().unwrap_or(""))
+        .map(|linha| match linha.find("...") {
+            // Continuação de linha
+            Some(i) => linha[..i].to_string(),
+            None => format!("{}\n", linha),
+        })
+        .collect();
+
+    let mut campos = HashMap::new();
+    let mut resto = texto.as_str();
+    loop {
+        resto = resto.trim_start_matches(|c: char| c.is_whitespace() || c == ';');
+        if resto.is_empty() {
+            break;
+        }
+        if let Some(depois) = resto.strip_prefix("function") {
+            resto = depois.split_once('\n').map_or("", |(_, r)| r);
+            continue;
+        }
+        let Some(depois) = resto.strip_prefix("mpc.") else {
+            let instrucao = resto.lines().next().unwrap_or("").trim();
+            return Err(format!("instrução não suportada: '{}'", instrucao));
+        };
+        let (nome, depois) = depois
+            .split_once('=')
+            .ok_or_else(|| format!("atribuição sem '=' em mpc.{}", depois.trim()))?;
+        let nome = nome.trim();
+        if nome.is_empty() || !nome.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return Err(format!("campo mpc.{} não suportado", nome));
+        }
+        let depois = depois.trim_start();
+        let (valor, depois) = if let Some(corpo) = depois.strip_prefix('[') {
+            let (corpo, depois) = corpo
+                .split_once(']')
+                .ok_or_else(|| format!("matriz mpc.{} sem ']'", nome))?;
+            (Valor::Matriz(ler_matriz(nome, corpo)?), depois)
+        } else if let Some(corpo) = depois.strip_prefix('{') {
+            // Vetor de células (nomes de barras etc.): só é aceito se ignorado
+            let (_, depois) = corpo
+                .split_once('}')
+                .ok_or_else(|| format!("células mpc.{} sem '}}'", nome))?;
+            (Valor::Texto(String::new()), depois)
+        } else if let Some(corpo) = depois.strip_prefix('\'') {
+            let (texto, depois) = corpo
+                .split_once('\'')
+                .ok_or_else(|| format!("texto mpc.{} sem aspas finais", nome))?;
+            (Valor::Texto(texto.to_string()), depois)
+        } else {
+            let fim = depois.find([';', '\n']).unwrap_or(depois.len());
+            let valor = ler_numero(depois[..fim].trim()).ok_or_else(|| {
+                format!("valor inválido em mpc.{}: '{}'", nome, depois[..fim].trim())
+            })?;
+            (Valor::Escalar(valor), &depois[fim..])
+        };
+        campos.insert(nome.to_string(), valor);
+        resto = depois;
+    }
+    Ok(campos)
+}
+
+// Linhas da matriz separadas por `;` ou quebra de linha e elementos por
+// espaços ou vírgulas
+fn ler_matriz(nome: &str, corpo: &str) -> std::result::Result<Vec<Vec<f64>>, String> {
+    corpo
+        .split([';', '\n'])
+        .map(|linha| {
+            linha
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|e| !e.is_empty())
+                .map(|e| {
+                    ler_numero(e).ok_or_else(|| format!("valor inválido em mpc.{}: '{}'", nome, e))
+                })
+                .collect::<std::result::Result<Vec<f64>, String>>()
+        })
+        .filter(|linha| !matches!(linha, Ok(l) if l.is_empty()))
+        .collect()
+}
+
+// Número no formato do MATPOWER, incluindo `Inf` e `-Inf`
+fn ler_numero(texto: &str) -> Option<f64> {
+    match texto {
+        "Inf" | "inf" => Some(f64::INFINITY),
+        "-Inf" | "-inf" => Some(f64::NEG_INFINITY),
+        _ => texto.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // baseMVA = 100; o segundo gerador está fora de serviço, o que deixa a
+    // barra 2 sem gerador, e o transformador 2-3 tem tap 0,95
+    const CASO: &str = "function mpc = caso3
+% Caso de teste
+mpc.version = '2';
+mpc.baseMVA = 100;
+mpc.bus = [
+    1 3 0  0  0 0  1 1.02 0   230 1 1.1 0.9;
+    2 2 0  0  0 10 1 1    -2  230 1 1.1 0.9;
+    3 1 90 30 5 0  1 0.98 -5  230 1 1.1 0.9;
+];
+mpc.gen = [
+    1 95 10 300 -300 1.04 200 1 250 10;
+    2 50 0  100 -100 1.01 100 0 100 0;
+];
+mpc.branch = [
+    1 2 0.01 0.1 0.02 250 250 250 0    0 1 -360 360;
+    2 3 0    0.2 0    250 250 250 0.95 3 1 -360 360;
+    1 3 0.02 0.3 0.04 250 250 250 0    0 0 -360 360;
+];
+mpc.gencost = [2 0 0 3 0.1 20 0; 2 0 0 3 0.1 20 0];
+";
+
+    #[test]
+    fn caso_convertido_para_por_unidade() {
+        let (sistema, tipos, razoes_base) = ler_caso(CASO).unwrap();
+        // A barra 2 perde o único gerador em serviço e passa a PQ
+        assert_eq!(tipos, [TipoBarra::Referencia, TipoBarra::Pq, TipoBarra::Pq]);
+
+        let carga = &sistema.barras[2];
+        assert_eq!((carga.pl, carga.ql, carga.gs), (0.9, 0.3, 0.05));
+        assert_eq!(sistema.barras[1].bs, 0.1);
+        // Tensão especificada do gerador na barra de referência
+        assert_eq!(sistema.barras[0].v, 1.04);
+        assert_eq!(sistema.barras[2].theta, -5.0);
+
+        assert_eq!(sistema.geradores.len(), 1);
+        assert_eq!(
+            (sistema.geradores[0].p, sistema.geradores[0].q),
+            (0.95, 0.1)
+        );
+        assert_eq!(razoes_base, [2.0]);
+
+        let ramos = &sistema.ramos;
+        assert_eq!((ramos[0].tap, ramos[1].tap), (1.0, 0.95));
+        assert_eq!(ramos[1].defasagem, 3.0);
+        assert!(ramos[0].em_servico && !ramos[2].em_servico);
+    }
+
+    #[test]
+    fn dinamica_convertida_para_a_base_do_sistema() {
+        let (mut sistema, _, razoes_base) = ler_caso(CASO).unwrap();
+        let dinamica = DadosDinamicos {
+            geradores: vec![DinamicaGerador {
+                barra: 1,
+                h: 3.0,
+                d: 1.0,
+                xd_transitoria: 0.3,
+            }],
+        };
+        aplicar_dinamica(&mut sistema, &razoes_base, &dinamica).unwrap();
+        // mBase = 200 MVA: H e D dobram e x'd cai à metade
+        let gerador = &sistema.geradores[0];
+        assert_eq!(
+            (gerador.h, gerador.d, gerador.xd_transitoria),
+            (6.0, 2.0, 0.15)
+        );
+    }
+
+    #[test]
+    fn campos_nao_suportados_sao_rejeitados() {
+        let caso = format!("{}mpc.dcline = [1 2 1];\n", CASO);
+        let erro = ler_caso(&caso).unwrap_err();
+        assert!(erro.contains("mpc.dcline"), "{}", erro);
+        let caso = CASO.replace("mpc.version = '2'", "mpc.version = '1'");
+        assert!(ler_caso(&caso).is_err());
+    }
+}
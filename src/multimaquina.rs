@@ -0,0 +1,896 @@
+//! Sistema multimáquinas com o modelo clássico (FEM constante atrás de `x'd`).
+//!
+//! A rede é descrita por barras, ramos e geradores no ponto de operação de um
+//! fluxo de potência já resolvido (tensões das barras e despacho dos
+//! geradores). As cargas viram admitâncias constantes, cada gerador ganha um nó
+//! interno atrás de `x'd` e a matriz de admitância aumentada é reduzida por
+//! Kron aos nós internos a cada configuração da rede (pré-falta, falta, após a
+//! abertura etc.). As equações de oscilação de todas as máquinas são integradas
+//! com o mesmo esquema da simulação máquina x barra infinita
+//! ([`crate::sim_per_time`]), e os eventos de rede usam os mesmos instantes
+//! relativos a `tab` e `tr` de [`crate::eventos`].
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use crate::case::Case;
+use crate::complexo::Complexo;
+use crate::error::{Error, Result};
+use crate::estabilidade::{CriterioInstabilidade, Veredito};
+use crate::estudo::otimizar;
+use crate::eventos::{self, Referencia};
+use crate::ode::OdeSystem;
+use crate::plot::{abrir_imagem, plotar_angulos_multimaquina};
+use crate::sim_per_time::{Avanco, Registro};
+
+/// Sistema multimáquinas (seção `[multimaquina]` do arquivo de caso).
+///
+/// Grandezas em p.u. na base do sistema e ângulos em graus. A frequência é a
+/// de `[sistema]`, e a integração e os critérios de estabilidade são os de
+/// `[integrador]` e `[estabilidade]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MultiMachineSystem {
+    /// Instante de abertura (s) para eventos com `apos = "tab"`.
+    #[serde(default)]
+    pub tab: f64,
+    /// Instante de religamento (s) para eventos com `apos = "tr"`.
+    #[serde(default)]
+    pub tr: f64,
+    /// Busca `tab` e `tr` com o PSO, nos limites de `[pso]`, em vez de usar os
+    /// valores acima (ver [`crate::objective_function`]).
+    #[serde(default)]
+    pub otimizar: bool,
+    /// Barras com o ponto de operação.
+    pub barras: Vec<Barra>,
+    /// Geradores síncronos.
+    pub geradores: Vec<Gerador>,
+    /// Linhas e transformadores.
+    #[serde(default)]
+    pub ramos: Vec<Ramo>,
+    /// Eventos de rede (`[[multimaquina.eventos]]`), aplicados a partir da
+    /// rede pré-falta.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub eventos: Vec<EventoRede>,
+}
+
+/// Barra da rede com a tensão do ponto de operação.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Barra {
+    /// Número da barra.
+    pub id: usize,
+    /// Módulo da tensão (p.u.).
+    #[serde(default = "um")]
+    pub v: f64,
+    /// Ângulo da tensão (graus).
+    #[serde(default)]
+    pub theta: f64,
+    /// Carga ativa (p.u.), representada como impedância constante.
+    #[serde(default)]
+    pub pl: f64,
+    /// Carga reativa (p.u.), representada como impedância constante.
+    #[serde(default)]
+    pub ql: f64,
+    /// Condutância em derivação (p.u. em V = 1).
+    #[serde(default)]
+    pub gs: f64,
+    /// Susceptância em derivação (p.u. em V = 1).
+    #[serde(default)]
+    pub bs: f64,
+}
+
+/// Gerador representado pelo modelo clássico.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Gerador {
+    /// Barra terminal.
+    pub barra: usize,
+    /// Potência ativa gerada no ponto de operação (p.u.).
+    pub p: f64,
+    /// Potência reativa gerada no ponto de operação (p.u.).
+    #[serde(default)]
+    pub q: f64,
+    /// Constante de inércia na base do sistema (s).
+    pub h: f64,
+    /// Coeficiente de amortecimento.
+    #[serde(default)]
+    pub d: f64,
+    /// Reatância transitória de eixo direto `x'd` (p.u.).
+    pub xd_transitoria: f64,
+}
+
+/// Linha (modelo π) ou transformador entre duas barras.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Ramo {
+    /// Barra de origem (lado do tap).
+    pub de: usize,
+    /// Barra de destino.
+    pub para: usize,
+    /// Resistência série (p.u.).
+    #[serde(default)]
+    pub r: f64,
+    /// Reatância série (p.u.).
+    pub x: f64,
+    /// Susceptância total de carregamento (p.u.).
+    #[serde(default)]
+    pub b: f64,
+    /// Relação de transformação no lado `de` (p.u.).
+    #[serde(default = "um")]
+    pub tap: f64,
+    /// Defasagem do transformador (graus).
+    #[serde(default)]
+    pub defasagem: f64,
+    /// Ramo em serviço no ponto de operação.
+    #[serde(default = "verdadeiro")]
+    pub em_servico: bool,
+}
+
+/// Evento de rede do sistema multimáquinas.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventoRede {
+    /// Instante do evento (s); com `apos`, atraso em relação à referência.
+    #[serde(default)]
+    pub t: f64,
+    /// Referência do instante (`"tab"` ou `"tr"`); se ausente, `t` é absoluto.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apos: Option<Referencia>,
+    /// Alteração da rede.
+    #[serde(flatten)]
+    pub acao: AcaoRede,
+}
+
+/// Alteração da rede aplicada por um [`EventoRede`] (campo `acao`).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "acao", rename_all = "snake_case")]
+pub enum AcaoRede {
+    /// Falta trifásica na barra, franca ou através da reatância de falta.
+    Falta {
+        /// Barra em falta.
+        barra: usize,
+        /// Reatância de falta (p.u.).
+        #[serde(default)]
+        reatancia: f64,
+    },
+    /// Eliminação da falta na barra.
+    EliminarFalta {
+        /// Barra em falta.
+        barra: usize,
+    },
+    /// Abertura de um ramo (índice em `ramos`, a partir de 0).
+    AbrirRamo {
+        /// Índice do ramo.
+        ramo: usize,
+    },
+    /// Fechamento de um ramo (índice em `ramos`, a partir de 0).
+    FecharRamo {
+        /// Índice do ramo.
+        ramo: usize,
+    },
+}
+
+/// Ponto de operação de um gerador no modelo clássico.
+#[derive(Debug, Clone, Copy)]
+pub struct MaquinaClassica {
+    /// Módulo da FEM interna `E'` (p.u.).
+    pub e: f64,
+    /// Ângulo inicial do rotor (rad).
+    pub delta_0: f64,
+    /// Potência mecânica, igual à potência elétrica pré-falta (p.u.).
+    pub pm: f64,
+}
+
+/// Trajetória simulada do sistema multimáquinas.
+#[derive(Debug, Clone)]
+pub struct ResultadoMultimaquina {
+    /// Instante de abertura simulado (s).
+    pub tab: f64,
+    /// Instante de religamento simulado (s).
+    pub tr: f64,
+    /// Ponto de operação de cada gerador, na ordem de `geradores`.
+    pub maquinas: Vec<MaquinaClassica>,
+    /// Instantes de tempo (s).
+    pub tempos: Vec<f64>,
+    /// Ângulo do rotor (rad) de cada gerador em cada instante.
+    pub angulos: Vec<Vec<f64>>,
+    /// Desvio de velocidade (rad/s) de cada gerador em cada instante.
+    pub velocidades: Vec<Vec<f64>>,
+    /// Ângulo do centro de inércia (rad) em cada instante.
+    pub angulo_coi: Vec<f64>,
+    /// Veredito: instável quando a separação angular entre dois geradores
+    /// passa de `limite_angulo_graus`.
+    pub veredito: Veredito,
+    /// Maior separação angular entre dois geradores (rad).
+    pub separacao_max: f64,
+    /// Instante da maior separação angular (s).
+    pub t_separacao_max: f64,
+}
+
+impl fmt::Display for AcaoRede {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AcaoRede::Falta { barra, reatancia } if *reatancia > 0.0 => {
+                write!(f, "falta na barra {} (Xf = {} p.u.)", barra, reatancia)
+            }
+            AcaoRede::Falta { barra, .. } => write!(f, "falta na barra {}", barra),
+            AcaoRede::EliminarFalta { barra } => {
+                write!(f, "eliminação da falta na barra {}", barra)
+            }
+            AcaoRede::AbrirRamo { ramo } => write!(f, "abertura do ramo {}", ramo),
+            AcaoRede::FecharRamo { ramo } => write!(f, "fechamento do ramo {}", ramo),
+        }
+    }
+}
+
+fn um() -> f64 {
+    1.0
+}
+
+fn verdadeiro() -> bool {
+    true
+}
+
+impl MultiMachineSystem {
+    /// Ponto de operação dos geradores: FEM `E' = V + j·x'd·I` a partir da
+    /// tensão terminal e da potência gerada, e potência mecânica igual à
+    /// potência elétrica da rede pré-falta reduzida.
+    pub fn maquinas(&self) -> Result<Vec<MaquinaClassica>> {
+        let rede = RedeReduzivel::new(self)?;
+        let fem = rede.fem();
+        let y = rede.reduzir(&Topologia::inicial(self));
+        let e: Vec<f64> = fem.iter().map(|e| e.abs()).collect();
+        let delta: Vec<f64> = fem.iter().map(|e| e.arg()).collect();
+        Ok(e.iter()
+            .zip(&delta)
+            .zip(potencias_eletricas(&y, &e, &delta))
+            .map(|((&e, &delta_0), pm)| MaquinaClassica { e, delta_0, pm })
+            .collect())
+    }
+
+    /// Eventos resolvidos para `tab` e `tr`, em ordem cronológica; eventos
+    /// simultâneos mantêm a ordem declarada.
+    pub fn resolver_eventos(&self, tab: f64, tr: f64) -> Vec<(f64, AcaoRede)> {
+        let mut resolvidos: Vec<(f64, AcaoRede)> = self
+            .eventos
+            .iter()
+            .map(|evento| {
+                (
+                    eventos::instante(evento.t, evento.apos, tab, tr),
+                    evento.acao,
+                )
+            })
+            .collect();
+        resolvidos.sort_by(|a, b| a.0.total_cmp(&b.0));
+        resolvidos
+    }
+}
+
+/// Simula o sistema multimáquinas do caso a partir do ponto de operação,
+/// aplicando os eventos de rede resolvidos para `tab` e `tr`, até
+/// `integrador.t_max`.
+///
+/// A cada evento a rede é reduzida de novo aos nós internos dos geradores. A
+/// integração chega exatamente aos instantes dos eventos, como em
+/// [`crate::sim_per_time::sim_pet_time`]. Com `estabilidade.interromper`, a
+/// simulação para assim que a separação angular passa do limite.
+pub fn sim_multimaquina(case: &Case, tab: f64, tr: f64) -> Result<ResultadoMultimaquina> {
+    let sistema = case
+        .multimaquina
+        .as_ref()
+        .ok_or_else(|| Error::Configuracao("caso sem a seção [multimaquina]".to_string()))?;
+    let rede = RedeReduzivel::new(sistema)?;
+    let maquinas = sistema.maquinas()?;
+    let n = maquinas.len();
+    let t_max = case.integrador.t_max;
+
+    let mut topologia = Topologia::inicial(sistema);
+    let mut oscilacao = OscilacaoMultimaquina {
+        m: sistema
+            .geradores
+            .iter()
+            .map(|g| g.h / (PI * case.sistema.f))
+            .collect(),
+        d: sistema.geradores.iter().map(|g| g.d).collect(),
+        pm: maquinas.iter().map(|m| m.pm).collect(),
+        e: maquinas.iter().map(|m| m.e).collect(),
+        y: rede.reduzir(&topologia),
+    };
+
+    let mut x = vec![0.0; 2 * n];
+    for (xi, maquina) in x.iter_mut().zip(&maquinas) {
+        *xi = maquina.delta_0;
+    }
+    let mut t = 0.0;
+    let mut trajetoria = TrajetoriaMultimaquina::new(case, &oscilacao.m, t, &x);
+    let mut avanco = Avanco::new(&case.integrador);
+
+    let resolvidos = sistema.resolver_eventos(tab, tr);
+    let mut pendentes = resolvidos.iter().peekable();
+    loop {
+        let t_parada = pendentes
+            .peek()
+            .map_or(t_max, |(t_evento, _)| t_max.min(*t_evento));
+        avanco.integrar_ate(&oscilacao, &mut t, &mut x, t_parada, &mut trajetoria);
+
+        // Eventos simultâneos são aplicados na ordem declarada
+        let mut chaveou = false;
+        while let Some((_, acao)) = pendentes.next_if(|(t_evento, _)| *t_evento <= t) {
+            topologia.aplicar(acao, &rede)?;
+            chaveou = true;
+        }
+        if chaveou {
+            oscilacao.y = rede.reduzir(&topologia);
+        }
+
+        if t >= t_max || trajetoria.encerrar() {
+            break;
+        }
+    }
+
+    Ok(ResultadoMultimaquina {
+        tab,
+        tr,
+        maquinas,
+        tempos: trajetoria.tempos,
+        angulos: trajetoria.angulos,
+        velocidades: trajetoria.velocidades,
+        angulo_coi: trajetoria.angulo_coi,
+        veredito: trajetoria.veredito,
+        separacao_max: trajetoria.separacao_max,
+        t_separacao_max: trajetoria.t_separacao_max,
+    })
+}
+
+/// Simula o sistema multimáquinas do caso com os `tab` e `tr` da seção
+/// `[multimaquina]`, ou com os encontrados pelo PSO quando `otimizar` está
+/// ativo, gravando `resultados.txt`, o gráfico `angulos_multimaquina.png` e a
+/// cópia do caso em `pasta_saida`.
+pub fn executar_multimaquina(
+    case: &Case,
+    caminho_caso: Option<&Path>,
+    pasta_saida: &str,
+    should_open_images: bool,
+) -> Result<ResultadoMultimaquina> {
+    let sistema = case
+        .multimaquina
+        .as_ref()
+        .ok_or_else(|| Error::Configuracao("caso sem a seção [multimaquina]".to_string()))?;
+    fs::create_dir_all(pasta_saida).map_err(Error::io(pasta_saida))?;
+    let destino = case.copiar_para(caminho_caso, Path::new(pasta_saida))?;
+    println!("Caso copiado para: {}", destino.display());
+
+    let caminho_resultado = format!("{}/resultados.txt", pasta_saida);
+    let mut arquivo =
+        fs::File::create(&caminho_resultado).map_err(Error::io(&caminho_resultado))?;
+
+    let pso = if sistema.otimizar {
+        // Rede inválida é erro do caso, não uma partícula descartada
+        sistema.maquinas()?;
+        // Partículas instáveis recebem a penalidade assim que passam do limite
+        let mut case_pso = case.clone();
+        case_pso.estabilidade.interromper = true;
+        Some(otimizar(&case_pso)?)
+    } else {
+        None
+    };
+    let (tab, tr) = pso.map_or((sistema.tab, sistema.tr), |p| (p.tab, p.tr));
+    let resultado = sim_multimaquina(case, tab, tr)?;
+
+    let mut texto = format!(
+        "=== SISTEMA MULTIMÁQUINAS (MODELO CLÁSSICO) ===\n\
+         Barras: {}, ramos: {}, geradores: {}\n\
+         tab: {:.4}s, tr: {:.4}s, t_max: {:.4}s\n",
+        sistema.barras.len(),
+        sistema.ramos.len(),
+        sistema.geradores.len(),
+        tab,
+        tr,
+        case.integrador.t_max
+    );
+    if let Some(pso) = pso {
+        texto += &format!(
+            "tab e tr encontrados pelo PSO (função objetivo: {:.6e})\n",
+            pso.f_best
+        );
+    }
+    texto += "\nPonto de operação dos geradores:\n";
+    for (gerador, maquina) in sistema.geradores.iter().zip(&resultado.maquinas) {
+        texto += &format!(
+            "- Barra {}: E' = {:.5} p.u., delta_0 = {:.4}°, Pm = {:.5} p.u.\n",
+            gerador.barra,
+            maquina.e,
+            maquina.delta_0.to_degrees(),
+            maquina.pm
+        );
+    }
+    texto += "\nSequência de eventos:\n";
+    for (t, acao) in sistema.resolver_eventos(tab, tr) {
+        texto += &format!("- t: {:.4}s -> {}\n", t, acao);
+    }
+    texto += &format!(
+        "\nEstabilidade: {}\nSeparação angular máxima: {:.4}° -> t: {:.4}s\n",
+        resultado.veredito,
+        resultado.separacao_max.to_degrees(),
+        resultado.t_separacao_max
+    );
+
+    let caminho_grafico = format!("{}/angulos_multimaquina.png", pasta_saida);
+    let rotulos: Vec<String> = sistema
+        .geradores
+        .iter()
+        .map(|g| format!("Gerador da barra {}", g.barra))
+        .collect();
+    let relativos: Vec<Vec<f64>> = resultado
+        .angulos
+        .iter()
+        .map(|angulos| {
+            angulos
+                .iter()
+                .zip(&resultado.angulo_coi)
+                .map(|(delta, coi)| delta - coi)
+                .collect()
+        })
+        .collect();
+    match plotar_angulos_multimaquina(
+        &resultado.tempos,
+        &relativos,
+        &resultado.velocidades,
+        &rotulos,
+        &caminho_grafico,
+        case.integrador.t_max,
+    ) {
+        Ok(()) => {
+            texto += "Gráfico de ângulos e velocidades gerado com sucesso!\n";
+            if should_open_images {
+                abrir_imagem(&caminho_grafico);
+            }
+        }
+        Err(e) => texto += &format!("Erro ao plotar ângulos e velocidades: {}\n", e),
+    }
+
+    print!("{}", texto);
+    arquivo
+        .write_all(texto.as_bytes())
+        .map_err(Error::io(&caminho_resultado))?;
+    Ok(resultado)
+}
+
+// Matriz de admitância das barras da rede no ponto de operação, na ordem de
+// `barras` (usada pelo fluxo de potência)
+pub(crate) fn matriz_admitancia(sistema: &MultiMachineSystem) -> Result<Vec<Vec<Complexo>>> {
+    let rede = RedeReduzivel::new(sistema)?;
+    Ok(rede.admitancia(0, &Topologia::inicial(sistema).ramos_em_servico))
+}
+
+// Potência elétrica de cada máquina: Pe_i = Re(E_i · conj(Σ_j Y_ij·E_j))
+fn potencias_eletricas(y: &[Vec<Complexo>], e: &[f64], delta: &[f64]) -> Vec<f64> {
+    (0..e.len())
+        .map(|i| {
+            (0..e.len())
+                .map(|j| {
+                    let angulo = delta[i] - delta[j];
+                    e[i] * e[j] * (y[i][j].re * angulo.cos() + y[i][j].im * angulo.sin())
+                })
+                .sum()
+        })
+        .collect()
+}
+
+// Equações de oscilação das máquinas na rede reduzida aos nós internos.
+// Estado [δ_1..δ_n, Δω_1..Δω_n]
+struct OscilacaoMultimaquina {
+    m: Vec<f64>,
+    d: Vec<f64>,
+    pm: Vec<f64>,
+    e: Vec<f64>,
+    y: Vec<Vec<Complexo>>,
+}
+
+impl OdeSystem for OscilacaoMultimaquina {
+    fn dimensao(&self) -> usize {
+        2 * self.m.len()
+    }
+
+    fn derivada(&self, _t: f64, x: &[f64], dxdt: &mut [f64]) {
+        let n = self.m.len();
+        let (delta, delta_w) = x.split_at(n);
+        let pe = potencias_eletricas(&self.y, &self.e, delta);
+        for i in 0..n {
+            dxdt[i] = delta_w[i];
+            dxdt[n + i] = (self.pm[i] - pe[i] - self.d[i] * delta_w[i]) / self.m[i];
+        }
+    }
+}
+
+// Estado das faltas e dos ramos em uma configuração da rede
+#[derive(Debug, Clone)]
+struct Topologia {
+    // Reatância de falta por índice de barra
+    faltas: Vec<Option<f64>>,
+    ramos_em_servico: Vec<bool>,
+}
+
+impl Topologia {
+    fn inicial(sistema: &MultiMachineSystem) -> Self {
+        Topologia {
+            faltas: vec![None; sistema.barras.len()],
+            ramos_em_servico: sistema.ramos.iter().map(|r| r.em_servico).collect(),
+        }
+    }
+
+    fn aplicar(&mut self, acao: &AcaoRede, rede: &RedeReduzivel) -> Result<()> {
+        match *acao {
+            AcaoRede::Falta { barra, reatancia } => {
+                self.faltas[rede.indice(barra)?] = Some(reatancia)
+            }
+            AcaoRede::EliminarFalta { barra } => self.faltas[rede.indice(barra)?] = None,
+            AcaoRede::AbrirRamo { ramo } => *rede.ramo(&mut self.ramos_em_servico, ramo)? = false,
+            AcaoRede::FecharRamo { ramo } => *rede.ramo(&mut self.ramos_em_servico, ramo)? = true,
+        }
+        Ok(())
+    }
+}
+
+// Dados da rede validados, com as barras indexadas
+struct RedeReduzivel<'a> {
+    sistema: &'a MultiMachineSystem,
+    indices: HashMap<usize, usize>,
+}
+
+impl<'a> RedeReduzivel<'a> {
+    fn new(sistema: &'a MultiMachineSystem) -> Result<Self> {
+        let mut indices = HashMap::new();
+        for (i, barra) in sistema.barras.iter().enumerate() {
+            if indices.insert(barra.id, i).is_some() {
+                return Err(Error::Rede(format!("barra {} duplicada", barra.id)));
+            }
+        }
+        let rede = RedeReduzivel { sistema, indices };
+
+        if sistema.geradores.is_empty() {
+            return Err(Error::Rede(
+                "sistema multimáquinas sem geradores".to_string(),
+            ));
+        }
+        for gerador in &sistema.geradores {
+            rede.indice(gerador.barra)?;
+            if gerador.h <= 0.0 || gerador.xd_transitoria <= 0.0 {
+                return Err(Error::Rede(format!(
+                    "gerador da barra {}: h e xd_transitoria devem ser positivos",
+                    gerador.barra
+                )));
+            }
+        }
+        for (i, ramo) in sistema.ramos.iter().enumerate() {
+            rede.indice(ramo.de)?;
+            rede.indice(ramo.para)?;
+            if ramo.r == 0.0 && ramo.x == 0.0 {
+                return Err(Error::Rede(format!("ramo {} com impedância nula", i)));
+            }
+            if ramo.tap <= 0.0 {
+                return Err(Error::Rede(format!("ramo {} com tap não positivo", i)));
+            }
+        }
+        let mut topologia = Topologia::inicial(sistema);
+        for evento in &sistema.eventos {
+            topologia.aplicar(&evento.acao, &rede)?;
+        }
+        Ok(rede)
+    }
+
+    fn indice(&self, barra: usize) -> Result<usize> {
+        self.indices
+            .get(&barra)
+            .copied()
+            .ok_or_else(|| Error::Rede(format!("barra {} inexistente", barra)))
+    }
+
+    fn ramo<'b>(&self, em_servico: &'b mut [bool], ramo: usize) -> Result<&'b mut bool> {
+        let n_ramos = em_servico.len();
+        em_servico.get_mut(ramo).ok_or_else(|| {
+            Error::Rede(format!(
+                "ramo {} fora dos {} ramo(s) informado(s)",
+                ramo, n_ramos
+            ))
+        })
+    }
+
+    // FEM interna de cada gerador: E' = V + j·x'd·conj(S/V)
+    fn fem(&self) -> Vec<Complexo> {
+        self.sistema
+            .geradores
+            .iter()
+            .map(|g| {
+                let barra = &self.sistema.barras[self.indices[&g.barra]];
+                let v = Complexo::polar(barra.v, barra.theta.to_radians());
+                let corrente = (Complexo::new(g.p, g.q) / v).conj();
+                v + Complexo::new(0.0, g.xd_transitoria) * corrente
+            })
+            .collect()
+    }
+
+    // Matriz de admitância das barras (ramos e derivações), precedida de
+    // `n_internos` nós sem ligação
+    fn admitancia(&self, n_internos: usize, ramos_em_servico: &[bool]) -> Vec<Vec<Complexo>> {
+        let sistema = self.sistema;
+        let n = n_internos + sistema.barras.len();
+        let mut y = vec![vec![Complexo::ZERO; n]; n];
+        for (ramo, _) in sistema
+            .ramos
+            .iter()
+            .zip(ramos_em_servico)
+            .filter(|(_, em_servico)| **em_servico)
+        {
+            let (de, para) = (
+                n_internos + self.indices[&ramo.de],
+                n_internos + self.indices[&ramo.para],
+            );
+            let y_serie = Complexo::new(ramo.r, ramo.x).inv();
+            let carregamento = Complexo::new(0.0, ramo.b / 2.0);
+            let tap = Complexo::polar(ramo.tap, ramo.defasagem.to_radians());
+            y[de][de] += (y_serie + carregamento) * (1.0 / (ramo.tap * ramo.tap));
+            y[para][para] += y_serie + carregamento;
+            y[de][para] -= y_serie / tap.conj();
+            y[para][de] -= y_serie / tap;
+        }
+        for (i, barra) in sistema.barras.iter().enumerate() {
+            y[n_internos + i][n_internos + i] += Complexo::new(barra.gs, barra.bs);
+        }
+        y
+    }
+
+    // Matriz de admitância aumentada (nós internos dos geradores seguidos das
+    // barras) reduzida por Kron aos nós internos. Barras em falta franca ficam
+    // com tensão nula e saem da rede
+    fn reduzir(&self, topologia: &Topologia) -> Vec<Vec<Complexo>> {
+        let sistema = self.sistema;
+        let n_g = sistema.geradores.len();
+        let n = n_g + sistema.barras.len();
+        let mut y = self.admitancia(n_g, &topologia.ramos_em_servico);
+
+        for (i, gerador) in sistema.geradores.iter().enumerate() {
+            let y_gerador = Complexo::new(0.0, gerador.xd_transitoria).inv();
+            let k = n_g + self.indices[&gerador.barra];
+            y[i][i] += y_gerador;
+            y[k][k] += y_gerador;
+            y[i][k] -= y_gerador;
+            y[k][i] -= y_gerador;
+        }
+        let mut aterradas = vec![false; n];
+        for (i, barra) in sistema.barras.iter().enumerate() {
+            let k = n_g + i;
+            // Carga de impedância constante: y = (P - jQ) / V²
+            let v2 = barra.v * barra.v;
+            y[k][k] += Complexo::new(barra.pl / v2, -barra.ql / v2);
+            match topologia.faltas[i] {
+                Some(reatancia) if reatancia > 0.0 => {
+                    y[k][k] += Complexo::new(0.0, reatancia).inv();
+                }
+                Some(_) => aterradas[k] = true,
+                None => {}
+            }
+        }
+
+        for k in n_g..n {
+            if aterradas[k] || y[k][k] == Complexo::ZERO {
+                continue;
+            }
+            let pivo = y[k][k];
+            for i in (0..n).filter(|&i| i != k && !aterradas[i]) {
+                let fator = y[i][k] / pivo;
+                if fator == Complexo::ZERO {
+                    continue;
+                }
+                for j in (0..n).filter(|&j| j != k && !aterradas[j]) {
+                    let termo = fator * y[k][j];
+                    y[i][j] -= termo;
+                }
+            }
+            aterradas[k] = true;
+        }
+        y.truncate(n_g);
+        for linha in &mut y {
+            linha.truncate(n_g);
+        }
+        y
+    }
+}
+
+// Trajetória registrada, com a separação angular entre as máquinas
+struct TrajetoriaMultimaquina {
+    tempos: Vec<f64>,
+    angulos: Vec<Vec<f64>>,
+    velocidades: Vec<Vec<f64>>,
+    angulo_coi: Vec<f64>,
+    m: Vec<f64>,
+    limite_angulo: f64,
+    interromper: bool,
+    veredito: Veredito,
+    separacao_max: f64,
+    t_separacao_max: f64,
+}
+
+impl TrajetoriaMultimaquina {
+    fn new(case: &Case, m: &[f64], t: f64, x: &[f64]) -> Self {
+        let n = m.len();
+        let mut trajetoria = TrajetoriaMultimaquina {
+            tempos: Vec::new(),
+            angulos: vec![Vec::new(); n],
+            velocidades: vec![Vec::new(); n],
+            angulo_coi: Vec::new(),
+            m: m.to_vec(),
+            limite_angulo: case.estabilidade.limite_angulo_graus.to_radians(),
+            interromper: case.estabilidade.interromper,
+            veredito: Veredito::Estavel,
+            separacao_max: 0.0,
+            t_separacao_max: t,
+        };
+        trajetoria.registrar(t, x);
+        trajetoria
+    }
+}
+
+impl Registro for TrajetoriaMultimaquina {
+    fn registrar(&mut self, t: f64, x: &[f64]) {
+        let n = self.m.len();
+        let (delta, delta_w) = x.split_at(n);
+        self.tempos.push(t);
+        for i in 0..n {
+            self.angulos[i].push(delta[i]);
+            self.velocidades[i].push(delta_w[i]);
+        }
+        let m_total: f64 = self.m.iter().sum();
+        let coi = self.m.iter().zip(delta).map(|(m, d)| m * d).sum::<f64>() / m_total;
+        self.angulo_coi.push(coi);
+
+        let maximo = delta.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let minimo = delta.iter().copied().fold(f64::INFINITY, f64::min);
+        let separacao = maximo - minimo;
+        if self.veredito == Veredito::Estavel && separacao > self.separacao_max {
+            self.separacao_max = separacao;
+            self.t_separacao_max = t;
+        }
+        if self.veredito == Veredito::Estavel && separacao > self.limite_angulo {
+            self.veredito = Veredito::Instavel {
+                t_perda: t,
+                criterio: CriterioInstabilidade::LimiteAngulo,
+            };
+        }
+    }
+
+    fn encerrar(&self) -> bool {
+        self.interromper && self.veredito != Veredito::Estavel
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Dois geradores ligados pelas barras 1-2-3 (x = 0,2 e 0,3), sem perdas
+    // nem cargas. Com V1 = 1∠0 e V3 = 1∠-θ, a potência é sin θ / 0,5 e cada
+    // extremo fornece (1 - cos θ) / 0,5 de reativo
+    fn sistema() -> MultiMachineSystem {
+        let theta = 10.0_f64;
+        let p = theta.to_radians().sin() / 0.5;
+        let q = (1.0 - theta.to_radians().cos()) / 0.5;
+        // Tensão da barra do meio pelo divisor de tensão da linha
+        let v1 = Complexo::polar(1.0, 0.0);
+        let v3 = Complexo::polar(1.0, -theta.to_radians());
+        let v2 = v1 + (v3 - v1) * (0.2 / 0.5);
+        toml::from_str(&format!(
+            "barras = [{{ id = 1 }}, {{ id = 2, v = {}, theta = {} }}, {{ id = 3, theta = {} }}]\n\
+             geradores = [\n\
+                 {{ barra = 1, p = {p}, q = {q}, h = 5.0, xd_transitoria = 0.1 }},\n\
+                 {{ barra = 3, p = {}, q = {q}, h = 3.0, xd_transitoria = 0.15 }},\n\
+             ]\n\
+             ramos = [{{ de = 1, para = 2, x = 0.2 }}, {{ de = 2, para = 3, x = 0.3 }}]\n",
+            v2.abs(),
+            v2.arg().to_degrees(),
+            -theta,
+            -p,
+        ))
+        .unwrap()
+    }
+
+    fn perto(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-10
+    }
+
+    fn transferencia(sistema: &MultiMachineSystem, topologia: &Topologia) -> Complexo {
+        RedeReduzivel::new(sistema).unwrap().reduzir(topologia)[0][1]
+    }
+
+    #[test]
+    fn reducao_de_kron_da_ligacao_serie() {
+        let sistema = sistema();
+        let y = RedeReduzivel::new(&sistema)
+            .unwrap()
+            .reduzir(&Topologia::inicial(&sistema));
+        // Y12 = -1 / (j·X) com X = 0,1 + 0,2 + 0,3 + 0,15
+        let x = 0.75;
+        assert!(perto(y[0][1].re, 0.0) && perto(y[0][1].im, 1.0 / x));
+        assert!(perto(y[0][0].re, 0.0) && perto(y[0][0].im, -1.0 / x));
+        assert_eq!(y[0][1], y[1][0]);
+    }
+
+    #[test]
+    fn reducao_de_kron_com_falta_na_barra_do_meio() {
+        let sistema = sistema();
+        let mut topologia = Topologia::inicial(&sistema);
+        let rede = RedeReduzivel::new(&sistema).unwrap();
+
+        // Falta franca: os geradores ficam desacoplados
+        topologia
+            .aplicar(
+                &AcaoRede::Falta {
+                    barra: 2,
+                    reatancia: 0.0,
+                },
+                &rede,
+            )
+            .unwrap();
+        assert_eq!(transferencia(&sistema, &topologia), Complexo::ZERO);
+
+        // Falta por reatância: estrela (a, b, xf) convertida em triângulo
+        let xf = 0.05;
+        topologia
+            .aplicar(
+                &AcaoRede::Falta {
+                    barra: 2,
+                    reatancia: xf,
+                },
+                &rede,
+            )
+            .unwrap();
+        let (a, b) = (0.1 + 0.2, 0.3 + 0.15);
+        let x = a + b + a * b / xf;
+        let y = transferencia(&sistema, &topologia);
+        assert!(perto(y.re, 0.0) && perto(y.im, 1.0 / x), "{:?}", y);
+
+        // Abertura do ramo 1-2: sem ligação entre os geradores
+        topologia
+            .aplicar(&AcaoRede::EliminarFalta { barra: 2 }, &rede)
+            .unwrap();
+        topologia
+            .aplicar(&AcaoRede::AbrirRamo { ramo: 0 }, &rede)
+            .unwrap();
+        assert_eq!(transferencia(&sistema, &topologia), Complexo::ZERO);
+    }
+
+    #[test]
+    fn ponto_de_operacao_reproduz_o_despacho() {
+        let sistema = sistema();
+        let maquinas = sistema.maquinas().unwrap();
+        for (gerador, maquina) in sistema.geradores.iter().zip(&maquinas) {
+            assert!(
+                perto(maquina.pm, gerador.p),
+                "{} != {}",
+                maquina.pm,
+                gerador.p
+            );
+        }
+    }
+
+    #[test]
+    fn sem_eventos_o_sistema_fica_no_equilibrio() {
+        let mut case = Case::default();
+        case.integrador.t_max = 1.0;
+        case.multimaquina = Some(sistema());
+        let r = sim_multimaquina(&case, 0.0, 0.0).unwrap();
+        assert_eq!(r.veredito, Veredito::Estavel);
+        for (angulos, maquina) in r.angulos.iter().zip(&r.maquinas) {
+            let desvio = angulos
+                .iter()
+                .map(|delta| (delta - maquina.delta_0).abs())
+                .fold(0.0, f64::max);
+            assert!(desvio < 1e-9, "desvio de {} rad", desvio);
+        }
+    }
+}
@@ -1,28 +1,137 @@
+//! Função objetivo do PSO e caso ativo.
+//!
+//! O pso_rs recebe a função objetivo e o critério de parada como ponteiros de
+//! função, sem captura de contexto, então o caso em estudo fica registrado em
+//! um espaço global do processo durante a otimização. [`definir_caso_ativo`]
+//! devolve uma reserva ([`CasoAtivo`]) que mantém o caso registrado até ser
+//! descartada: otimizações simultâneas, em threads diferentes, são executadas
+//! uma de cada vez, enquanto o restante de cada estudo pode rodar em paralelo.
+
 use std::f64::consts::PI;
+use std::sync::{Arc, Mutex, MutexGuard, RwLock};
 
 use pso_rs::Particle;
 
-use crate::{area::calcular_areas_melhorado, sim_per_time::sim_pet_time, values::*};
+use crate::{
+    area::calcular_areas_melhorado,
+    case::{Case, ObjetivoPso},
+    energia::margem_energia,
+    estabilidade::Veredito,
+    multimaquina::sim_multimaquina,
+    sim_per_time::sim_pet_time,
+};
+
+// Caso lido pela função objetivo e pelo critério de parada, que rodam nas
+// threads do pso_rs
+static CASO_ATIVO: RwLock<Option<Arc<Case>>> = RwLock::new(None);
+// Reserva do caso ativo: uma otimização por vez no processo
+static RESERVA: Mutex<()> = Mutex::new(());
+
+/// Reserva do caso ativo, obtida com [`definir_caso_ativo`]. Enquanto existir,
+/// o caso é o lido por [`objective_function`] e outras otimizações aguardam;
+/// ao ser descartada, o registro é removido.
+#[must_use = "o caso deixa de ser o caso ativo quando a reserva é descartada"]
+pub struct CasoAtivo {
+    _reserva: MutexGuard<'static, ()>,
+}
+
+impl Drop for CasoAtivo {
+    fn drop(&mut self) {
+        *CASO_ATIVO.write().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+/// Registra o caso lido por [`objective_function`] e pelo critério de parada
+/// até que a reserva devolvida seja descartada. Se outra reserva existir,
+/// aguarda que ela seja descartada.
+pub fn definir_caso_ativo(case: Case) -> CasoAtivo {
+    // Uma otimização que entrou em pânico não invalida as seguintes
+    let reserva = RESERVA.lock().unwrap_or_else(|e| e.into_inner());
+    *CASO_ATIVO.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(case));
+    CasoAtivo { _reserva: reserva }
+}
+
+/// Caso registrado por [`definir_caso_ativo`].
+///
+/// # Panics
+///
+/// Se nenhum caso está registrado (fora de uma otimização).
+pub fn caso_ativo() -> Arc<Case> {
+    CASO_ATIVO
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .expect("caso ativo não definido: chame definir_caso_ativo antes do PSO")
+}
 
+/// Erro quadrático entre as áreas de aceleração (Área 1 + Área 2) e de
+/// desaceleração (Área 3) para a partícula `[tab, tr]`, penalizado quando o
+/// caso é instável ou `tr < tab`.
+///
+/// Com `objetivo = "energia"` em `[pso]`, o erro é o quadrado da margem da
+/// função de energia transitória no último chaveamento (ver
+/// [`crate::energia`]), penalizado quando o método direto não garante a
+/// estabilidade ou `tr < tab`. Sem amortecimento, essa margem é
+/// `Área 3 - (Área 1 + Área 2)`, mas a trajetória só é integrada até o
+/// religamento.
+///
+/// Em casos multimáquinas, o erro é o quadrado da folga entre
+/// `limite_angulo_graus` e a maior separação angular entre dois geradores,
+/// penalizado quando a separação passa do limite ou `tr < tab`: o mínimo fica
+/// na fronteira de estabilidade, como o das áreas iguais, mas em geral não é
+/// nulo, e a busca termina por `t_max` de `[pso]`.
 pub fn objective_function(p: &Particle, _flat_dim: usize, _dimensions: &Vec<usize>) -> f64 {
+    let case = caso_ativo();
+    let sistema = &case.sistema;
     let tab = p[0];
     let tr = p[1];
-    let delta_n_ini = (PM / PE1).asin();
-
-    let (_tempos, _angulos, _velocidades, cra, mut crr) = sim_pet_time(
-        PE1,
-        PE2,
-        PE3,
-        tab,
-        tr,
-        DELTA_W_INI,
-        delta_n_ini,
-        T_MAX,
-        DELTA_T,
-    );
-
-    crr = crr.min(PI);
-    let (area1, area2, area3) = calcular_areas_melhorado(PE1, PE2, PE3, PM, cra, crr);
+
+    if case.multimaquina.is_some() {
+        // A rede é validada antes da busca; um erro aqui só descarta a partícula
+        let Ok(sim) = sim_multimaquina(&case, tab, tr) else {
+            return 1e10;
+        };
+        if sim.veredito != Veredito::Estavel || tr < tab {
+            // Penalidade constante: interrompida logo após passar do limite,
+            // a folga de uma trajetória instável seria quase nula
+            return 1e10;
+        }
+        let limite = case.estabilidade.limite_angulo_graus.to_radians();
+        return (limite - sim.separacao_max).powi(2);
+    }
+
+    if case.pso.objetivo == ObjetivoPso::Energia {
+        // Verificado por `Case::carregar`; um erro aqui só descarta a partícula
+        let Ok(margem) = margem_energia(&case, tab, tr) else {
+            return 1e10;
+        };
+        let erro = margem.margem.powi(2);
+        let penalidade = if !margem.estavel() || tr < tab {
+            1e10
+        } else {
+            1.0
+        };
+        return erro * penalidade;
+    }
+
+    // O modelo do gerador é validado antes da busca; um erro aqui só
+    // descarta a partícula
+    let Ok(sim) = sim_pet_time(&case, tab, tr) else {
+        return 1e10;
+    };
+    // Penalidade constante para trajetórias instáveis, como nos demais
+    // objetivos: interrompidas, elas podem nem chegar a tab ou tr
+    if !sim.estabilidade.estavel() {
+        return 1e10;
+    }
+    // Sem os dois ângulos (simulação encerrada antes de tab ou tr) não há
+    // áreas a comparar
+    let (Some(cra), Some(crr)) = (sim.cra, sim.crr) else {
+        return 1e10;
+    };
+    let crr = crr.min(PI);
+    let (area1, area2, area3) =
+        calcular_areas_melhorado(sistema.pe1, sistema.pe2, sistema.pe3, sistema.pm, cra, crr);
 
     let erro = ((area1 + area2) - area3).powi(2); // Erro quadrático para otimização fina
     let penalidade = if (area1 + area2) > area3 || tr < tab {
@@ -33,3 +142,22 @@ pub fn objective_function(p: &Particle, _flat_dim: usize, _dimensions: &Vec<usiz
 
     erro * penalidade
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trajetoria_instavel_interrompida_e_penalizada() {
+        // Abertura tripolar (Pe3 = 0): religamento tardio perde o sincronismo
+        let mut case = Case::default();
+        case.sistema.pe2 = 0.50924.into();
+        case.sistema.pe3 = 0.0.into();
+        case.sistema.pm = 0.9;
+        case.estabilidade.interromper = true;
+        let _caso_ativo = definir_caso_ativo(case);
+        let avaliar = |tr: f64| objective_function(&vec![0.15, tr], 2, &vec![2]);
+        assert!(avaliar(1.0) >= 1e10);
+        assert!(avaliar(0.3) < 1.0, "{}", avaliar(0.3));
+    }
+}
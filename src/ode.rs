@@ -0,0 +1,101 @@
+//! Sistemas de equações diferenciais ordinárias e integradores numéricos.
+//!
+//! Um modelo dinâmico implementa [`OdeSystem`] (vetor de estado e derivada) e
+//! um método numérico implementa [`Integrator`], de modo que o mesmo integrador
+//! serve à equação de oscilação, a modelos com reguladores e a sistemas
+//! multimáquinas.
+
+/// Sistema de EDOs `dx/dt = f(t, x)`.
+///
+/// Os parâmetros do modelo ficam nos campos do tipo que implementa o trait.
+pub trait OdeSystem {
+    /// Dimensão do vetor de estado.
+    fn dimensao(&self) -> usize;
+
+    /// Calcula `dxdt = f(t, x)`.
+    fn derivada(&self, t: f64, x: &[f64], dxdt: &mut [f64]);
+}
+
+/// Método de integração numérica de passo único.
+pub trait Integrator {
+    /// Avança o estado `x` do instante `t` para `t + h`.
+    fn passo(&mut self, sistema: &dyn OdeSystem, t: f64, x: &mut [f64], h: f64);
+}
+
+/// Método de integração com passo adaptativo e saída densa.
+pub trait AdaptiveIntegrator {
+    /// Avança o estado `x` a partir de `t` com um passo aceito pelo controle de
+    /// erro, sem ultrapassar `t_limite`, e retorna o instante alcançado.
+    fn passo_adaptativo(
+        &mut self,
+        sistema: &dyn OdeSystem,
+        t: f64,
+        x: &mut [f64],
+        t_limite: f64,
+    ) -> f64;
+
+    /// Estado interpolado no instante `t`, dentro do último passo aceito.
+    fn interpolar(&self, t: f64, saida: &mut [f64]);
+}
+
+/// Runge-Kutta clássico de 4ª ordem.
+///
+/// Guarda os vetores intermediários para não alocar memória a cada passo.
+#[derive(Debug, Clone, Default)]
+pub struct Rk4 {
+    k1: Vec<f64>,
+    k2: Vec<f64>,
+    k3: Vec<f64>,
+    k4: Vec<f64>,
+    x_tmp: Vec<f64>,
+}
+
+impl Rk4 {
+    /// Cria o integrador.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Integrator for Rk4 {
+    fn passo(&mut self, sistema: &dyn OdeSystem, t: f64, x: &mut [f64], h: f64) {
+        let n = sistema.dimensao();
+        for v in [
+            &mut self.k1,
+            &mut self.k2,
+            &mut self.k3,
+            &mut self.k4,
+            &mut self.x_tmp,
+        ] {
+            v.resize(n, 0.0);
+        }
+
+        sistema.derivada(t, x, &mut self.k1);
+
+        combinar(&mut self.x_tmp, x, 0.5 * h, &self.k1);
+        sistema.derivada(t + 0.5 * h, &self.x_tmp, &mut self.k2);
+
+        combinar(&mut self.x_tmp, x, 0.5 * h, &self.k2);
+        sistema.derivada(t + 0.5 * h, &self.x_tmp, &mut self.k3);
+
+        combinar(&mut self.x_tmp, x, h, &self.k3);
+        sistema.derivada(t + h, &self.x_tmp, &mut self.k4);
+
+        for ((((xi, k1), k2), k3), k4) in x
+            .iter_mut()
+            .zip(&self.k1)
+            .zip(&self.k2)
+            .zip(&self.k3)
+            .zip(&self.k4)
+        {
+            *xi += h * (k1 + 2.0 * k2 + 2.0 * k3 + k4) / 6.0;
+        }
+    }
+}
+
+// destino = x + h * k (combinação usada nos estágios dos métodos Runge-Kutta)
+pub(crate) fn combinar(destino: &mut [f64], x: &[f64], h: f64, k: &[f64]) {
+    for ((d, xi), ki) in destino.iter_mut().zip(x).zip(k) {
+        *d = xi + h * ki;
+    }
+}
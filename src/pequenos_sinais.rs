@@ -0,0 +1,363 @@
+//! Análise de pequenos sinais no ponto de equilíbrio pós-falta.
+//!
+//! O modelo do gerador de `[maquina]`, com os controladores acoplados (ver
+//! [`crate::maquina`]), recebe todos os chaveamentos da sequência de eventos;
+//! o equilíbrio da rede final (após o religamento, na sequência clássica) é
+//! obtido por Newton-Raphson a partir do estado pré-falta. A matriz de estado
+//! `A = ∂f/∂x` vem de diferenças centrais nesse ponto, e seus autovalores, da
+//! decomposição de Schur real do crate `nalgebra` (QR com deslocamento duplo
+//! sobre a forma de Hessenberg).
+//!
+//! Para cada autovalor `λ = σ ± jω`: frequência natural `ω/2π`, razão de
+//! amortecimento `ζ = -σ/|λ|` e fatores de participação
+//! `p_k = |w_k·v_k / (wᵀ·v)|`, com os autovetores à direita `v` e à esquerda
+//! `w` obtidos por iteração inversa.
+//!
+//! Estados com derivada identicamente nula (blocos de controladores com
+//! constante de tempo nula) ficam fora da linearização. No modelo clássico com
+//! a curva `Pe = Pmax·sin(δ - γ)`, os modos são os da equação
+//! `M·s² + D·s + Pmax·cos(δs - γ) = 0`.
+
+use std::f64::consts::PI;
+
+use nalgebra::{DMatrix, Schur};
+
+use crate::algebra::resolver_linear;
+use crate::case::Case;
+use crate::complexo::Complexo;
+use crate::error::{Error, Result};
+use crate::eventos;
+use crate::maquina::{MachineModel, modelo_maquina};
+use crate::swing::DELTA;
+
+const TOLERANCIA: f64 = 1e-10;
+const MAX_ITERACOES: usize = 50;
+// Iterações do QR por autovalor, como no LAPACK
+const MAX_ITERACOES_QR: usize = 30;
+// Passo relativo das diferenças centrais
+const PASSO: f64 = 1e-6;
+// Parte real abaixo de RESOLUCAO·|λ| é erro das diferenças centrais: o modo
+// não tem amortecimento (modelo clássico com D = 0)
+const RESOLUCAO: f64 = 1e-8;
+
+/// Modo de oscilação (ou modo real) do sistema linearizado.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Modo {
+    /// Parte real `σ` do autovalor (1/s).
+    pub real: f64,
+    /// Parte imaginária `ω ≥ 0` do autovalor (rad/s); o par conjugado não é
+    /// listado.
+    pub imaginaria: f64,
+    /// Frequência natural amortecida `ω/2π` (Hz).
+    pub frequencia: f64,
+    /// Razão de amortecimento `ζ = -σ/|λ|`.
+    pub amortecimento: f64,
+    /// Fator de participação de cada estado de
+    /// [`AnalisePequenosSinais::estados`] no modo.
+    pub participacao: Vec<f64>,
+}
+
+impl Modo {
+    /// `true` se o autovalor é complexo (modo oscilatório).
+    pub fn oscilatorio(&self) -> bool {
+        self.imaginaria > 0.0
+    }
+}
+
+/// Resultado da análise de pequenos sinais.
+#[derive(Debug, Clone)]
+pub struct AnalisePequenosSinais {
+    /// Nomes dos estados linearizados.
+    pub estados: Vec<String>,
+    /// Ponto de equilíbrio pós-falta dos estados linearizados.
+    pub equilibrio: Vec<f64>,
+    /// Ângulo do rotor no equilíbrio pós-falta (rad).
+    pub delta: f64,
+    /// Matriz de estado `A` no equilíbrio.
+    pub matriz_estado: Vec<Vec<f64>>,
+    /// Modos, do menos para o mais amortecido.
+    pub modos: Vec<Modo>,
+}
+
+impl AnalisePequenosSinais {
+    /// `true` se todos os autovalores têm parte real negativa.
+    pub fn estavel(&self) -> bool {
+        self.modos.iter().all(|m| m.real < 0.0)
+    }
+
+    /// `true` se algum autovalor tem parte real positiva; sem autovalores
+    /// positivos, um sistema não [`estavel`](Self::estavel) tem modos sem
+    /// amortecimento.
+    pub fn instavel(&self) -> bool {
+        self.modos.iter().any(|m| m.real > 0.0)
+    }
+
+    /// Modo oscilatório de menor amortecimento (eletromecânico, no SMIB).
+    pub fn modo_dominante(&self) -> Option<&Modo> {
+        self.modos.iter().find(|m| m.oscilatorio())
+    }
+}
+
+/// Lineariza o modelo do gerador do caso em torno do equilíbrio da rede final
+/// da sequência de eventos com abertura em `tab` e religamento em `tr`, e
+/// calcula autovalores, frequências, amortecimentos e fatores de
+/// participação.
+///
+/// # Errors
+///
+/// Se o modelo do gerador não pode ser montado, se Newton-Raphson não
+/// encontra o equilíbrio pós-falta (rede final sem ponto de operação) ou se
+/// o método QR não converge.
+pub fn analisar_pequenos_sinais(case: &Case, tab: f64, tr: f64) -> Result<AnalisePequenosSinais> {
+    let mut maquina = modelo_maquina(case)?;
+    for chaveamento in eventos::resolver(&case.eventos, &case.sistema, tab, tr) {
+        maquina.chavear(&chaveamento);
+    }
+    let inicial = maquina.estado_inicial(&case.sistema);
+    let ativos = estados_ativos(maquina.as_ref(), &inicial);
+    let x = equilibrio(maquina.as_ref(), inicial, &ativos)?;
+    let a = submatriz(&jacobiana(maquina.as_ref(), &x), &ativos);
+
+    let nomes = maquina.nomes_estados();
+    let mut modos: Vec<Modo> = autovalores(&a)?
+        .into_iter()
+        .filter(|lambda| lambda.im >= 0.0)
+        .map(|lambda| {
+            let modulo = lambda.abs();
+            let real = if lambda.re.abs() <= RESOLUCAO * modulo {
+                0.0
+            } else {
+                lambda.re
+            };
+            Modo {
+                real,
+                imaginaria: lambda.im,
+                frequencia: lambda.im / (2.0 * PI),
+                amortecimento: if real != 0.0 { -real / modulo } else { 0.0 },
+                participacao: participacao(&a, lambda),
+            }
+        })
+        .collect();
+    modos.sort_by(|m1, m2| m1.amortecimento.total_cmp(&m2.amortecimento));
+
+    Ok(AnalisePequenosSinais {
+        estados: ativos.iter().map(|&i| nomes[i].clone()).collect(),
+        equilibrio: ativos.iter().map(|&i| x[i]).collect(),
+        delta: x[DELTA],
+        matriz_estado: a,
+        modos,
+    })
+}
+
+/// Matriz jacobiana `∂f/∂x` do modelo no estado `x` por diferenças centrais,
+/// com a válvula rápida já reaberta.
+pub fn jacobiana(maquina: &dyn MachineModel, x: &[f64]) -> Vec<Vec<f64>> {
+    let n = x.len();
+    let mut colunas = Vec::with_capacity(n);
+    let (mut mais, mut menos) = (vec![0.0; n], vec![0.0; n]);
+    let mut perturbado = x.to_vec();
+    for j in 0..n {
+        let h = PASSO * x[j].abs().max(1.0);
+        perturbado[j] = x[j] + h;
+        maquina.derivada(f64::INFINITY, &perturbado, &mut mais);
+        perturbado[j] = x[j] - h;
+        maquina.derivada(f64::INFINITY, &perturbado, &mut menos);
+        perturbado[j] = x[j];
+        colunas.push(
+            mais.iter()
+                .zip(&menos)
+                .map(|(a, b)| (a - b) / (2.0 * h))
+                .collect::<Vec<f64>>(),
+        );
+    }
+    (0..n)
+        .map(|i| colunas.iter().map(|coluna| coluna[i]).collect())
+        .collect()
+}
+
+// Autovalores de uma matriz real quadrada pela decomposição de Schur real do
+// nalgebra (redução de Hessenberg e QR com deslocamento duplo de Francis)
+pub(crate) fn autovalores(a: &[Vec<f64>]) -> Result<Vec<Complexo>> {
+    let n = a.len();
+    let matriz = DMatrix::from_fn(n, n, |i, j| a[i][j]);
+    let schur =
+        Schur::try_new(matriz, f64::EPSILON, MAX_ITERACOES_QR * n.max(1)).ok_or_else(|| {
+            Error::Convergencia("método QR não convergiu no cálculo dos autovalores".to_string())
+        })?;
+    Ok(schur
+        .complex_eigenvalues()
+        .iter()
+        .map(|lambda| Complexo::new(lambda.re, lambda.im))
+        .collect())
+}
+
+// Estados cuja derivada depende de algum estado em `x`; os demais (blocos
+// ignorados) ficam constantes
+fn estados_ativos(maquina: &dyn MachineModel, x: &[f64]) -> Vec<usize> {
+    jacobiana(maquina, x)
+        .iter()
+        .enumerate()
+        .filter(|(_, linha)| linha.iter().any(|&v| v != 0.0))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn submatriz(a: &[Vec<f64>], indices: &[usize]) -> Vec<Vec<f64>> {
+    indices
+        .iter()
+        .map(|&i| indices.iter().map(|&j| a[i][j]).collect())
+        .collect()
+}
+
+// Equilíbrio `f(x) = 0` por Newton-Raphson nos estados `ativos`
+fn equilibrio(maquina: &dyn MachineModel, mut x: Vec<f64>, ativos: &[usize]) -> Result<Vec<f64>> {
+    let mut f = vec![0.0; x.len()];
+    for _ in 0..MAX_ITERACOES {
+        maquina.derivada(f64::INFINITY, &x, &mut f);
+        let residuo: Vec<f64> = ativos.iter().map(|&i| -f[i]).collect();
+        if residuo.iter().all(|r| r.abs() < TOLERANCIA) {
+            return Ok(x);
+        }
+        let j = submatriz(&jacobiana(maquina, &x), ativos);
+        let correcao = resolver_linear(j, residuo).ok_or_else(|| {
+            Error::Convergencia("jacobiana singular na busca do equilíbrio pós-falta".to_string())
+        })?;
+        for (&i, dx) in ativos.iter().zip(correcao) {
+            x[i] += dx;
+        }
+    }
+    Err(Error::Convergencia(format!(
+        "equilíbrio pós-falta não encontrado em {} iterações (rede final sem ponto de \
+         operação estável?)",
+        MAX_ITERACOES
+    )))
+}
+
+// Fatores de participação do autovalor `lambda` de `a`
+fn participacao(a: &[Vec<f64>], lambda: Complexo) -> Vec<f64> {
+    let transposta: Vec<Vec<f64>> = (0..a.len())
+        .map(|j| a.iter().map(|linha| linha[j]).collect())
+        .collect();
+    let v = autovetor(a, lambda);
+    let w = autovetor(&transposta, lambda);
+    let produtos: Vec<Complexo> = w.iter().zip(&v).map(|(&wk, &vk)| wk * vk).collect();
+    let soma = produtos.iter().fold(Complexo::ZERO, |acc, &p| acc + p);
+    produtos.iter().map(|&p| (p / soma).abs()).collect()
+}
+
+// Autovetor de `a` associado a `lambda` por iteração inversa
+fn autovetor(a: &[Vec<f64>], lambda: Complexo) -> Vec<Complexo> {
+    let n = a.len();
+    let deslocada: Vec<Vec<Complexo>> = (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| {
+                    let diagonal = if i == j { lambda } else { Complexo::ZERO };
+                    Complexo::new(a[i][j], 0.0) - diagonal
+                })
+                .collect()
+        })
+        .collect();
+    let mut v = vec![Complexo::new(1.0, 0.0); n];
+    for _ in 0..3 {
+        v = resolver_complexo(deslocada.clone(), v);
+        let maior = v.iter().fold(0.0, |m: f64, c| m.max(c.abs()));
+        if maior > 0.0 {
+            v.iter_mut().for_each(|c| *c = *c * (1.0 / maior));
+        }
+    }
+    v
+}
+
+// Eliminação de Gauss complexa com pivotamento parcial; pivôs nulos (matriz
+// deslocada por um autovalor) são substituídos por um valor pequeno
+#[allow(clippy::needless_range_loop)]
+fn resolver_complexo(mut a: Vec<Vec<Complexo>>, mut b: Vec<Complexo>) -> Vec<Complexo> {
+    let n = b.len();
+    let escala = a
+        .iter()
+        .flatten()
+        .fold(0.0, |m: f64, c| m.max(c.abs()))
+        .max(1.0);
+    for k in 0..n {
+        let pivo = (k..n)
+            .max_by(|&i, &j| a[i][k].abs().total_cmp(&a[j][k].abs()))
+            .unwrap_or(k);
+        a.swap(k, pivo);
+        b.swap(k, pivo);
+        if a[k][k].abs() < 1e-14 * escala {
+            a[k][k] = Complexo::new(1e-14 * escala, 0.0);
+        }
+        for i in k + 1..n {
+            let fator = a[i][k] / a[k][k];
+            for j in k..n {
+                let akj = a[k][j];
+                a[i][j] -= fator * akj;
+            }
+            let bk = b[k];
+            b[i] -= fator * bk;
+        }
+    }
+    let mut x = vec![Complexo::ZERO; n];
+    for k in (0..n).rev() {
+        let soma = (k + 1..n).fold(Complexo::ZERO, |acc, j| acc + a[k][j] * x[j]);
+        x[k] = (b[k] - soma) / a[k][k];
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::case::caso_sem_amortecimento;
+
+    #[test]
+    fn modo_eletromecanico_do_modelo_classico() {
+        let case = caso_sem_amortecimento();
+        let r = analisar_pequenos_sinais(&case, 0.1, 0.0).unwrap();
+        // Na sequência clássica, a rede final é a do religamento (Pe1)
+        let delta_s = (1.44_f64 / 1.83333).asin();
+        assert!((r.delta - delta_s).abs() < 1e-9);
+
+        // M·s² + Pmax·cos δs = 0: s = ±j·sqrt(Pmax·cos δs / M)
+        let m = 9.0 / (PI * 60.0);
+        let omega = (1.83333 * delta_s.cos() / m).sqrt();
+        let modo = r.modo_dominante().unwrap();
+        assert_eq!(r.modos.len(), 1);
+        assert_eq!(modo.real, 0.0);
+        assert!((modo.imaginaria - omega).abs() < 1e-6 * omega);
+        assert!(!r.estavel() && !r.instavel());
+    }
+
+    #[test]
+    fn amortecimento_desloca_a_parte_real() {
+        let mut case = caso_sem_amortecimento();
+        case.sistema.d = 0.02;
+        let r = analisar_pequenos_sinais(&case, 0.1, 0.0).unwrap();
+        // σ = -D / (2·M)
+        let m = 9.0 / (PI * 60.0);
+        let modo = r.modo_dominante().unwrap();
+        assert!((modo.real + 0.02 / (2.0 * m)).abs() < 1e-6);
+        assert!(r.estavel());
+    }
+
+    #[test]
+    fn qr_na_matriz_companheira() {
+        // (x - 1)(x - 2)(x - 3)(x² + 4) = x⁵ - 6x⁴ + 15x³ - 30x² + 44x - 24
+        let mut a = vec![vec![0.0; 5]; 5];
+        a[0] = vec![6.0, -15.0, 30.0, -44.0, 24.0];
+        for i in 1..5 {
+            a[i][i - 1] = 1.0;
+        }
+        let mut lambda = autovalores(&a).unwrap();
+        lambda.sort_by(|l1, l2| l1.re.total_cmp(&l2.re).then(l1.im.total_cmp(&l2.im)));
+        let esperados = [(0.0, -2.0), (0.0, 2.0), (1.0, 0.0), (2.0, 0.0), (3.0, 0.0)];
+        for (l, (re, im)) in lambda.iter().zip(esperados) {
+            assert!(
+                (l.re - re).abs() < 1e-9 && (l.im - im).abs() < 1e-9,
+                "{:?}",
+                lambda
+            );
+        }
+    }
+}
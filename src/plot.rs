@@ -1,15 +1,20 @@
+//! Gráficos da simulação no tempo e das curvas de potência.
+
 use plotters::prelude::*;
 use std::f64::consts::PI;
 use std::process::Command;
 
-// Função para plotar ângulos e velocidades ao longo do tempo
+use crate::curva::PowerAngleCurve;
+use crate::error::Result;
+
+/// Função para plotar ângulos e velocidades ao longo do tempo.
 pub fn plotar_angulos_velocidades(
     tempos: &[f64],
     angulos: &[f64],
     velocidades: &[f64],
     nome_arquivo: &str,
     t_max_plot: f64,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<()> {
     let root = BitMapBackend::new(nome_arquivo, (800, 600)).into_drawing_area();
     root.fill(&WHITE)?;
 
@@ -43,7 +48,7 @@ pub fn plotar_angulos_velocidades(
                 &BLUE,
             ))?
             .label("Ângulo")
-            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &BLUE));
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], BLUE));
 
         chart.configure_series_labels().draw()?;
     }
@@ -73,7 +78,7 @@ pub fn plotar_angulos_velocidades(
                 &RED,
             ))?
             .label("Velocidade")
-            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &RED));
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], RED));
 
         chart.configure_series_labels().draw()?;
     }
@@ -83,35 +88,260 @@ pub fn plotar_angulos_velocidades(
     Ok(())
 }
 
-// Função para calcular potência elétrica
-fn calcular_potencia_eletrica(angulo_rad: f64, pe_max: f64) -> f64 {
-    pe_max * angulo_rad.sin()
+/// Tensão terminal e tensão de campo do gerador ao longo do tempo, em dois
+/// gráficos empilhados como os de [`plotar_angulos_velocidades`].
+pub fn plotar_excitacao(
+    tempos: &[f64],
+    tensao_terminal: &[f64],
+    tensao_campo: &[f64],
+    nome_arquivo: &str,
+    t_max_plot: f64,
+) -> Result<()> {
+    let root = BitMapBackend::new(nome_arquivo, (800, 600)).into_drawing_area();
+    root.fill(&WHITE)?;
+    let areas = root.split_evenly((2, 1));
+
+    let sinais = [
+        (
+            "Tensão Terminal vs Tempo",
+            "Vt (p.u.)",
+            "Vt",
+            tensao_terminal,
+            BLUE,
+        ),
+        (
+            "Tensão de Campo vs Tempo",
+            "Efd (p.u.)",
+            "Efd",
+            tensao_campo,
+            RED,
+        ),
+    ];
+    for (area, (titulo, eixo, rotulo, valores, cor)) in areas.iter().zip(sinais) {
+        let min = valores.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+        let max = valores.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+        // Sinal constante (tensão de campo sem excitatriz): faixa mínima
+        let folga = ((max - min) * 0.05).max(1e-3);
+        let mut chart = ChartBuilder::on(area)
+            .caption(titulo, ("sans-serif", 40))
+            .margin(10)
+            .x_label_area_size(50)
+            .y_label_area_size(60)
+            .build_cartesian_2d(tempos[0]..t_max_plot, min - folga..max + folga)?;
+
+        chart
+            .configure_mesh()
+            .x_desc("Tempo (s)")
+            .y_desc(eixo)
+            .draw()?;
+
+        chart
+            .draw_series(LineSeries::new(
+                tempos.iter().zip(valores.iter()).map(|(t, v)| (*t, *v)),
+                &cor,
+            ))?
+            .label(rotulo)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], cor));
+
+        chart.configure_series_labels().draw()?;
+    }
+
+    root.present()?;
+    Ok(())
 }
 
-// Função para encontrar interseções entre curva de potência e PM
-fn encontrar_intersecoes(pe_max: f64, pm: f64) -> (f64, f64) {
-    if pe_max > pm {
-        // Primeira interseção (delta_n_ini)
-        let delta_n_ini = (pm / pe_max).asin();
-
-        // Segunda interseção (delta_m)
-        let delta_m = PI - delta_n_ini;
-        (delta_n_ini, delta_m)
-    } else {
-        (0.0, 0.0)
+/// Potência mecânica do gerador ao longo do tempo, com o regulador de
+/// velocidade e a válvula rápida.
+pub fn plotar_potencia_mecanica(
+    tempos: &[f64],
+    potencia_mecanica: &[f64],
+    nome_arquivo: &str,
+    t_max_plot: f64,
+) -> Result<()> {
+    let root = BitMapBackend::new(nome_arquivo, (800, 400)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let min = potencia_mecanica
+        .iter()
+        .fold(f64::INFINITY, |a, &b| a.min(b));
+    let max = potencia_mecanica
+        .iter()
+        .fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+    let folga = ((max - min) * 0.05).max(1e-3);
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Potência Mecânica vs Tempo", ("sans-serif", 40))
+        .margin(10)
+        .x_label_area_size(50)
+        .y_label_area_size(60)
+        .build_cartesian_2d(tempos[0]..t_max_plot, min - folga..max + folga)?;
+
+    chart
+        .configure_mesh()
+        .x_desc("Tempo (s)")
+        .y_desc("Pm (p.u.)")
+        .draw()?;
+
+    chart
+        .draw_series(LineSeries::new(
+            tempos
+                .iter()
+                .zip(potencia_mecanica.iter())
+                .map(|(t, p)| (*t, *p)),
+            &BLUE,
+        ))?
+        .label("Pm")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], BLUE));
+
+    chart.configure_series_labels().draw()?;
+
+    root.present()?;
+    Ok(())
+}
+
+/// Ângulos e velocidades de várias máquinas ao longo do tempo, uma curva por
+/// máquina com o rótulo correspondente em `rotulos`.
+pub fn plotar_angulos_multimaquina(
+    tempos: &[f64],
+    angulos: &[Vec<f64>],
+    velocidades: &[Vec<f64>],
+    rotulos: &[String],
+    nome_arquivo: &str,
+    t_max_plot: f64,
+) -> Result<()> {
+    let root = BitMapBackend::new(nome_arquivo, (800, 600)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    // Dividir em dois subplots
+    let areas = root.split_evenly((2, 1));
+    let subplots = [
+        (&areas[0], angulos, "Ângulos (COI) vs Tempo", "Ângulo (rad)"),
+        (
+            &areas[1],
+            velocidades,
+            "Velocidades vs Tempo",
+            "Velocidade (rad/s)",
+        ),
+    ];
+
+    for (area, series, titulo, eixo_y) in subplots {
+        let valores = series.iter().flatten();
+        let minimo = valores.clone().fold(f64::INFINITY, |a, &b| a.min(b));
+        let maximo = valores.fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+        let mut chart = ChartBuilder::on(area)
+            .caption(titulo, ("sans-serif", 40))
+            .margin(10)
+            .x_label_area_size(50)
+            .y_label_area_size(60)
+            .build_cartesian_2d(tempos[0]..t_max_plot, minimo..maximo)?;
+
+        chart
+            .configure_mesh()
+            .x_desc("Tempo (s)")
+            .y_desc(eixo_y)
+            .draw()?;
+
+        for (i, (serie, rotulo)) in series.iter().zip(rotulos).enumerate() {
+            let cor = Palette99::pick(i).to_rgba();
+            chart
+                .draw_series(LineSeries::new(
+                    tempos.iter().zip(serie.iter()).map(|(t, v)| (*t, *v)),
+                    cor,
+                ))?
+                .label(rotulo.as_str())
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], cor));
+        }
+
+        chart
+            .configure_series_labels()
+            .background_style(WHITE.mix(0.8))
+            .draw()?;
     }
+
+    root.present()?;
+    Ok(())
 }
 
-// Função para plotar curva de potência elétrica vs ângulo de potência
+/// Trajetória de [`plotar_comparacao`]: `(rótulo, tempos, ângulos,
+/// velocidades)`.
+pub type TrajetoriaComparada<'a> = (&'a str, &'a [f64], &'a [f64], &'a [f64]);
+
+/// Ângulos e velocidades de várias simulações do mesmo gerador sobrepostos.
+pub fn plotar_comparacao(
+    trajetorias: &[TrajetoriaComparada],
+    nome_arquivo: &str,
+    t_max_plot: f64,
+) -> Result<()> {
+    let root = BitMapBackend::new(nome_arquivo, (800, 600)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    // Dividir em dois subplots
+    let areas = root.split_evenly((2, 1));
+    let subplots = [
+        (&areas[0], "Ângulos vs Tempo", "Ângulo (rad)"),
+        (&areas[1], "Velocidades vs Tempo", "Velocidade (rad/s)"),
+    ];
+
+    for (k, (area, titulo, eixo_y)) in subplots.into_iter().enumerate() {
+        let serie = |trajetoria: &TrajetoriaComparada| -> Vec<(f64, f64)> {
+            let (_, tempos, angulos, velocidades) = *trajetoria;
+            let valores = if k == 0 { angulos } else { velocidades };
+            tempos
+                .iter()
+                .copied()
+                .zip(valores.iter().copied())
+                .collect()
+        };
+        let pontos: Vec<Vec<(f64, f64)>> = trajetorias.iter().map(serie).collect();
+        let valores = pontos.iter().flatten().map(|&(_, v)| v);
+        let minimo = valores.clone().fold(f64::INFINITY, f64::min);
+        let maximo = valores.fold(f64::NEG_INFINITY, f64::max);
+        let inicio = trajetorias.first().map_or(0.0, |t| t.1[0]);
+        let mut chart = ChartBuilder::on(area)
+            .caption(titulo, ("sans-serif", 40))
+            .margin(10)
+            .x_label_area_size(50)
+            .y_label_area_size(60)
+            .build_cartesian_2d(inicio..t_max_plot, minimo..maximo)?;
+
+        chart
+            .configure_mesh()
+            .x_desc("Tempo (s)")
+            .y_desc(eixo_y)
+            .draw()?;
+
+        for (i, (trajetoria, pontos)) in trajetorias.iter().zip(pontos).enumerate() {
+            let cor = Palette99::pick(i).to_rgba();
+            chart
+                .draw_series(LineSeries::new(pontos, cor))?
+                .label(trajetoria.0)
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], cor));
+        }
+
+        chart
+            .configure_series_labels()
+            .background_style(WHITE.mix(0.8))
+            .draw()?;
+    }
+
+    root.present()?;
+    Ok(())
+}
+
+// Função para encontrar interseções entre curva de potência e PM
+fn encontrar_intersecoes(curva: &PowerAngleCurve, pm: f64) -> (f64, f64) {
+    curva.intersecoes(pm).unwrap_or((0.0, 0.0))
+}
+
+/// Função para plotar curva de potência elétrica vs ângulo de potência.
 pub fn plotar_curva_potencia(
-    pe1: f64, // Pe máximo pré-falta
-    pe2: f64, // Pe máximo durante falta
-    pe3: f64, // Pe máximo pós-falta
-    pm: f64,  // Potência mecânica
-    cra: f64, // Limite inferior de critério de estabilidade
-    crr: f64, // Limite superior de critério de estabilidade
+    pe1: PowerAngleCurve, // Curva pré-falta
+    pe2: PowerAngleCurve, // Curva durante falta
+    pe3: PowerAngleCurve, // Curva pós-falta
+    pm: f64,              // Potência mecânica
+    cra: f64,             // Limite inferior de critério de estabilidade
+    crr: f64,             // Limite superior de critério de estabilidade
     nome_arquivo: &str,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<()> {
     let root = BitMapBackend::new(nome_arquivo, (800, 600)).into_drawing_area();
     root.fill(&WHITE)?;
 
@@ -123,22 +353,13 @@ pub fn plotar_curva_potencia(
     let angulos_graus: Vec<f64> = angulos_rad.iter().map(|&a| a.to_degrees()).collect();
 
     // Cálculo das potências
-    let pe1_valores: Vec<f64> = angulos_rad
-        .iter()
-        .map(|&a| calcular_potencia_eletrica(a, pe1))
-        .collect();
-    let pe2_valores: Vec<f64> = angulos_rad
-        .iter()
-        .map(|&a| calcular_potencia_eletrica(a, pe2))
-        .collect();
-    let pe3_valores: Vec<f64> = angulos_rad
-        .iter()
-        .map(|&a| calcular_potencia_eletrica(a, pe3))
-        .collect();
+    let pe1_valores: Vec<f64> = angulos_rad.iter().map(|&a| pe1.potencia(a)).collect();
+    let pe2_valores: Vec<f64> = angulos_rad.iter().map(|&a| pe2.potencia(a)).collect();
+    let pe3_valores: Vec<f64> = angulos_rad.iter().map(|&a| pe3.potencia(a)).collect();
 
     // Interseções
-    let intersecoes_pe1 = encontrar_intersecoes(pe1, pm);
-    let intersecoes_pe3 = encontrar_intersecoes(pe3, pm);
+    let intersecoes_pe1 = encontrar_intersecoes(&pe1, pm);
+    let intersecoes_pe3 = encontrar_intersecoes(&pe3, pm);
 
     let delta_n_ini_graus = intersecoes_pe1.0.to_degrees();
     let delta_m_graus = intersecoes_pe1.1.to_degrees();
@@ -146,7 +367,13 @@ pub fn plotar_curva_potencia(
     let delta_cl_graus = intersecoes_pe3.1.to_degrees();
 
     // Limites do gráfico
-    let max_pe = pe1.max(pe2).max(pe3).max(pm).max(cra).max(crr);
+    let max_pe = pe1
+        .maximo()
+        .max(pe2.maximo())
+        .max(pe3.maximo())
+        .max(pm)
+        .max(cra)
+        .max(crr);
     let y_max = max_pe * 1.2;
 
     let mut chart = ChartBuilder::on(&root)
@@ -253,7 +480,10 @@ pub fn plotar_curva_potencia(
     }
 
     // ÁREA DE ACELERAÇÃO ORIGINAL: verde entre delta_n_ini e delta_cr (mantida)
-    if pe1 > pm && pe3 > pm && delta_n_ini_graus < delta_cr_graus {
+    if pe1.intersecoes(pm).is_some()
+        && pe3.intersecoes(pm).is_some()
+        && delta_n_ini_graus < delta_cr_graus
+    {
         let area_acel: Vec<(f64, f64)> = angulos_graus
             .iter()
             .zip(pe3_valores.iter())
@@ -267,7 +497,10 @@ pub fn plotar_curva_potencia(
     }
 
     // ÁREA DE DESACELERAÇÃO ORIGINAL: vermelha entre delta_cl e delta_m (mantida)
-    if pe1 > pm && pe3 > pm && delta_cl_graus < delta_m_graus {
+    if pe1.intersecoes(pm).is_some()
+        && pe3.intersecoes(pm).is_some()
+        && delta_cl_graus < delta_m_graus
+    {
         let area_desacel: Vec<(f64, f64)> = angulos_graus
             .iter()
             .zip(pe2_valores.iter())
@@ -290,7 +523,7 @@ pub fn plotar_curva_potencia(
             &BLUE,
         ))?
         .label("Pré-falta")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &BLUE));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], BLUE));
 
     chart
         .draw_series(LineSeries::new(
@@ -301,7 +534,7 @@ pub fn plotar_curva_potencia(
             &RED,
         ))?
         .label("Durante falta")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &RED));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], RED));
 
     chart
         .draw_series(LineSeries::new(
@@ -312,7 +545,7 @@ pub fn plotar_curva_potencia(
             &GREEN,
         ))?
         .label("Pós-falta")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &GREEN));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], GREEN));
 
     // Linha Pm
     chart
@@ -321,22 +554,22 @@ pub fn plotar_curva_potencia(
             CYAN.stroke_width(2),
         ))?
         .label(format!("Pm = {:.2}", pm))
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &CYAN));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], CYAN));
 
     // Linhas verticais CRA e CRR
     chart.draw_series(std::iter::once(PathElement::new(
         vec![(cra.to_degrees(), 0.0), (cra.to_degrees(), y_max)],
-        &BLACK,
+        BLACK,
     )))?;
     chart.draw_series(std::iter::once(PathElement::new(
         vec![(crr.to_degrees(), 0.0), (crr.to_degrees(), y_max)],
-        &BLACK,
+        BLACK,
     )))?;
 
     // Legenda
     chart
         .configure_series_labels()
-        .background_style(&WHITE.mix(0.8))
+        .background_style(WHITE.mix(0.8))
         .draw()?;
 
     root.present()?;
@@ -344,6 +577,7 @@ pub fn plotar_curva_potencia(
     Ok(())
 }
 
+/// Abre a imagem no visualizador padrão do sistema operacional.
 pub fn abrir_imagem(caminho: &str) {
     #[cfg(target_os = "windows")]
     {
@@ -1,17 +1,28 @@
+//! Configuração do PSO a partir do caso.
+
 use pso_rs::Config;
 
-pub fn pso_config() -> (Config, Option<fn(f64) -> bool>) {
+use crate::{case::PsoSettings, objective_function::caso_ativo};
+
+/// Critério de parada no formato aceito pelo pso_rs.
+pub type Terminate = Option<fn(f64) -> bool>;
+
+/// Monta a configuração do pso_rs para a busca de `[tab, tr]`.
+pub fn pso_config(settings: &PsoSettings) -> (Config, Terminate) {
+    let padrao = Config::default();
     let config = Config {
-        dimensions: vec![2],                             // [tab, tr]
-        bounds: vec![(0.15, 0.15 + 1e-10), (0.15, 5.0)], // Limites [tab_min, tab_max], [tr_min, tr_max]
-        c1: 2.05,                                        // Coeficiente cognitivo
-        c2: 2.05,                                        // Coeficiente social
-        population_size: 1000,                           // Tamanho da população
-        ..Config::default()
+        dimensions: vec![2],                                     // [tab, tr]
+        bounds: vec![settings.limites_tab, settings.limites_tr], // Limites [tab_min, tab_max], [tr_min, tr_max]
+        c1: settings.c1,                                         // Coeficiente cognitivo
+        c2: settings.c2,                                         // Coeficiente social
+        population_size: settings.population_size,               // Tamanho da população
+        t_max: settings.t_max.unwrap_or(padrao.t_max),           // Número máximo de iterações
+        ..padrao
     };
 
+    // O pso_rs aceita apenas ponteiros de função, então a tolerância vem do caso ativo
     fn terminate(f_best: f64) -> bool {
-        f_best < 1e-4 // Critério de parada: o método para se função objetivo é menor que 1e-5
+        f_best < caso_ativo().pso.tolerancia // Critério de parada: o método para se função objetivo é menor que a tolerância
     }
 
     (config, Some(terminate))
@@ -0,0 +1,876 @@
+//! Importação de dados do PSS/E (RAW e DYR) para estudos multimáquinas.
+//!
+//! O arquivo RAW (revisão 33) fornece barras, cargas, derivações, geradores,
+//! linhas e transformadores de dois e três enrolamentos; o arquivo DYR fornece
+//! os dados dinâmicos dos geradores pelos registros `GENCLS` e `GENROU`, dos
+//! quais o modelo clássico usa `H`, `D` e `x'd`. O ponto de operação é obtido
+//! por um fluxo de potência a partir das tensões do RAW, e o resultado é o
+//! [`MultiMachineSystem`] consumido por [`crate::multimaquina`].
+//!
+//! Os ramos ficam na ordem do RAW: linhas seguidas dos transformadores, sendo
+//! que cada transformador de três enrolamentos gera três ramos ligados a uma
+//! barra fictícia no ponto estrela (numerada após a maior barra do caso).
+//! Elos de corrente contínua, FACTS, equipamentos GNE e máquinas de indução
+//! são rejeitados; os demais registros do DYR (excitação, reguladores de
+//! velocidade, estabilizadores etc.) são ignorados.
+
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use crate::fluxo_potencia::{self, TipoBarra};
+use crate::multimaquina::{Barra, Gerador, MultiMachineSystem, Ramo};
+
+// Revisão do formato RAW suportada
+const REVISAO: usize = 33;
+
+// Seções do RAW após os transformadores, na ordem do arquivo, e se um
+// registro nelas é aceito (sem efeito na simulação) ou rejeitado
+const SECOES_FINAIS: [(&str, Secao); 13] = [
+    ("áreas", Secao::Ignorada),
+    ("elos CC de dois terminais", Secao::Rejeitada),
+    ("elos CC VSC", Secao::Rejeitada),
+    ("tabelas de correção de impedância", Secao::Ignorada),
+    ("elos CC multiterminais", Secao::Rejeitada),
+    ("linhas multisseção", Secao::Ignorada),
+    ("zonas", Secao::Ignorada),
+    ("intercâmbios entre áreas", Secao::Ignorada),
+    ("proprietários", Secao::Ignorada),
+    ("FACTS", Secao::Rejeitada),
+    ("derivações chaveadas", Secao::DerivacaoChaveada),
+    ("equipamentos GNE", Secao::Rejeitada),
+    ("máquinas de indução", Secao::Rejeitada),
+];
+
+#[derive(Clone, Copy)]
+enum Secao {
+    Ignorada,
+    Rejeitada,
+    DerivacaoChaveada,
+}
+
+// Registro de uma seção do RAW: número da primeira linha e campos de cada
+// linha do registro
+struct Registro {
+    linha: usize,
+    campos: Vec<Vec<String>>,
+}
+
+// Gerador em serviço do RAW, identificado pela barra e pelo ID da máquina
+struct MaquinaRaw {
+    id: String,
+    // Razão MBASE/SBASE
+    razao_base: f64,
+    // Reatância da fonte ZX na base da máquina (x'd do GENCLS)
+    zx: f64,
+}
+
+// Dados do modelo clássico lidos de um registro GENCLS ou GENROU, na base da
+// máquina; `xd_transitoria` ausente no GENCLS, que usa ZX do RAW
+struct DinamicaDyr {
+    modelo: String,
+    h: f64,
+    d: f64,
+    xd_transitoria: Option<f64>,
+}
+
+/// Importa os arquivos RAW e DYR do PSS/E, resolve o fluxo de potência e
+/// retorna o sistema multimáquinas sem eventos, com as grandezas na base
+/// `SBASE` do caso.
+///
+/// Geradores, cargas e derivações fora de serviço são descartados e ramos
+/// fora de serviço permanecem abertos. Cargas de corrente constante entram
+/// como potência constante na tensão do RAW, e cargas de admitância constante
+/// como derivações. O amortecimento `D` do DYR (p.u. de potência por p.u. de
+/// velocidade) é convertido para a frequência `BASFRQ` do caso.
+pub fn importar_psse(caminho_raw: &Path, caminho_dyr: &Path) -> Result<MultiMachineSystem> {
+    let erro_caso = |caminho: &Path| {
+        let caminho = caminho.to_path_buf();
+        move |mensagem: String| Error::Caso { caminho, mensagem }
+    };
+    let conteudo = fs::read_to_string(caminho_raw).map_err(Error::io(caminho_raw))?;
+    let (mut sistema, tipos, maquinas, frequencia) =
+        ler_raw(&conteudo).map_err(erro_caso(caminho_raw))?;
+
+    let conteudo = fs::read_to_string(caminho_dyr).map_err(Error::io(caminho_dyr))?;
+    let dinamica = ler_dyr(&conteudo).map_err(erro_caso(caminho_dyr))?;
+    aplicar_dinamica(&mut sistema, &maquinas, &dinamica, frequencia)
+        .map_err(erro_caso(caminho_dyr))?;
+
+    fluxo_potencia::resolver(&mut sistema, &tipos)?;
+    Ok(sistema)
+}
+
+// Converte o conteúdo do RAW na rede com o despacho (geradores ainda sem dados
+// dinâmicos), nos tipos das barras, nos geradores em serviço e na frequência
+// base
+#[allow(clippy::type_complexity)]
+fn ler_raw(
+    conteudo: &str,
+) -> std::result::Result<(MultiMachineSystem, Vec<TipoBarra>, Vec<MaquinaRaw>, f64), String> {
+    let mut leitor = Leitor {
+        linhas: conteudo.lines().enumerate(),
+    };
+    let (n, cabecalho) = leitor.proxima("cabeçalho")?;
+    let cabecalho = campos(cabecalho);
+    let em_linha = |linha: usize| move |mensagem: String| format!("linha {}: {}", linha, mensagem);
+    if numero(&cabecalho, 0, 0.0).map_err(em_linha(n))? != 0.0 {
+        return Err("caso de alteração (IC = 1) não suportado".to_string());
+    }
+    let base = numero(&cabecalho, 1, 100.0).map_err(em_linha(n))?;
+    let revisao = numero(&cabecalho, 2, 0.0).map_err(em_linha(n))?;
+    if revisao != REVISAO as f64 {
+        return Err(format!(
+            "revisão {} do RAW não suportada (apenas {})",
+            revisao, REVISAO
+        ));
+    }
+    let frequencia = match numero(&cabecalho, 5, 60.0).map_err(em_linha(n))? {
+        f if f > 0.0 => f,
+        _ => 60.0,
+    };
+    if base <= 0.0 {
+        return Err(format!("linha {}: SBASE deve ser positivo", n));
+    }
+    // Duas linhas de título
+    leitor.proxima("título")?;
+    leitor.proxima("título")?;
+
+    let mut barras = Vec::new();
+    let mut tipos = Vec::new();
+    let mut tensoes_base = Vec::new();
+    for registro in leitor.secao("barras", |_| 1)? {
+        let c = &registro.campos[0];
+        let valor = |i, padrao| numero(c, i, padrao).map_err(em_linha(registro.linha));
+        let id = numero_barra(valor(0, 0.0)?).map_err(em_linha(registro.linha))?;
+        tipos.push(match valor(3, 1.0)? {
+            1.0 => TipoBarra::Pq,
+            2.0 => TipoBarra::Pv,
+            3.0 => TipoBarra::Referencia,
+            4.0 => return Err(format!("barra {} isolada (tipo 4) não suportada", id)),
+            t => return Err(format!("barra {} com tipo {} inválido", id, t)),
+        });
+        tensoes_base.push(valor(2, 0.0)?);
+        barras.push(Barra {
+            id,
+            v: valor(7, 1.0)?,
+            theta: valor(8, 0.0)?,
+            pl: 0.0,
+            ql: 0.0,
+            gs: 0.0,
+            bs: 0.0,
+        });
+    }
+    let indice = |barras: &[Barra], id: f64, linha: usize| {
+        let id = numero_barra(id.abs()).map_err(em_linha(linha))?;
+        barras
+            .iter()
+            .position(|b| b.id == id)
+            .ok_or_else(|| format!("linha {}: barra {} inexistente", linha, id))
+    };
+
+    for registro in leitor.secao("cargas", |_| 1)? {
+        let c = &registro.campos[0];
+        let valor = |i, padrao| numero(c, i, padrao).map_err(em_linha(registro.linha));
+        if valor(2, 1.0)? == 0.0 {
+            continue;
+        }
+        let k = indice(&barras, valor(0, 0.0)?, registro.linha)?;
+        let barra = &mut barras[k];
+        barra.pl += (valor(5, 0.0)? + valor(7, 0.0)? * barra.v) / base;
+        barra.ql += (valor(6, 0.0)? + valor(8, 0.0)? * barra.v) / base;
+        barra.gs += valor(9, 0.0)? / base;
+        // YQ é positivo para carga indutiva
+        barra.bs -= valor(10, 0.0)? / base;
+    }
+
+    for registro in leitor.secao("derivações fixas", |_| 1)? {
+        let c = &registro.campos[0];
+        let valor = |i, padrao| numero(c, i, padrao).map_err(em_linha(registro.linha));
+        if valor(2, 1.0)? == 0.0 {
+            continue;
+        }
+        let k = indice(&barras, valor(0, 0.0)?, registro.linha)?;
+        barras[k].gs += valor(3, 0.0)? / base;
+        barras[k].bs += valor(4, 0.0)? / base;
+    }
+
+    let mut geradores = Vec::new();
+    let mut maquinas = Vec::new();
+    for registro in leitor.secao("geradores", |_| 1)? {
+        let c = &registro.campos[0];
+        let valor = |i, padrao| numero(c, i, padrao).map_err(em_linha(registro.linha));
+        if valor(14, 1.0)? == 0.0 {
+            continue;
+        }
+        let k = indice(&barras, valor(0, 0.0)?, registro.linha)?;
+        if valor(11, 0.0)? != 0.0 || valor(12, 0.0)? != 0.0 {
+            return Err(format!(
+                "linha {}: transformador elevador no registro do gerador (RT, XT) não suportado",
+                registro.linha
+            ));
+        }
+        // Tensão especificada das barras PV e de referência
+        if tipos[k] != TipoBarra::Pq {
+            barras[k].v = valor(6, 1.0)?;
+        }
+        let m_base = valor(8, base)?;
+        geradores.push(Gerador {
+            barra: barras[k].id,
+            p: valor(2, 0.0)? / base,
+            q: valor(3, 0.0)? / base,
+            h: 0.0,
+            d: 0.0,
+            xd_transitoria: 0.0,
+        });
+        maquinas.push(MaquinaRaw {
+            id: texto(c, 1, "1"),
+            razao_base: if m_base > 0.0 { m_base / base } else { 1.0 },
+            zx: valor(10, 1.0)?,
+        });
+    }
+
+    let mut ramos = Vec::new();
+    for registro in leitor.secao("linhas", |_| 1)? {
+        let c = &registro.campos[0];
+        let valor = |i, padrao| numero(c, i, padrao).map_err(em_linha(registro.linha));
+        let de = indice(&barras, valor(0, 0.0)?, registro.linha)?;
+        let para = indice(&barras, valor(1, 0.0)?, registro.linha)?;
+        let em_servico = valor(13, 1.0)? != 0.0;
+        // Derivações nas extremidades da linha
+        if em_servico {
+            barras[de].gs += valor(9, 0.0)?;
+            barras[de].bs += valor(10, 0.0)?;
+            barras[para].gs += valor(11, 0.0)?;
+            barras[para].bs += valor(12, 0.0)?;
+        }
+        ramos.push(Ramo {
+            de: barras[de].id,
+            para: barras[para].id,
+            r: valor(3, 0.0)?,
+            x: valor(4, 0.0)?,
+            b: valor(5, 0.0)?,
+            tap: 1.0,
+            defasagem: 0.0,
+            em_servico,
+        });
+    }
+
+    // Transformadores: quatro linhas (dois enrolamentos) ou cinco (três)
+    let transformadores = leitor.secao("transformadores", |c| {
+        if numero(c, 2, 0.0).unwrap_or(0.0) == 0.0 {
+            4
+        } else {
+            5
+        }
+    })?;
+    let mut proxima_barra = barras.iter().map(|b| b.id).max().unwrap_or(0) + 1;
+    for registro in transformadores {
+        let linha = registro.linha;
+        let c = &registro.campos;
+        let valor = |l: usize, i, padrao| numero(&c[l], i, padrao).map_err(em_linha(linha + l));
+        let terminais = if valor(0, 2, 0.0)? == 0.0 { 2 } else { 3 };
+        let mut k = Vec::with_capacity(terminais);
+        for i in 0..terminais {
+            k.push(indice(&barras, valor(0, i, 0.0)?, linha)?);
+        }
+        let (cw, cz, cm) = (valor(0, 4, 1.0)?, valor(0, 5, 1.0)?, valor(0, 6, 1.0)?);
+        let status = valor(0, 11, 1.0)?;
+
+        // Relação de cada enrolamento em p.u. da tensão base da barra
+        let mut relacoes = Vec::with_capacity(terminais);
+        let mut angulos = Vec::with_capacity(terminais);
+        let mut fatores_tensao = Vec::with_capacity(terminais);
+        for (w, &kw) in k.iter().enumerate() {
+            let windv = valor(2 + w, 0, 1.0)?;
+            let nomv = valor(2 + w, 1, 0.0)?;
+            let kv = tensoes_base[kw];
+            let por_base = |v: f64| {
+                if kv > 0.0 {
+                    Ok(v / kv)
+                } else {
+                    Err(format!(
+                        "linha {}: barra {} sem tensão base (BASKV)",
+                        linha, barras[kw].id
+                    ))
+                }
+            };
+            relacoes.push(match cw {
+                1.0 => windv,
+                2.0 => por_base(windv)?,
+                3.0 if nomv > 0.0 => windv * por_base(nomv)?,
+                3.0 => windv,
+                _ => return Err(format!("linha {}: CW = {} inválido", linha, cw)),
+            });
+            angulos.push(valor(2 + w, 2, 0.0)?);
+            fatores_tensao.push(if cz != 1.0 && nomv > 0.0 {
+                por_base(nomv)?.powi(2)
+            } else {
+                1.0
+            });
+        }
+
+        // Impedâncias entre enrolamentos (1-2, 2-3, 3-1) na base do sistema
+        let mut impedancias = Vec::with_capacity(terminais);
+        let n_pares = if terminais == 2 { 1 } else { 3 };
+        for (par, fator_tensao) in fatores_tensao.iter().take(n_pares).enumerate() {
+            let r = valor(1, 3 * par, 0.0)?;
+            let x = valor(1, 3 * par + 1, 0.0)?;
+            let s_base = match valor(1, 3 * par + 2, base)? {
+                s if s > 0.0 => s,
+                _ => base,
+            };
+            let (r, x) = match cz {
+                1.0 | 2.0 => (r, x),
+                // R: perdas no cobre (W); X: módulo da impedância
+                3.0 => {
+                    let r = r / (1e6 * s_base);
+                    (r, (x * x - r * r).max(0.0).sqrt())
+                }
+                _ => return Err(format!("linha {}: CZ = {} inválido", linha, cz)),
+            };
+            let fator = if cz == 1.0 {
+                1.0
+            } else {
+                fator_tensao * base / s_base
+            };
+            impedancias.push((r * fator, x * fator));
+        }
+
+        // Ramo de magnetização no enrolamento 1
+        let (mag1, mag2) = (valor(0, 7, 0.0)?, valor(0, 8, 0.0)?);
+        let (g, b) = match cm {
+            1.0 => (mag1, mag2),
+            2.0 => {
+                let s_base = match valor(1, 2, base)? {
+                    s if s > 0.0 => s,
+                    _ => base,
+                };
+                let g = mag1 / (1e6 * s_base);
+                let b = -(mag2 * mag2 - g * g).max(0.0).sqrt();
+                (g * s_base / base, b * s_base / base)
+            }
+            _ => return Err(format!("linha {}: CM = {} inválido", linha, cm)),
+        };
+        if status != 0.0 {
+            barras[k[0]].gs += g;
+            barras[k[0]].bs += b;
+        }
+
+        if terminais == 2 {
+            let (r, x) = impedancias[0];
+            let t2 = relacoes[1];
+            ramos.push(Ramo {
+                de: barras[k[0]].id,
+                para: barras[k[1]].id,
+                r: r * t2 * t2,
+                x: x * t2 * t2,
+                b: 0.0,
+                tap: relacoes[0] / t2,
+                defasagem: angulos[0],
+                em_servico: status != 0.0,
+            });
+        } else {
+            // Estrela equivalente: Z1 = (Z12 + Z31 - Z23) / 2 etc.
+            let estrela = |a: usize, b: usize, c: usize| {
+                (
+                    (impedancias[a].0 + impedancias[c].0 - impedancias[b].0) / 2.0,
+                    (impedancias[a].1 + impedancias[c].1 - impedancias[b].1) / 2.0,
+                )
+            };
+            let ramos_estrela = [estrela(0, 1, 2), estrela(1, 2, 0), estrela(2, 0, 1)];
+            let id_estrela = proxima_barra;
+            proxima_barra += 1;
+            barras.push(Barra {
+                id: id_estrela,
+                v: valor(1, 9, 1.0)?,
+                theta: valor(1, 10, 0.0)?,
+                pl: 0.0,
+                ql: 0.0,
+                gs: 0.0,
+                bs: 0.0,
+            });
+            tipos.push(TipoBarra::Pq);
+            tensoes_base.push(0.0);
+            for (w, (r, x)) in ramos_estrela.into_iter().enumerate() {
+                // STAT: 0 todos fora, 1 todos em serviço, 2/3/4 enrolamento
+                // 2/3/1 fora
+                let fora = match status {
+                    0.0 => true,
+                    2.0 => w == 1,
+                    3.0 => w == 2,
+                    4.0 => w == 0,
+                    _ => false,
+                };
+                ramos.push(Ramo {
+                    de: barras[k[w]].id,
+                    para: id_estrela,
+                    r,
+                    x,
+                    b: 0.0,
+                    tap: relacoes[w],
+                    defasagem: angulos[w],
+                    em_servico: !fora,
+                });
+            }
+        }
+    }
+
+    for (nome, secao) in SECOES_FINAIS {
+        if leitor.fim() {
+            break;
+        }
+        let registros = leitor.secao(nome, |_| 1)?;
+        match secao {
+            Secao::Ignorada => {}
+            Secao::Rejeitada => {
+                if let Some(registro) = registros.first() {
+                    return Err(format!("linha {}: {} não suportados", registro.linha, nome));
+                }
+            }
+            // Susceptância inicial BINIT como derivação fixa
+            Secao::DerivacaoChaveada => {
+                for registro in registros {
+                    let c = &registro.campos[0];
+                    let valor = |i, padrao| numero(c, i, padrao).map_err(em_linha(registro.linha));
+                    if valor(3, 1.0)? == 0.0 {
+                        continue;
+                    }
+                    let k = indice(&barras, valor(0, 0.0)?, registro.linha)?;
+                    barras[k].bs += valor(9, 0.0)? / base;
+                }
+            }
+        }
+    }
+
+    for (k, barra) in barras.iter().enumerate() {
+        let tem_gerador = geradores.iter().any(|g| g.barra == barra.id);
+        match tipos[k] {
+            // Sem gerador em serviço, a barra PV passa a PQ
+            TipoBarra::Pv if !tem_gerador => tipos[k] = TipoBarra::Pq,
+            TipoBarra::Referencia if !tem_gerador => {
+                return Err(format!(
+                    "barra de referência {} sem gerador em serviço",
+                    barra.id
+                ));
+            }
+            _ => {}
+        }
+    }
+    match tipos
+        .iter()
+        .filter(|&&t| t == TipoBarra::Referencia)
+        .count()
+    {
+        1 => {}
+        0 => return Err("caso sem barra de referência (tipo 3)".to_string()),
+        n => {
+            return Err(format!(
+                "{} barras de referência; apenas uma é suportada",
+                n
+            ));
+        }
+    }
+
+    let sistema = MultiMachineSystem {
+        tab: 0.0,
+        tr: 0.0,
+        otimizar: false,
+        barras,
+        geradores,
+        ramos,
+        eventos: Vec::new(),
+    };
+    Ok((sistema, tipos, maquinas, frequencia))
+}
+
+// Lê os registros GENCLS e GENROU do DYR, indexados pela barra e pelo ID da
+// máquina
+fn ler_dyr(conteudo: &str) -> std::result::Result<HashMap<(usize, String), DinamicaDyr>, String> {
+    // Registros terminados por '/', possivelmente em várias linhas; o resto
+    // da linha após a '/' é comentário
+    let mut registros = Vec::new();
+    let mut atual = String::new();
+    for linha in conteudo.lines() {
+        match linha.split_once('/') {
+            Some((fim, _)) => {
+                atual += fim;
+                registros.push(std::mem::take(&mut atual));
+            }
+            None => {
+                atual += linha;
+                atual.push(' ');
+            }
+        }
+    }
+
+    let mut dinamica = HashMap::new();
+    for registro in registros {
+        let c: Vec<String> = registro
+            .split(|ch: char| ch.is_whitespace() || ch == ',')
+            .filter(|t| !t.is_empty())
+            .map(|t| t.trim_matches('\'').trim().to_string())
+            .collect();
+        if c.len() < 2 {
+            continue;
+        }
+        let modelo = c[1].to_uppercase();
+        let parametros = match modelo.as_str() {
+            "GENCLS" => 2,
+            "GENROU" => 14,
+            _ => continue,
+        };
+        let barra = numero(&c, 0, 0.0).and_then(numero_barra)?;
+        let id = texto(&c, 2, "1");
+        if c.len() < 3 + parametros {
+            return Err(format!(
+                "{} da barra {} (máquina {}) com {} parâmetro(s); são necessários {}",
+                modelo,
+                barra,
+                id,
+                c.len().saturating_sub(3),
+                parametros
+            ));
+        }
+        let p = |i: usize| numero(&c, 3 + i, 0.0);
+        let dados = match modelo.as_str() {
+            "GENCLS" => DinamicaDyr {
+                modelo,
+                h: p(0)?,
+                d: p(1)?,
+                xd_transitoria: None,
+            },
+            // T'do T''do T'qo T''qo H D Xd Xq X'd X'q X''d Xl S(1.0) S(1.2)
+            _ => DinamicaDyr {
+                modelo,
+                h: p(4)?,
+                d: p(5)?,
+                xd_transitoria: Some(p(8)?),
+            },
+        };
+        if dinamica.insert((barra, id.clone()), dados).is_some() {
+            return Err(format!(
+                "mais de um modelo de gerador para a barra {} (máquina {})",
+                barra, id
+            ));
+        }
+    }
+    Ok(dinamica)
+}
+
+// Associa os dados dinâmicos aos geradores, convertendo da base da máquina
+// para a base do sistema e o amortecimento para p.u. por rad/s
+fn aplicar_dinamica(
+    sistema: &mut MultiMachineSystem,
+    maquinas: &[MaquinaRaw],
+    dinamica: &HashMap<(usize, String), DinamicaDyr>,
+    frequencia: f64,
+) -> std::result::Result<(), String> {
+    let omega_s = 2.0 * PI * frequencia;
+    for (gerador, maquina) in sistema.geradores.iter_mut().zip(maquinas) {
+        let dados = dinamica
+            .get(&(gerador.barra, maquina.id.clone()))
+            .ok_or_else(|| {
+                format!(
+                    "sem registro GENCLS/GENROU para o gerador da barra {} (máquina {})",
+                    gerador.barra, maquina.id
+                )
+            })?;
+        let xd_transitoria = dados.xd_transitoria.unwrap_or(maquina.zx);
+        if xd_transitoria <= 0.0 {
+            return Err(format!(
+                "{} da barra {} (máquina {}) sem reatância transitória positiva",
+                dados.modelo, gerador.barra, maquina.id
+            ));
+        }
+        gerador.h = dados.h * maquina.razao_base;
+        gerador.d = dados.d * maquina.razao_base / omega_s;
+        gerador.xd_transitoria = xd_transitoria / maquina.razao_base;
+    }
+    Ok(())
+}
+
+// Linhas do RAW numeradas a partir de 1
+struct Leitor<'a> {
+    linhas: std::iter::Enumerate<std::str::Lines<'a>>,
+}
+
+impl<'a> Leitor<'a> {
+    fn proxima(&mut self, secao: &str) -> std::result::Result<(usize, &'a str), String> {
+        self.linhas
+            .next()
+            .map(|(i, linha)| (i + 1, linha))
+            .ok_or_else(|| format!("arquivo termina antes do fim da seção de {}", secao))
+    }
+
+    // Fim dos dados: fim do arquivo ou registro `Q`
+    fn fim(&self) -> bool {
+        let mut resto = self.linhas.clone();
+        resto
+            .find(|(_, linha)| !linha.trim().is_empty())
+            .is_none_or(|(_, linha)| linha.trim_start().starts_with('Q'))
+    }
+
+    // Registros até a linha `0 / END OF ...`; `linhas_por_registro` recebe os
+    // campos da primeira linha
+    fn secao(
+        &mut self,
+        nome: &str,
+        linhas_por_registro: impl Fn(&[String]) -> usize,
+    ) -> std::result::Result<Vec<Registro>, String> {
+        let mut registros = Vec::new();
+        loop {
+            let (n, linha) = self.proxima(nome)?;
+            let primeira = campos(linha);
+            match primeira.first().map(String::as_str) {
+                Some("0") => return Ok(registros),
+                Some(q) if q.starts_with('Q') => {
+                    return Err(format!("linha {}: fim dos dados na seção de {}", n, nome));
+                }
+                _ => {}
+            }
+            let mut registro = Registro {
+                linha: n,
+                campos: vec![primeira],
+            };
+            for _ in 1..linhas_por_registro(&registro.campos[0]) {
+                let (_, linha) = self.proxima(nome)?;
+                registro.campos.push(campos(linha));
+            }
+            registros.push(registro);
+        }
+    }
+}
+
+// Campos de uma linha separados por vírgulas (ou espaços, se não houver
+// vírgulas), sem o comentário após '/' e sem as aspas
+fn campos(linha: &str) -> Vec<String> {
+    let mut resultado = Vec::new();
+    let mut atual = String::new();
+    let mut entre_aspas = false;
+    let mut tem_virgula = false;
+    for ch in linha.chars() {
+        match ch {
+            '\'' | '"' => entre_aspas = !entre_aspas,
+            '/' if !entre_aspas => break,
+            ',' if !entre_aspas => {
+                resultado.push(atual.trim().to_string());
+                atual.clear();
+                tem_virgula = true;
+            }
+            _ => atual.push(ch),
+        }
+    }
+    resultado.push(atual.trim().to_string());
+    if !tem_virgula {
+        return resultado[0]
+            .split_whitespace()
+            .map(str::to_string)
+            .collect();
+    }
+    resultado
+}
+
+// Campo numérico `i`; ausente ou vazio vale `padrao`
+fn numero(campos: &[String], i: usize, padrao: f64) -> std::result::Result<f64, String> {
+    match campos.get(i).map(String::as_str) {
+        None | Some("") => Ok(padrao),
+        Some(texto) => texto
+            .parse()
+            .map_err(|_| format!("valor numérico inválido: '{}'", texto)),
+    }
+}
+
+// Campo de texto `i`; ausente ou vazio vale `padrao`
+fn texto(campos: &[String], i: usize, padrao: &str) -> String {
+    match campos.get(i).map(|t| t.trim()) {
+        None | Some("") => padrao.to_string(),
+        Some(t) => t.to_string(),
+    }
+}
+
+// Número de barra (inteiro positivo)
+fn numero_barra(valor: f64) -> std::result::Result<usize, String> {
+    if valor >= 1.0 && valor.fract() == 0.0 {
+        Ok(valor as usize)
+    } else {
+        Err(format!("número de barra inválido: {}", valor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // SBASE = 100 MVA. Barras de 230, 115 e 13,8 kV ligadas por um
+    // transformador de dois enrolamentos (CW = CZ = CM = 2) e um de três
+    // (CW = 1, CZ = 3, CM = 1)
+    const RAW: &str = "0, 100.00, 33, 0, 1, 60.00     / PSS(R)E-33.0
+CASO DE TESTE
+QUATRO BARRAS
+1,'A', 230.0, 3, 1, 1, 1, 1.02, 0.0, 1.1, 0.9, 1.1, 0.9
+2,'B', 115.0, 1, 1, 1, 1, 0.98, -3.0, 1.1, 0.9, 1.1, 0.9
+3,'C', 13.8, 2, 1, 1, 1, 1.0, 5.0, 1.1, 0.9, 1.1, 0.9
+4,'D', 13.8, 1, 1, 1, 1, 1.0, 0.0, 1.1, 0.9, 1.1, 0.9
+0 / END OF BUS DATA, BEGIN LOAD DATA
+2,'1', 1, 1, 1, 50.0, 20.0, 10.0, 0.0, 5.0, 8.0, 1, 1, 0
+4,'1', 0, 1, 1, 30.0, 10.0, 0.0, 0.0, 0.0, 0.0, 1, 1, 0
+0 / END OF LOAD DATA, BEGIN FIXED SHUNT DATA
+4,'1', 1, 0.0, 20.0
+0 / END OF FIXED SHUNT DATA, BEGIN GENERATOR DATA
+1,'1', 100.0, 10.0, 999.0, -999.0, 1.03, 0, 200.0, 0.0, 0.25, 0.0, 0.0, 1.0, 1, 100.0, 999.0, -999.0
+3,'1', 80.0, 0.0, 999.0, -999.0, 1.01, 0, 100.0, 0.0, 0.3, 0.0, 0.0, 1.0, 1, 100.0, 999.0, -999.0
+3,'2', 20.0, 0.0, 999.0, -999.0, 1.01, 0, 100.0, 0.0, 0.3, 0.0, 0.0, 1.0, 0, 100.0, 999.0, -999.0
+0 / END OF GENERATOR DATA, BEGIN BRANCH DATA
+0 / END OF BRANCH DATA, BEGIN TRANSFORMER DATA
+1, 2, 0,'1', 2, 2, 2, 50000.0, 0.02, 2,'T1', 1, 1, 1.0
+0.0, 0.1, 200.0
+241.5, 230.0, 0.0, 250.0, 250.0, 250.0, 0, 0, 1.1, 0.9, 1.1, 0.9, 33, 0, 0.0, 0.0, 0.0
+115.0, 115.0
+2, 3, 4,'1', 1, 3, 1, 0.0, -0.01, 2,'T2', 1, 1, 1.0
+30000.0, 0.1, 100.0, 0.0, 0.2, 50.0, 0.0, 0.35, 100.0, 1.0, 0.0
+1.02, 0.0, 0.0, 250.0, 250.0, 250.0, 0, 0, 1.1, 0.9, 1.1, 0.9, 33, 0, 0.0, 0.0, 0.0
+1.0, 0.0, 30.0, 250.0, 250.0, 250.0, 0, 0, 1.1, 0.9, 1.1, 0.9, 33, 0, 0.0, 0.0, 0.0
+0.98, 0.0, 0.0, 250.0, 250.0, 250.0, 0, 0, 1.1, 0.9, 1.1, 0.9, 33, 0, 0.0, 0.0, 0.0
+0 / END OF TRANSFORMER DATA, BEGIN AREA DATA
+Q
+";
+
+    const DYR: &str = "1 'GENROU' 1 6.0 0.05 0.2 0.05 4.0 0.0 1.8 1.7
+    0.3 0.55 0.25 0.15 0.1 0.3 /
+3 'GENCLS' 1 3.0 2.0 /
+3 'IEEET1' 1 0.0 50.0 0.06 1.0 -1.0 0.0 0.46 3.1 0.33 2.3 0.1 0.1 1.0 0.0 /
+";
+
+    fn perto(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-12
+    }
+
+    #[test]
+    fn barras_cargas_e_geradores_em_por_unidade() {
+        let (sistema, tipos, maquinas, frequencia) = ler_raw(RAW).unwrap();
+        assert_eq!(frequencia, 60.0);
+        assert_eq!(
+            tipos,
+            [
+                TipoBarra::Referencia,
+                TipoBarra::Pq,
+                TipoBarra::Pv,
+                TipoBarra::Pq,
+                TipoBarra::Pq
+            ]
+        );
+
+        // Corrente constante na tensão do RAW e admitância como derivação
+        let b2 = &sistema.barras[1];
+        assert!(perto(b2.pl, (50.0 + 10.0 * 0.98) / 100.0));
+        assert!(perto(b2.ql, 0.2));
+        assert!(perto(b2.gs, 0.05));
+        // YQ indutivo mais a magnetização do transformador de três enrolamentos
+        assert!(perto(b2.bs, -0.08 - 0.01));
+        // Carga fora de serviço descartada; derivação fixa mantida
+        let b4 = &sistema.barras[3];
+        assert_eq!((b4.pl, b4.ql, b4.bs), (0.0, 0.0, 0.2));
+
+        assert_eq!(sistema.geradores.len(), 2);
+        assert_eq!((sistema.geradores[0].p, sistema.geradores[0].q), (1.0, 0.1));
+        assert_eq!(sistema.barras[0].v, 1.03);
+        assert_eq!(maquinas[0].razao_base, 2.0);
+        assert_eq!(maquinas[1].zx, 0.3);
+    }
+
+    #[test]
+    fn transformador_de_dois_enrolamentos() {
+        let (sistema, _, _, _) = ler_raw(RAW).unwrap();
+        let ramo = &sistema.ramos[0];
+        assert_eq!((ramo.de, ramo.para), (1, 2));
+        // CW = 2: 241,5 kV sobre 230 kV
+        assert!(perto(ramo.tap, 1.05));
+        // CZ = 2: X na base de 200 MVA levado a 100 MVA
+        assert!(perto(ramo.x, 0.05) && ramo.r == 0.0);
+
+        // CM = 2: perdas a vazio de 50 kW e corrente de excitação de 0,02 p.u.
+        // em 200 MVA, no enrolamento 1
+        let g = 50_000.0 / (1e6 * 200.0);
+        let b1 = &sistema.barras[0];
+        assert!(perto(b1.gs, g * 2.0));
+        assert!(perto(b1.bs, -(0.02_f64.powi(2) - g * g).sqrt() * 2.0));
+    }
+
+    #[test]
+    fn transformador_de_tres_enrolamentos_em_estrela() {
+        let (sistema, tipos, _, _) = ler_raw(RAW).unwrap();
+        // Barra fictícia do ponto estrela após a maior barra do caso
+        let estrela = &sistema.barras[4];
+        assert_eq!((estrela.id, estrela.v, estrela.theta), (5, 1.0, 0.0));
+        assert_eq!(tipos[4], TipoBarra::Pq);
+
+        // CZ = 3: R12 de 30 kW de perdas e |Z| nas bases dos pares
+        let r12 = 30_000.0 / (1e6 * 100.0);
+        let z12 = (r12, (0.1_f64.powi(2) - r12 * r12).sqrt());
+        let z23 = (0.0, 0.2 * 100.0 / 50.0);
+        let z31 = (0.0, 0.35);
+        let esperados = [
+            (
+                2,
+                1.02,
+                0.0,
+                (z12.0 + z31.0 - z23.0) / 2.0,
+                (z12.1 + z31.1 - z23.1) / 2.0,
+            ),
+            (
+                3,
+                1.0,
+                30.0,
+                (z12.0 + z23.0 - z31.0) / 2.0,
+                (z12.1 + z23.1 - z31.1) / 2.0,
+            ),
+            (
+                4,
+                0.98,
+                0.0,
+                (z23.0 + z31.0 - z12.0) / 2.0,
+                (z23.1 + z31.1 - z12.1) / 2.0,
+            ),
+        ];
+        for (ramo, (de, tap, defasagem, r, x)) in sistema.ramos[1..].iter().zip(esperados) {
+            assert_eq!((ramo.de, ramo.para), (de, 5));
+            assert_eq!((ramo.tap, ramo.defasagem), (tap, defasagem));
+            assert!(perto(ramo.r, r) && perto(ramo.x, x), "{:?}", ramo);
+            assert!(ramo.em_servico);
+        }
+    }
+
+    #[test]
+    fn dinamica_do_dyr_na_base_do_sistema() {
+        let (mut sistema, _, maquinas, frequencia) = ler_raw(RAW).unwrap();
+        let dinamica = ler_dyr(DYR).unwrap();
+        assert_eq!(dinamica.len(), 2);
+        aplicar_dinamica(&mut sistema, &maquinas, &dinamica, frequencia).unwrap();
+
+        // GENROU em MBASE = 200 MVA: H = 4 s e X'd = 0,3 p.u.
+        let g1 = &sistema.geradores[0];
+        assert_eq!((g1.h, g1.d, g1.xd_transitoria), (8.0, 0.0, 0.15));
+        // GENCLS: x'd é o ZX do RAW e D passa a p.u. por rad/s
+        let g3 = &sistema.geradores[1];
+        assert_eq!((g3.h, g3.xd_transitoria), (3.0, 0.3));
+        assert!(perto(g3.d, 2.0 / (2.0 * PI * 60.0)));
+    }
+
+    #[test]
+    fn secoes_nao_suportadas_sao_rejeitadas() {
+        let raw = RAW.replace(
+            "Q\n",
+            "0 / END OF AREA DATA, BEGIN TWO-TERMINAL DC DATA\n\
+             1, 1, 100.0, 500.0\n",
+        );
+        let Err(erro) = ler_raw(&raw) else {
+            panic!("elo CC aceito");
+        };
+        assert!(erro.contains("elos CC"), "{}", erro);
+        let raw = RAW.replacen(", 33,", ", 32,", 1);
+        assert!(matches!(ler_raw(&raw), Err(erro) if erro.contains("revisão")));
+    }
+}
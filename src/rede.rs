@@ -0,0 +1,665 @@
+//! Rede máquina x barra infinita (SMIB) e cálculo das curvas Pe1, Pe2 e Pe3.
+//!
+//! O gerador (reatância transitória `x'd` atrás da FEM `E'`) se liga pelo
+//! transformador a uma barra de alta tensão, de onde linhas em paralelo seguem
+//! até a barra infinita. A falta ocorre em um ponto de uma das linhas; após a
+//! abertura, essa linha sai de operação. As reatâncias de transferência de cada
+//! configuração são obtidas por redução de Kron da rede de reatâncias.
+//!
+//! Faltas desequilibradas ([`TipoFalta`]) entram na rede de sequência positiva
+//! como uma derivação no ponto de falta, e a abertura de uma ou duas fases no
+//! tempo morto ([`TipoAbertura`]) como uma reatância em série com a linha em
+//! falta, ambas calculadas a partir das reatâncias de Thévenin das redes de
+//! sequência negativa e zero.
+
+use serde::{Deserialize, Serialize};
+
+use crate::curva::PowerAngleCurve;
+use crate::error::{Error, Result};
+use crate::falta::{TipoAbertura, TipoFalta};
+
+// Nós da rede: FEM interna do gerador, barra infinita, barra de alta tensão
+// (saída do transformador) e ponto de falta no interior da linha
+const NO_GERADOR: usize = 0;
+const NO_BARRA_INFINITA: usize = 1;
+const NO_ALTA_TENSAO: usize = 2;
+const NO_FALTA: usize = 3;
+const N_NOS: usize = 4;
+
+// Reatância usada no lugar de zero em ramos entre nós
+const REATANCIA_MIN: f64 = 1e-9;
+
+/// Descrição da rede SMIB (seção `[rede]` do arquivo de caso).
+///
+/// O carregamento pré-falta é a potência mecânica `pm` do sistema, com a
+/// tensão terminal `vt` ou a potência reativa `q` entregue à barra infinita.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SmibNetwork {
+    /// Reatância transitória de eixo direto do gerador `x'd` (p.u.).
+    pub xd_transitoria: f64,
+    /// Reatância do transformador elevador (p.u.).
+    pub xt: f64,
+    /// Reatâncias das linhas em paralelo (p.u.).
+    pub x_linhas: Vec<f64>,
+    /// Tensão da barra infinita (p.u.).
+    #[serde(default = "um")]
+    pub v_barra_infinita: f64,
+    /// Tensão terminal do gerador no carregamento pré-falta (p.u.).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vt: Option<f64>,
+    /// Potência reativa entregue à barra infinita no carregamento pré-falta (p.u.).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q: Option<f64>,
+    /// Índice (a partir de 0) da linha em falta.
+    #[serde(default)]
+    pub linha_falta: usize,
+    /// Posição da falta ao longo da linha, de 0 (barra de alta tensão do
+    /// gerador) a 1 (barra infinita).
+    #[serde(default)]
+    pub posicao_falta: f64,
+    /// Tipo da falta (padrão: trifásica).
+    #[serde(default)]
+    pub tipo_falta: TipoFalta,
+    /// Fases abertas na linha em falta após `tab` (padrão: tripolar).
+    #[serde(default)]
+    pub tipo_abertura: TipoAbertura,
+    /// Reatância de falta `Xf` (p.u.; padrão: curto franco).
+    #[serde(default)]
+    pub reatancia_falta: f64,
+    /// Reatância de sequência negativa do gerador (p.u.; padrão: `x'd`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x2_gerador: Option<f64>,
+    /// Reatância de sequência zero do transformador Δ-Yg (p.u.; padrão: `xt`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x0_transformador: Option<f64>,
+    /// Reatâncias de sequência zero das linhas (p.u.); obrigatórias em faltas
+    /// à terra e na abertura monopolar ou bipolar.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x0_linhas: Option<Vec<f64>>,
+    /// Reatância de sequência zero da barra infinita (p.u.; padrão: 0,
+    /// solidamente aterrada).
+    #[serde(default)]
+    pub x0_barra_infinita: f64,
+}
+
+/// Grandezas da rede SMIB calculadas por [`SmibNetwork::calcular`].
+#[derive(Debug, Clone, Copy)]
+pub struct ResultadoRede {
+    /// Módulo da FEM interna `E'` (p.u.).
+    pub e_interna: f64,
+    /// Ângulo de `E'` em relação à barra infinita no carregamento pré-falta (rad).
+    pub delta_0: f64,
+    /// Reatância de transferência pré-falta (p.u.).
+    pub x_pre_falta: f64,
+    /// Reatância de transferência durante a falta (p.u.).
+    pub x_falta: f64,
+    /// Reatância em derivação equivalente à falta no ponto de falta (p.u.;
+    /// ver [`TipoFalta::reatancia_derivacao`]).
+    pub x_derivacao: f64,
+    /// Reatância de Thévenin de sequência negativa no ponto de falta (p.u.),
+    /// se usada pelo tipo de falta.
+    pub x2_thevenin: Option<f64>,
+    /// Reatância de Thévenin de sequência zero no ponto de falta (p.u.), se
+    /// usada pelo tipo de falta.
+    pub x0_thevenin: Option<f64>,
+    /// Reatância de transferência após a abertura da linha em falta (p.u.).
+    pub x_pos_abertura: f64,
+    /// Reatância em série com a linha em falta devida às fases abertas (p.u.;
+    /// infinita na abertura tripolar, ver [`TipoAbertura::reatancia_serie`]).
+    pub x_serie_abertura: f64,
+    /// Rede pré-falta reduzida ao nó interno do gerador e à barra infinita.
+    pub reduzida_pre_falta: RedeReduzida,
+    /// Rede em falta reduzida ao nó interno do gerador e à barra infinita.
+    pub reduzida_falta: RedeReduzida,
+    /// Rede após a abertura reduzida ao nó interno do gerador e à barra
+    /// infinita.
+    pub reduzida_pos_abertura: RedeReduzida,
+    /// Curva pré-falta.
+    pub pe1: PowerAngleCurve,
+    /// Curva durante a falta.
+    pub pe2: PowerAngleCurve,
+    /// Curva após a abertura.
+    pub pe3: PowerAngleCurve,
+}
+
+/// Susceptâncias da rede de reatâncias reduzida ao nó interno do gerador (atrás
+/// de `x'd`) e à barra infinita, com a corrente injetada pelo gerador
+/// `I = -j·(b_propria·E + b_transferencia·V)`.
+///
+/// Em uma ligação série de reatância `X`, `b_propria = 1/X` e
+/// `b_transferencia = -1/X`; a derivação da falta aumenta `b_propria`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RedeReduzida {
+    /// Susceptância própria do nó interno (p.u.).
+    pub b_propria: f64,
+    /// Susceptância de transferência entre o nó interno e a barra infinita
+    /// (p.u.; `-1/X` da reatância de transferência).
+    pub b_transferencia: f64,
+}
+
+// Ramo de reatância `x` entre dois nós (`para = None`: terra)
+struct Ramo {
+    de: usize,
+    para: Option<usize>,
+    x: f64,
+}
+
+// Estado da linha em falta em cada configuração da rede; `Aberta` guarda a
+// reatância série das fases abertas (infinita: linha fora de operação)
+#[derive(Clone, Copy, PartialEq)]
+enum EstadoLinha {
+    Normal,
+    EmFalta,
+    Aberta(f64),
+}
+
+#[derive(Clone, Copy)]
+enum Sequencia {
+    Negativa,
+    Zero,
+}
+
+fn um() -> f64 {
+    1.0
+}
+
+impl SmibNetwork {
+    /// Calcula `E'` a partir do carregamento pré-falta `pm` e as curvas
+    /// pré-falta, durante a falta do tipo `tipo_falta` e após a abertura
+    /// `tipo_abertura` da linha em falta.
+    pub fn calcular(&self, pm: f64) -> Result<ResultadoRede> {
+        self.validar()?;
+        let v = self.v_barra_infinita;
+        let xd = self.xd_transitoria;
+
+        let (x2_thevenin, x0_thevenin, x_derivacao) = self.derivacao_falta();
+        let x_serie_abertura = self.serie_abertura();
+        let [reduzida_pre_falta, reduzida_falta, reduzida_pos_abertura] =
+            self.configuracoes(xd, x_derivacao, x_serie_abertura);
+        let x_pre_falta = inverter(-reduzida_pre_falta.b_transferencia);
+        let x_falta = inverter(-reduzida_falta.b_transferencia);
+        let x_pos_abertura = inverter(-reduzida_pos_abertura.b_transferencia);
+
+        // FEM interna E' = (re, im) com a barra infinita como referência
+        let (re, im) = match (self.vt, self.q) {
+            (Some(vt), None) => {
+                // Ângulo da tensão terminal pela potência transmitida do terminal à barra infinita
+                let x_externa =
+                    inverter(-self.reduzida(0.0, EstadoLinha::Normal, 0.0).b_transferencia);
+                let seno = pm * x_externa / (vt * v);
+                if seno.abs() > 1.0 {
+                    return Err(Error::Rede(format!(
+                        "Pm = {} p.u. não pode ser transmitida com Vt = {} p.u.",
+                        pm, vt
+                    )));
+                }
+                let theta = seno.asin();
+                // I = (Vt - V) / jXe; E' = Vt + j·x'd·I
+                let corrente_re = vt * theta.sin() / x_externa;
+                let corrente_im = -(vt * theta.cos() - v) / x_externa;
+                (
+                    vt * theta.cos() - xd * corrente_im,
+                    vt * theta.sin() + xd * corrente_re,
+                )
+            }
+            // I = (P - jQ) / V; E' = V + j·X·I
+            (None, Some(q)) => (v + x_pre_falta * q / v, x_pre_falta * pm / v),
+            _ => {
+                return Err(Error::Rede(
+                    "informe exatamente um entre `vt` e `q` para o carregamento pré-falta"
+                        .to_string(),
+                ));
+            }
+        };
+        let e_interna = re.hypot(im);
+
+        let curva = |x: f64| PowerAngleCurve::senoidal(e_interna * v / x);
+        Ok(ResultadoRede {
+            e_interna,
+            delta_0: im.atan2(re),
+            x_pre_falta,
+            x_falta,
+            x_derivacao,
+            x2_thevenin,
+            x0_thevenin,
+            x_pos_abertura,
+            x_serie_abertura,
+            reduzida_pre_falta,
+            reduzida_falta,
+            reduzida_pos_abertura,
+            pe1: curva(x_pre_falta),
+            pe2: curva(x_falta),
+            pe3: curva(x_pos_abertura),
+        })
+    }
+
+    /// Redes pré-falta, em falta e após a abertura reduzidas ao nó atrás da
+    /// reatância `x_gerador` (no lugar de `xd_transitoria`; por exemplo, `x''d`)
+    /// e à barra infinita.
+    pub fn reduzidas(&self, x_gerador: f64) -> Result<[RedeReduzida; 3]> {
+        self.validar()?;
+        let (_, _, x_derivacao) = self.derivacao_falta();
+        Ok(self.configuracoes(x_gerador, x_derivacao, self.serie_abertura()))
+    }
+
+    fn validar(&self) -> Result<()> {
+        let positivas = [self.xd_transitoria, self.xt, self.v_barra_infinita]
+            .iter()
+            .chain(&self.x_linhas)
+            .all(|&x| x > 0.0);
+        if !positivas {
+            return Err(Error::Rede(
+                "reatâncias e tensão da barra infinita devem ser positivas".to_string(),
+            ));
+        }
+        if self.linha_falta >= self.x_linhas.len() {
+            return Err(Error::Rede(format!(
+                "linha_falta = {} fora das {} linha(s) informada(s)",
+                self.linha_falta,
+                self.x_linhas.len()
+            )));
+        }
+        if !(0.0..=1.0).contains(&self.posicao_falta) {
+            return Err(Error::Rede(
+                "posicao_falta deve estar entre 0 e 1".to_string(),
+            ));
+        }
+        self.validar_sequencias()
+    }
+
+    fn validar_sequencias(&self) -> Result<()> {
+        let positivas = self
+            .x2_gerador
+            .iter()
+            .chain(&self.x0_transformador)
+            .chain(self.x0_linhas.iter().flatten())
+            .all(|&x| x > 0.0);
+        if !positivas || self.x0_barra_infinita < 0.0 || self.reatancia_falta < 0.0 {
+            return Err(Error::Rede(
+                "reatâncias de sequência devem ser positivas e reatancia_falta e \
+                 x0_barra_infinita não negativas"
+                    .to_string(),
+            ));
+        }
+        match &self.x0_linhas {
+            None if self.tipo_falta.usa_sequencia_zero() => Err(Error::Rede(format!(
+                "falta {} requer x0_linhas",
+                self.tipo_falta
+            ))),
+            None if self.tipo_abertura.usa_sequencias() => Err(Error::Rede(format!(
+                "abertura {} requer x0_linhas",
+                self.tipo_abertura
+            ))),
+            Some(x0) if x0.len() != self.x_linhas.len() => Err(Error::Rede(format!(
+                "x0_linhas tem {} valor(es) para {} linha(s)",
+                x0.len(),
+                self.x_linhas.len()
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    // Reatâncias de Thévenin de sequência negativa e zero (se usadas pelo tipo
+    // de falta) e derivação equivalente à falta
+    fn derivacao_falta(&self) -> (Option<f64>, Option<f64>, f64) {
+        let tipo = self.tipo_falta;
+        let x2_thevenin = tipo
+            .usa_sequencia_negativa()
+            .then(|| self.reatancia_thevenin(Sequencia::Negativa));
+        let x0_thevenin = tipo
+            .usa_sequencia_zero()
+            .then(|| self.reatancia_thevenin(Sequencia::Zero));
+        let x_derivacao = tipo.reatancia_derivacao(
+            x2_thevenin.unwrap_or(0.0),
+            x0_thevenin.unwrap_or(0.0),
+            self.reatancia_falta,
+        );
+        (x2_thevenin, x0_thevenin, x_derivacao)
+    }
+
+    // Reatância série das fases abertas da linha em falta (infinita na
+    // abertura tripolar)
+    fn serie_abertura(&self) -> f64 {
+        if self.tipo_abertura.usa_sequencias() {
+            self.tipo_abertura.reatancia_serie(
+                self.reatancia_abertura(Sequencia::Negativa),
+                self.reatancia_abertura(Sequencia::Zero),
+            )
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    // Redes pré-falta, em falta e após a abertura reduzidas à FEM atrás de `xd`
+    fn configuracoes(&self, xd: f64, x_derivacao: f64, x_serie_abertura: f64) -> [RedeReduzida; 3] {
+        [
+            self.reduzida(xd, EstadoLinha::Normal, 0.0),
+            self.reduzida(xd, EstadoLinha::EmFalta, x_derivacao),
+            self.reduzida(xd, EstadoLinha::Aberta(x_serie_abertura), 0.0),
+        ]
+    }
+
+    // Rede reduzida à FEM atrás de `xd` e à barra infinita, com a linha em
+    // falta no estado dado e a derivação `x_derivacao` no ponto de falta
+    fn reduzida(&self, xd: f64, estado: EstadoLinha, x_derivacao: f64) -> RedeReduzida {
+        let mut ramos = vec![Ramo {
+            de: NO_GERADOR,
+            para: Some(NO_ALTA_TENSAO),
+            x: xd + self.xt,
+        }];
+        let no_falta = self.ligar_linhas(&self.x_linhas, estado, &mut ramos);
+        if estado == EstadoLinha::EmFalta {
+            ramos.push(Ramo {
+                de: no_falta,
+                para: None,
+                x: x_derivacao,
+            });
+        }
+        let b = reduzir(N_NOS, &ramos, &[NO_GERADOR, NO_BARRA_INFINITA]);
+        RedeReduzida {
+            b_propria: b[NO_GERADOR][NO_GERADOR],
+            b_transferencia: b[NO_GERADOR][NO_BARRA_INFINITA],
+        }
+    }
+
+    // Reatância de Thévenin vista do ponto de falta na rede de sequência
+    // negativa ou zero
+    fn reatancia_thevenin(&self, sequencia: Sequencia) -> f64 {
+        let (ramos, no_falta) = self.rede_sequencia(sequencia, EstadoLinha::EmFalta);
+        let b = reduzir(N_NOS, &ramos, &[no_falta]);
+        inverter(b[no_falta][no_falta])
+    }
+
+    // Reatância de Thévenin entre os pontos abertos de uma fase da linha em
+    // falta na rede de sequência negativa ou zero: a própria linha em série com
+    // a reatância entre as suas barras terminais sem ela
+    fn reatancia_abertura(&self, sequencia: Sequencia) -> f64 {
+        let (ramos, _) = self.rede_sequencia(sequencia, EstadoLinha::Aberta(f64::INFINITY));
+        let b = reduzir(N_NOS, &ramos, &[NO_ALTA_TENSAO, NO_BARRA_INFINITA]);
+        let (b_aa, b_bb, b_ab) = (
+            b[NO_ALTA_TENSAO][NO_ALTA_TENSAO],
+            b[NO_BARRA_INFINITA][NO_BARRA_INFINITA],
+            b[NO_ALTA_TENSAO][NO_BARRA_INFINITA],
+        );
+        // Zaa + Zbb - 2·Zab pela inversa da matriz 2x2 reduzida
+        let det = b_aa * b_bb - b_ab * b_ab;
+        let x_entre_barras = if det <= 0.0 {
+            f64::INFINITY
+        } else {
+            (b_aa + b_bb + 2.0 * b_ab) / det
+        };
+        let x_linha = match sequencia {
+            Sequencia::Negativa => self.x_linhas[self.linha_falta],
+            Sequencia::Zero => self.x0_linhas.as_ref().unwrap_or(&self.x_linhas)[self.linha_falta],
+        };
+        x_linha + x_entre_barras
+    }
+
+    // Rede de sequência negativa ou zero (fontes curto-circuitadas) com a linha
+    // em falta no estado dado; retorna os ramos e o nó do ponto de falta. O
+    // transformador é Δ-Yg, com o lado aterrado voltado para a linha: na
+    // sequência zero o gerador fica isolado e o transformador é uma derivação
+    // na barra de alta tensão.
+    fn rede_sequencia(&self, sequencia: Sequencia, estado: EstadoLinha) -> (Vec<Ramo>, usize) {
+        let (x_alta_tensao, x_barra_infinita, x_linhas) = match sequencia {
+            Sequencia::Negativa => (
+                self.x2_gerador.unwrap_or(self.xd_transitoria) + self.xt,
+                0.0,
+                &self.x_linhas,
+            ),
+            Sequencia::Zero => (
+                self.x0_transformador.unwrap_or(self.xt),
+                self.x0_barra_infinita,
+                self.x0_linhas.as_ref().unwrap_or(&self.x_linhas),
+            ),
+        };
+        let mut ramos = vec![
+            Ramo {
+                de: NO_ALTA_TENSAO,
+                para: None,
+                x: x_alta_tensao,
+            },
+            Ramo {
+                de: NO_BARRA_INFINITA,
+                para: None,
+                x: x_barra_infinita,
+            },
+        ];
+        let no_falta = self.ligar_linhas(x_linhas, estado, &mut ramos);
+        (ramos, no_falta)
+    }
+
+    // Liga as linhas entre a barra de alta tensão e a barra infinita. Em falta,
+    // a linha em falta é dividida no ponto de falta, que é uma das barras nas
+    // extremidades ou o nó adicional; aberta, recebe a reatância série das
+    // fases abertas. Retorna o nó do ponto de falta
+    fn ligar_linhas(&self, x_linhas: &[f64], estado: EstadoLinha, ramos: &mut Vec<Ramo>) -> usize {
+        let posicao = self.posicao_falta;
+        let no_falta = if posicao == 0.0 {
+            NO_ALTA_TENSAO
+        } else if posicao == 1.0 {
+            NO_BARRA_INFINITA
+        } else {
+            NO_FALTA
+        };
+        for (i, &x) in x_linhas.iter().enumerate() {
+            let trechos = match estado {
+                EstadoLinha::Aberta(x_serie) if i == self.linha_falta => {
+                    vec![(NO_ALTA_TENSAO, NO_BARRA_INFINITA, x + x_serie)]
+                }
+                EstadoLinha::EmFalta if i == self.linha_falta && no_falta == NO_FALTA => vec![
+                    (NO_ALTA_TENSAO, NO_FALTA, posicao * x),
+                    (NO_FALTA, NO_BARRA_INFINITA, (1.0 - posicao) * x),
+                ],
+                _ => vec![(NO_ALTA_TENSAO, NO_BARRA_INFINITA, x)],
+            };
+            ramos.extend(trechos.into_iter().map(|(de, para, x)| Ramo {
+                de,
+                para: Some(para),
+                x,
+            }));
+        }
+        no_falta
+    }
+}
+
+// Matriz de susceptâncias da rede após eliminar por redução de Kron os nós fora
+// de `manter`; só as linhas e colunas de `manter` são significativas
+fn reduzir(n_nos: usize, ramos: &[Ramo], manter: &[usize]) -> Vec<Vec<f64>> {
+    // Nós eliminados em curto franco com a terra passam a ser a própria terra
+    let aterrados: Vec<usize> = ramos
+        .iter()
+        .filter(|r| r.para.is_none() && r.x == 0.0 && !manter.contains(&r.de))
+        .map(|r| r.de)
+        .collect();
+    let no = |n: usize| (!aterrados.contains(&n)).then_some(n);
+
+    let mut b = vec![vec![0.0; n_nos]; n_nos];
+    for ramo in ramos {
+        let y = 1.0 / ramo.x.max(REATANCIA_MIN);
+        match (no(ramo.de), ramo.para.and_then(no)) {
+            (Some(de), Some(para)) => {
+                b[de][de] += y;
+                b[para][para] += y;
+                b[de][para] -= y;
+                b[para][de] -= y;
+            }
+            (Some(de), None) | (None, Some(de)) => b[de][de] += y,
+            (None, None) => {}
+        }
+    }
+    for k in (0..n_nos).filter(|k| !manter.contains(k)) {
+        let pivo = b[k][k];
+        if pivo == 0.0 {
+            continue;
+        }
+        for i in 0..n_nos {
+            for j in 0..n_nos {
+                if i != k && j != k {
+                    b[i][j] -= b[i][k] * b[k][j] / pivo;
+                }
+            }
+        }
+    }
+    b
+}
+
+// Reatância correspondente a uma susceptância; infinita se não há ligação
+fn inverter(y: f64) -> f64 {
+    if y <= 0.0 { f64::INFINITY } else { 1.0 / y }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // x'd = 0,3, transformador 0,2 e duas linhas de 0,3 com falta no meio da
+    // primeira
+    fn rede() -> SmibNetwork {
+        toml::from_str(
+            "xd_transitoria = 0.3\n\
+             xt = 0.2\n\
+             x_linhas = [0.3, 0.3]\n\
+             q = 0.2\n\
+             posicao_falta = 0.5\n",
+        )
+        .unwrap()
+    }
+
+    fn perto(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-12
+    }
+
+    #[test]
+    fn reatancias_de_transferencia_da_falta_trifasica() {
+        let r = rede().calcular(1.0).unwrap();
+        // Linhas em paralelo
+        assert!(perto(r.x_pre_falta, 0.3 + 0.2 + 0.15));
+        // Estrela G-AT (0,5), AT-barra infinita (0,3) e AT-terra (0,15, meia
+        // linha até a falta) convertida em triângulo: 0,5 + 0,3 + 0,5·0,3/0,15
+        assert!(perto(r.x_falta, 1.8));
+        // Uma linha só
+        assert!(perto(r.x_pos_abertura, 0.3 + 0.2 + 0.3));
+        assert!(perto(r.reduzida_pre_falta.b_propria, 1.0 / 0.65));
+        assert!(perto(r.reduzida_pre_falta.b_transferencia, -1.0 / 0.65));
+    }
+
+    #[test]
+    fn fem_e_curvas_pelo_carregamento() {
+        let (pm, q): (f64, f64) = (1.0, 0.2);
+        let r = rede().calcular(pm).unwrap();
+        // E' = V + j·X·(P - jQ)/V com V = 1
+        let (re, im) = (1.0 + 0.65 * q, 0.65 * pm);
+        assert!(perto(r.e_interna, re.hypot(im)));
+        assert!(perto(r.delta_0, im.atan2(re)));
+        assert!(perto(r.pe1.pmax, r.e_interna / 0.65));
+        assert!(perto(r.pe2.pmax, r.e_interna / 1.8));
+        assert!(perto(r.pe3.pmax, r.e_interna / 0.8));
+        // O ângulo inicial é o equilíbrio estável de Pe1
+        assert!(perto(r.pe1.potencia(r.delta_0), pm));
+    }
+
+    // Rede do README com os dados de sequência zero (x0 = 0,9 nas linhas) e
+    // Xf = 0,05 p.u.
+    fn rede_desequilibrada(tipo_falta: TipoFalta) -> SmibNetwork {
+        let mut rede = rede();
+        rede.tipo_falta = tipo_falta;
+        rede.reatancia_falta = 0.05;
+        rede.x0_linhas = Some(vec![0.9, 0.9]);
+        rede
+    }
+
+    // Reatância de transferência com a derivação `delta` no meio da primeira
+    // linha: o ponto de falta (0,15 até cada barra e `delta` até a terra) e
+    // depois a barra de alta tensão são eliminados por conversão
+    // estrela-triângulo
+    fn x_falta_com_derivacao(delta: f64) -> f64 {
+        let x_at_bi = paralelo(0.3, (0.15 * 0.15 + 2.0 * 0.15 * delta) / delta);
+        let x_at_terra = (0.15 * 0.15 + 2.0 * 0.15 * delta) / 0.15;
+        (0.5 * x_at_bi + x_at_bi * x_at_terra + x_at_terra * 0.5) / x_at_terra
+    }
+
+    fn paralelo(a: f64, b: f64) -> f64 {
+        a * b / (a + b)
+    }
+
+    #[test]
+    fn derivacao_das_faltas_desequilibradas() {
+        // Sequência negativa vista do ponto de falta: 0,15 até a barra infinita
+        // em paralelo com 0,15 + (0,5 ∥ 0,3) até a barra de alta tensão
+        let x2 = paralelo(0.15, 0.15 + paralelo(0.5, 0.3));
+        assert!(perto(x2, 27.0 / 260.0));
+        // Sequência zero: o transformador (0,2) é a derivação na barra de alta
+        // tensão e o gerador fica isolado
+        let x0 = paralelo(0.45, 0.45 + paralelo(0.2, 0.9));
+        assert!(perto(x0, 27.0 / 104.0));
+        let xf = 0.05;
+        for (tipo, delta) in [
+            (TipoFalta::FaseTerra, x2 + x0 + 3.0 * xf),
+            (TipoFalta::FaseFase, x2 + xf),
+            (TipoFalta::FaseFaseTerra, paralelo(x2, x0 + 3.0 * xf)),
+        ] {
+            let r = rede_desequilibrada(tipo).calcular(1.0).unwrap();
+            assert!(perto(r.x2_thevenin.unwrap(), x2), "{}", tipo);
+            assert_eq!(
+                r.x0_thevenin.is_some(),
+                tipo.usa_sequencia_zero(),
+                "{}",
+                tipo
+            );
+            if let Some(x0_thevenin) = r.x0_thevenin {
+                assert!(perto(x0_thevenin, x0), "{}", tipo);
+            }
+            assert!(perto(r.x_derivacao, delta), "{}", tipo);
+            assert!(perto(r.x_falta, x_falta_com_derivacao(delta)), "{}", tipo);
+            assert!(
+                perto(r.pe2.pmax, r.e_interna / x_falta_com_derivacao(delta)),
+                "{}",
+                tipo
+            );
+        }
+    }
+
+    #[test]
+    fn pe3_das_aberturas_monopolar_e_bipolar() {
+        // Entre os pontos abertos: a própria linha em série com a reatância
+        // entre as barras sem ela (barra infinita aterrada nas duas sequências)
+        let x2 = 0.3 + paralelo(0.5, 0.3);
+        let x0 = 0.9 + paralelo(0.2, 0.9);
+        for (tipo, x_serie) in [
+            (TipoAbertura::Monopolar, paralelo(x2, x0)),
+            (TipoAbertura::Bipolar, x2 + x0),
+        ] {
+            let mut rede = rede();
+            rede.tipo_abertura = tipo;
+            rede.x0_linhas = Some(vec![0.9, 0.9]);
+            let r = rede.calcular(1.0).unwrap();
+            // A linha em falta volta com a reatância série em paralelo com a sã
+            let x_pos_abertura = 0.5 + paralelo(0.3, 0.3 + x_serie);
+            assert!((r.x_serie_abertura - x_serie).abs() < 1e-6, "{}", tipo);
+            assert!((r.x_pos_abertura - x_pos_abertura).abs() < 1e-6, "{}", tipo);
+            assert!(
+                (r.pe3.pmax - r.e_interna / x_pos_abertura).abs() < 1e-6,
+                "{}",
+                tipo
+            );
+        }
+    }
+
+    #[test]
+    fn falta_na_barra_de_alta_tensao_anula_a_transferencia() {
+        let mut rede = rede();
+        rede.posicao_falta = 0.0;
+        let r = rede.calcular(1.0).unwrap();
+        assert_eq!(r.x_falta, f64::INFINITY);
+        assert_eq!(r.pe2.pmax, 0.0);
+    }
+
+    #[test]
+    fn carregamento_exige_vt_ou_q() {
+        let mut rede = rede();
+        rede.vt = Some(1.05);
+        assert!(matches!(rede.calcular(1.0), Err(Error::Rede(_))));
+    }
+}
@@ -0,0 +1,319 @@
+//! Integrador adaptativo de Dormand–Prince (RK45) com controle de erro e
+//! saída densa.
+
+use crate::ode::{AdaptiveIntegrator, OdeSystem, combinar};
+
+// Tabela de Butcher de Dormand–Prince 5(4)
+const C2: f64 = 1.0 / 5.0;
+const C3: f64 = 3.0 / 10.0;
+const C4: f64 = 4.0 / 5.0;
+const C5: f64 = 8.0 / 9.0;
+
+const A21: f64 = 1.0 / 5.0;
+const A31: f64 = 3.0 / 40.0;
+const A32: f64 = 9.0 / 40.0;
+const A41: f64 = 44.0 / 45.0;
+const A42: f64 = -56.0 / 15.0;
+const A43: f64 = 32.0 / 9.0;
+const A51: f64 = 19372.0 / 6561.0;
+const A52: f64 = -25360.0 / 2187.0;
+const A53: f64 = 64448.0 / 6561.0;
+const A54: f64 = -212.0 / 729.0;
+const A61: f64 = 9017.0 / 3168.0;
+const A62: f64 = -355.0 / 33.0;
+const A63: f64 = 46732.0 / 5247.0;
+const A64: f64 = 49.0 / 176.0;
+const A65: f64 = -5103.0 / 18656.0;
+// Solução de 5ª ordem
+const B1: f64 = 35.0 / 384.0;
+const B3: f64 = 500.0 / 1113.0;
+const B4: f64 = 125.0 / 192.0;
+const B5: f64 = -2187.0 / 6784.0;
+const B6: f64 = 11.0 / 84.0;
+// Diferença entre as soluções de 5ª e 4ª ordem (estimativa do erro local)
+const E1: f64 = 71.0 / 57600.0;
+const E3: f64 = -71.0 / 16695.0;
+const E4: f64 = 71.0 / 1920.0;
+const E5: f64 = -17253.0 / 339200.0;
+const E6: f64 = 22.0 / 525.0;
+const E7: f64 = -1.0 / 40.0;
+// Coeficientes da saída densa de 4ª ordem (Hairer, Nørsett e Wanner)
+const D1: f64 = -12715105075.0 / 11282082432.0;
+const D3: f64 = 87487479700.0 / 32700410799.0;
+const D4: f64 = -10690763975.0 / 1880347072.0;
+const D5: f64 = 701980252875.0 / 199316789632.0;
+const D6: f64 = -1453857185.0 / 822651844.0;
+const D7: f64 = 69997945.0 / 29380423.0;
+
+// Controle do passo
+const SEGURANCA: f64 = 0.9;
+const FATOR_MIN: f64 = 0.2;
+const FATOR_MAX: f64 = 10.0;
+
+/// Dormand–Prince 5(4) com passo adaptativo.
+///
+/// O erro local é medido pela norma RMS ponderada por
+/// `tolerancia_abs + tolerancia_rel·|x|`, e o passo é aceito quando essa norma
+/// não passa de 1. O primeiro estágio é recalculado a cada passo, então os
+/// parâmetros do sistema podem mudar entre passos (chaveamentos).
+#[derive(Debug, Clone)]
+pub struct Rk45 {
+    /// Tolerância absoluta.
+    pub tolerancia_abs: f64,
+    /// Tolerância relativa.
+    pub tolerancia_rel: f64,
+    /// Menor passo permitido; abaixo dele o passo é aceito mesmo com erro acima da tolerância.
+    pub passo_min: f64,
+    /// Maior passo permitido.
+    pub passo_max: f64,
+    // Próximo passo a tentar
+    h: f64,
+    k: [Vec<f64>; 7],
+    x_tmp: Vec<f64>,
+    x_novo: Vec<f64>,
+    // Saída densa do último passo aceito
+    t_ant: f64,
+    h_ant: f64,
+    rcont: [Vec<f64>; 5],
+}
+
+impl Rk45 {
+    /// Cria o integrador com passo inicial `passo_inicial`.
+    pub fn new(
+        tolerancia_abs: f64,
+        tolerancia_rel: f64,
+        passo_inicial: f64,
+        passo_max: f64,
+    ) -> Self {
+        Rk45 {
+            tolerancia_abs,
+            tolerancia_rel,
+            passo_min: 1e-12,
+            passo_max,
+            h: passo_inicial.min(passo_max),
+            k: Default::default(),
+            x_tmp: Vec::new(),
+            x_novo: Vec::new(),
+            t_ant: 0.0,
+            h_ant: 0.0,
+            rcont: Default::default(),
+        }
+    }
+
+    /// Passo que será tentado na próxima chamada.
+    pub fn passo_atual(&self) -> f64 {
+        self.h
+    }
+
+    // Calcula os sete estágios e a solução de 5ª ordem em x_novo; retorna a norma do erro
+    #[allow(clippy::needless_range_loop)]
+    fn tentar(&mut self, sistema: &dyn OdeSystem, t: f64, x: &[f64], h: f64) -> f64 {
+        let n = x.len();
+        let [k1, k2, k3, k4, k5, k6, k7] = &mut self.k;
+
+        sistema.derivada(t, x, k1);
+
+        combinar(&mut self.x_tmp, x, h * A21, k1);
+        sistema.derivada(t + C2 * h, &self.x_tmp, k2);
+
+        for i in 0..n {
+            self.x_tmp[i] = x[i] + h * (A31 * k1[i] + A32 * k2[i]);
+        }
+        sistema.derivada(t + C3 * h, &self.x_tmp, k3);
+
+        for i in 0..n {
+            self.x_tmp[i] = x[i] + h * (A41 * k1[i] + A42 * k2[i] + A43 * k3[i]);
+        }
+        sistema.derivada(t + C4 * h, &self.x_tmp, k4);
+
+        for i in 0..n {
+            self.x_tmp[i] = x[i] + h * (A51 * k1[i] + A52 * k2[i] + A53 * k3[i] + A54 * k4[i]);
+        }
+        sistema.derivada(t + C5 * h, &self.x_tmp, k5);
+
+        for i in 0..n {
+            self.x_tmp[i] =
+                x[i] + h * (A61 * k1[i] + A62 * k2[i] + A63 * k3[i] + A64 * k4[i] + A65 * k5[i]);
+        }
+        sistema.derivada(t + h, &self.x_tmp, k6);
+
+        for i in 0..n {
+            self.x_novo[i] =
+                x[i] + h * (B1 * k1[i] + B3 * k3[i] + B4 * k4[i] + B5 * k5[i] + B6 * k6[i]);
+        }
+        sistema.derivada(t + h, &self.x_novo, k7);
+
+        let mut soma = 0.0;
+        for i in 0..n {
+            let erro =
+                h * (E1 * k1[i] + E3 * k3[i] + E4 * k4[i] + E5 * k5[i] + E6 * k6[i] + E7 * k7[i]);
+            let escala =
+                self.tolerancia_abs + self.tolerancia_rel * x[i].abs().max(self.x_novo[i].abs());
+            soma += (erro / escala).powi(2);
+        }
+        (soma / n as f64).sqrt()
+    }
+
+    // Guarda os coeficientes da saída densa do passo aceito (t, x) -> (t + h, x_novo)
+    #[allow(clippy::needless_range_loop)]
+    fn preparar_saida_densa(&mut self, t: f64, x: &[f64], h: f64) {
+        let [k1, _, k3, k4, k5, k6, k7] = &self.k;
+        let [r1, r2, r3, r4, r5] = &mut self.rcont;
+        for i in 0..x.len() {
+            let diferenca = self.x_novo[i] - x[i];
+            let bspl = h * k1[i] - diferenca;
+            r1[i] = x[i];
+            r2[i] = diferenca;
+            r3[i] = bspl;
+            r4[i] = diferenca - h * k7[i] - bspl;
+            r5[i] =
+                h * (D1 * k1[i] + D3 * k3[i] + D4 * k4[i] + D5 * k5[i] + D6 * k6[i] + D7 * k7[i]);
+        }
+        self.t_ant = t;
+        self.h_ant = h;
+    }
+}
+
+impl AdaptiveIntegrator for Rk45 {
+    fn passo_adaptativo(
+        &mut self,
+        sistema: &dyn OdeSystem,
+        t: f64,
+        x: &mut [f64],
+        t_limite: f64,
+    ) -> f64 {
+        let n = sistema.dimensao();
+        for v in self
+            .k
+            .iter_mut()
+            .chain(self.rcont.iter_mut())
+            .chain([&mut self.x_tmp, &mut self.x_novo])
+        {
+            v.resize(n, 0.0);
+        }
+
+        loop {
+            let restante = t_limite - t;
+            let limitado = self.h >= restante;
+            let h = if limitado { restante } else { self.h };
+
+            let erro = self.tentar(sistema, t, x, h);
+            let fator = if erro == 0.0 {
+                FATOR_MAX
+            } else {
+                (SEGURANCA * erro.powf(-0.2)).clamp(FATOR_MIN, FATOR_MAX)
+            };
+
+            if erro <= 1.0 || h <= self.passo_min {
+                self.preparar_saida_densa(t, x, h);
+                x.copy_from_slice(&self.x_novo);
+                // Um passo encurtado para atingir t_limite não reduz o passo seguinte
+                let h_novo = (h * fator).min(self.passo_max);
+                self.h = if limitado { self.h.max(h_novo) } else { h_novo };
+                return if limitado { t_limite } else { t + h };
+            }
+
+            // Passo rejeitado: tenta novamente com passo menor
+            self.h = (h * fator.min(1.0)).max(self.passo_min);
+        }
+    }
+
+    fn interpolar(&self, t: f64, saida: &mut [f64]) {
+        let theta = (t - self.t_ant) / self.h_ant;
+        let theta1 = 1.0 - theta;
+        let [r1, r2, r3, r4, r5] = &self.rcont;
+        for (i, s) in saida.iter_mut().enumerate() {
+            *s = r1[i] + theta * (r2[i] + theta1 * (r3[i] + theta * (r4[i] + theta1 * r5[i])));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    // Oscilador harmônico x'' = -x, com solução (cos t, -sin t) a partir de (1, 0)
+    struct Oscilador;
+
+    impl OdeSystem for Oscilador {
+        fn dimensao(&self) -> usize {
+            2
+        }
+
+        fn derivada(&self, _t: f64, x: &[f64], dxdt: &mut [f64]) {
+            dxdt[0] = x[1];
+            dxdt[1] = -x[0];
+        }
+    }
+
+    fn perto(a: f64, b: f64, tolerancia: f64) -> bool {
+        (a - b).abs() <= tolerancia
+    }
+
+    #[test]
+    fn tabela_de_butcher_satisfaz_as_condicoes_de_ordem() {
+        let c = [0.0, C2, C3, C4, C5, 1.0];
+        let a = [
+            vec![],
+            vec![A21],
+            vec![A31, A32],
+            vec![A41, A42, A43],
+            vec![A51, A52, A53, A54],
+            vec![A61, A62, A63, A64, A65],
+        ];
+        for (ci, linha) in c.iter().zip(&a) {
+            assert!(perto(linha.iter().sum::<f64>(), *ci, 1e-14));
+        }
+
+        // b2 = 0 na solução de 5ª ordem
+        let b = [B1, 0.0, B3, B4, B5, B6];
+        let momento = |k: i32| -> f64 { b.iter().zip(&c).map(|(bi, ci)| bi * ci.powi(k)).sum() };
+        for k in 0..5 {
+            assert!(
+                perto(momento(k), 1.0 / (k + 1) as f64, 1e-14),
+                "Σ b·c^{}",
+                k
+            );
+        }
+
+        // As soluções de 5ª e 4ª ordem são consistentes: a diferença soma zero
+        let e = [E1, E3, E4, E5, E6, E7];
+        assert!(perto(e.iter().sum::<f64>(), 0.0, 1e-15));
+    }
+
+    #[test]
+    fn oscilador_harmonico_segue_a_solucao_exata() {
+        let mut integrador = Rk45::new(1e-10, 1e-10, 1e-3, 0.5);
+        let mut x = vec![1.0, 0.0];
+        let mut t = 0.0;
+        let t_fim = 2.0 * PI;
+        while t < t_fim {
+            t = integrador.passo_adaptativo(&Oscilador, t, &mut x, t_fim);
+        }
+        assert_eq!(t, t_fim);
+        assert!(perto(x[0], 1.0, 1e-8), "x = {:?}", x);
+        assert!(perto(x[1], 0.0, 1e-8), "x = {:?}", x);
+    }
+
+    #[test]
+    fn saida_densa_reproduz_os_extremos_e_interpola_o_passo() {
+        let mut integrador = Rk45::new(1e-8, 1e-8, 0.2, 0.2);
+        let mut x = vec![1.0, 0.0];
+        let t1 = integrador.passo_adaptativo(&Oscilador, 0.0, &mut x, 1.0);
+        assert!(t1 > 0.0);
+
+        let mut saida = vec![0.0; 2];
+        integrador.interpolar(0.0, &mut saida);
+        assert_eq!(saida, vec![1.0, 0.0]);
+        integrador.interpolar(t1, &mut saida);
+        assert!(perto(saida[0], x[0], 1e-15) && perto(saida[1], x[1], 1e-15));
+
+        for fracao in [0.25, 0.5, 0.75] {
+            let t = fracao * t1;
+            integrador.interpolar(t, &mut saida);
+            assert!(perto(saida[0], t.cos(), 1e-7), "t = {}: {:?}", t, saida);
+            assert!(perto(saida[1], -t.sin(), 1e-7), "t = {}: {:?}", t, saida);
+        }
+    }
+}
@@ -1,35 +1,29 @@
-use std::f64::consts::PI;
+//! Integração da equação de oscilação por Runge-Kutta de 4ª ordem.
 
-use crate::values::*;
-// Implementação do Runge Kutta com termo de amortecimento D
+use crate::case::SystemParams;
+use crate::curva::PowerAngleCurve;
+use crate::ode::{Integrator, Rk4};
+use crate::swing::{DELTA, DELTA_W, SwingEquation};
+
+/// Implementação do Runge Kutta com termo de amortecimento D.
+///
+/// Avança um passo `delta_t` da equação de oscilação com a curva elétrica
+/// `pe` e retorna `(Δω, δ)` ao final do passo. Para vários passos, prefira
+/// reutilizar um [`Rk4`] com [`SwingEquation`], como faz
+/// [`crate::sim_per_time::sim_pet_time`].
 pub fn runge_kutta_with_d(
+    sistema: &SystemParams,
     delta_w_ini: f64,
     delta_n_ini: f64,
-    pe: f64,
+    pe: PowerAngleCurve,
     delta_t: f64,
-    d: f64,
 ) -> (f64, f64) {
-    let m = H / (PI * F); // Mantendo a inércia como H
-
-    let k1 = delta_w_ini * delta_t;
-    let l1 = ((1.0 / m) * (PM - pe * delta_n_ini.sin()) - (d / m) * delta_w_ini) * delta_t;
-
-    let k2 = (delta_w_ini + 0.5 * l1) * delta_t;
-    let l2 = ((1.0 / m) * (PM - pe * (delta_n_ini + 0.5 * k1).sin())
-        - (d / m) * (delta_w_ini + 0.5 * l1))
-        * delta_t;
-
-    let k3 = (delta_w_ini + 0.5 * l2) * delta_t;
-    let l3 = ((1.0 / m) * (PM - pe * (delta_n_ini + 0.5 * k2).sin())
-        - (d / m) * (delta_w_ini + 0.5 * l2))
-        * delta_t;
-
-    let k4 = (delta_w_ini + l3) * delta_t;
-    let l4 =
-        ((1.0 / m) * (PM - pe * (delta_n_ini + k3).sin()) - (d / m) * (delta_w_ini + l3)) * delta_t;
+    let swing = SwingEquation::new(sistema, pe);
+    let mut x = [0.0; 2];
+    x[DELTA] = delta_n_ini;
+    x[DELTA_W] = delta_w_ini;
 
-    let delta_w = delta_w_ini + (l1 + 2.0 * l2 + 2.0 * l3 + l4) / 6.0;
-    let delta_n = delta_n_ini + (k1 + 2.0 * k2 + 2.0 * k3 + k4) / 6.0;
+    Rk4::new().passo(&swing, 0.0, &mut x, delta_t);
 
-    (delta_w, delta_n)
+    (x[DELTA_W], x[DELTA])
 }
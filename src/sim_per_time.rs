@@ -1,88 +1,466 @@
-use crate::{runge_kutta::runge_kutta_with_d, values::D};
-
-pub fn sim_pet_time(
-    pe1: f64,
-    pe2: f64,
-    pe3: f64,
-    tab: f64,
-    tr: f64,
-    delta_w_ini: f64,
-    delta_n_ini: f64,
-    t_max: f64,
-    delta_t: f64,
-) -> (Vec<f64>, Vec<f64>, Vec<f64>, f64, f64) {
-    let mut tempos = vec![0.0];
-    let mut angulos = vec![delta_n_ini];
-    let mut velocidades = vec![delta_w_ini];
-
-    let mut delta_n = delta_n_ini;
-    let mut delta_w = delta_w_ini;
+//! Simulação no tempo de uma sequência de chaveamentos (por padrão,
+//! falta → abertura → religamento).
+
+use crate::case::{Case, IntegratorSettings, MetodoIntegracao};
+use crate::error::Result;
+use crate::estabilidade::{Classificador, Estabilidade};
+use crate::eventos::{self, Chaveamento};
+use crate::maquina::{MachineModel, modelo_maquina};
+use crate::ode::{AdaptiveIntegrator, Integrator, OdeSystem, Rk4};
+use crate::rk45::Rk45;
+use crate::swing::{DELTA, DELTA_W};
+use crate::um_eixo::E_Q_TRANSITORIA;
+
+/// Trajetória simulada e ângulos nos instantes de chaveamento.
+#[derive(Debug, Clone)]
+pub struct ResultadoSimulacao {
+    /// Instantes de tempo (s).
+    pub tempos: Vec<f64>,
+    /// Ângulo do rotor δ (rad) em cada instante.
+    pub angulos: Vec<f64>,
+    /// Desvio de velocidade Δω (rad/s) em cada instante.
+    pub velocidades: Vec<f64>,
+    /// FEM transitória `E'q` (p.u.) em cada instante; vazia no modelo
+    /// clássico.
+    pub fem_transitoria: Vec<f64>,
+    /// Módulo da tensão terminal (p.u.) em cada instante; vazio no modelo
+    /// clássico.
+    pub tensao_terminal: Vec<f64>,
+    /// Tensão de campo `Efd` (p.u.) em cada instante; vazia no modelo
+    /// clássico.
+    pub tensao_campo: Vec<f64>,
+    /// Potência mecânica `Pm` (p.u.) em cada instante.
+    pub potencia_mecanica: Vec<f64>,
+    /// Ângulo no instante de abertura `tab` (CRA, rad); `None` se a simulação
+    /// termina antes de `tab` (`tab > t_max` ou interrupção de uma trajetória
+    /// instável).
+    pub cra: Option<f64>,
+    /// Ângulo no instante de religamento `max(tr, tab)` (CRR, rad); `None` se
+    /// a simulação termina antes desse instante.
+    pub crr: Option<f64>,
+    /// Avaliação de estabilidade da trajetória.
+    pub estabilidade: Estabilidade,
+}
+
+/// Simula o caso a partir do equilíbrio pré-falta (Pe1) aplicando a
+/// sequência de eventos do caso (ver [`crate::eventos`]), até
+/// `integrador.t_max`, com o método de integração do caso. Sem eventos
+/// declarados, usa a sequência clássica: curto-circuito em t = 0 (Pe1 → Pe2),
+/// abertura em `tab` (Pe2 → Pe3) e religamento em `tr` (Pe3 → Pe1).
+///
+/// A integração chega exatamente aos instantes dos eventos e a `tab` e `tr`
+/// (o passo que os contém é dividido), então CRA e CRR variam continuamente
+/// com `tab` e `tr` e não dependem do passo de integração.
+///
+/// A trajetória é classificada durante a simulação (ver
+/// [`crate::estabilidade`]); com `estabilidade.interromper`, a simulação para
+/// assim que a perda de sincronismo é certa.
+///
+/// O gerador segue o modelo de `[maquina]` (ver [`crate::maquina`]); nos
+/// modelos com dinâmica de fluxo, o critério do equilíbrio instável usa a
+/// curva da rede com as FEMs do instante de cada chaveamento.
+///
+/// # Errors
+///
+/// Se o modelo do gerador não pode ser montado: caso que não passa em
+/// [`Case::validar_maquina`] ou rede SMIB inválida (verificados por
+/// [`Case::carregar`], mas não em um `Case` montado no código).
+pub fn sim_pet_time(case: &Case, tab: f64, tr: f64) -> Result<ResultadoSimulacao> {
+    let sistema = &case.sistema;
+    let t_max = case.integrador.t_max;
+
+    let mut maquina = modelo_maquina(case)?;
+    let mut x = maquina.estado_inicial(sistema);
     let mut t = 0.0;
 
-    let mut current_pe = pe1;
-    let mut transicao_inicial_executada = false;
-    let mut transicao_tab_executada = false;
-    let mut transicao_tr_executada = false;
-    let mut delta_cra = 0.0;
-    let mut delta_crr = 0.0;
+    let classificador = Classificador::new(&case.estabilidade, t, x[DELTA]);
+    let mut trajetoria = Trajetoria::new(t, &x, classificador);
+    trajetoria.sinais(maquina.as_ref(), t, &x);
+    let mut avanco = Avanco::new(&case.integrador);
 
-    // println!("\n=== Execução da simulação com Runge-Kutta e transições por tempo ===");
+    let chaveamentos = eventos::resolver(&case.eventos, sistema, tab, tr);
+    let mut pendentes = chaveamentos.iter().peekable();
+    let definitiva = pendentes.peek().is_none_or(|c| c.t > t_max);
+    trajetoria
+        .classificador
+        .rede(t, &maquina.curva(&x), maquina.pm(t, &x), definitiva);
+
+    // CRA e CRR são os ângulos em tab e max(tr, tab), haja ou não evento neles
+    let t_crr = tr.max(tab);
+    let mut delta_cra = None;
+    let mut delta_crr = None;
 
     loop {
-        // Verificações de transição ANTES do cálculo Runge-Kutta
-        if t == 0.0 && !transicao_inicial_executada {
-            // println!("CURTO-CIRCUITO em t={:.3}s: Transição Pe1 → Pe2", t);
-            current_pe = pe2;
-            transicao_inicial_executada = true;
-        } else if current_pe == pe2 && t >= tab && !transicao_tab_executada {
-            // println!("\nTransição Pe2 → Pe3 em t={:.3}s", t);
-            // println!(
-            //     "δn no momento da transição: {:.4}° -> {:.4}rad",
-            //     delta_n.to_degrees(),
-            //     delta_n
-            // );
-            delta_cra = delta_n;
-            current_pe = pe3;
-            transicao_tab_executada = true;
-        } else if current_pe == pe3 && t >= tr && !transicao_tr_executada {
-            // println!("\nTransição Pe3 → Pe1 em t={:.3}s", t);
-            // println!(
-            //     "δn no momento da transição: {:.4}° -> {:.4}rad",
-            //     delta_n.to_degrees(),
-            //     delta_n
-            // );
-            delta_crr = delta_n;
-            current_pe = pe1;
-            transicao_tr_executada = true;
-        }
-
-        // Aplicar Runge-Kutta DEPOIS das verificações de transição
-        let (new_delta_w, new_delta_n) =
-            runge_kutta_with_d(delta_w, delta_n, current_pe, delta_t, D);
-        delta_n = new_delta_n;
-        delta_w = new_delta_w;
-
-        // Print dos valores atuais (usando índice i para acessar os vetores)
-        // println!(
-        //     "t={:.3}s, δn={:.4}° {:.4} rad| , Δw={:.4} rad/s, Pe={:.4} sen(δ)",
-        //     tempos[i],
-        //     angulos[i].to_degrees(),
-        //     angulos[i],
-        //     velocidades[i],
-        //     current_pe
-        // );
-
-        // Atualizar tempo e adicionar novos valores aos vetores
-        t += delta_t;
-        tempos.push(t);
-        angulos.push(delta_n);
-        velocidades.push(delta_w);
-
-        if t >= t_max {
+        let mut t_parada = t_max;
+        if let Some(chaveamento) = pendentes.peek() {
+            t_parada = t_parada.min(chaveamento.t);
+        }
+        if delta_cra.is_none() {
+            t_parada = t_parada.min(tab);
+        }
+        if delta_crr.is_none() {
+            t_parada = t_parada.min(t_crr);
+        }
+        let mut registro = RegistroMaquina {
+            maquina: maquina.as_ref(),
+            trajetoria: &mut trajetoria,
+        };
+        avanco.integrar_ate(maquina.as_ref(), &mut t, &mut x, t_parada, &mut registro);
+
+        if delta_cra.is_none() && tab <= t {
+            delta_cra = Some(x[DELTA]);
+        }
+        if delta_crr.is_none() && t_crr <= t {
+            delta_crr = Some(x[DELTA]);
+        }
+        // Eventos simultâneos são aplicados na ordem declarada
+        let mut chaveou = false;
+        while let Some(chaveamento) = pendentes.next_if(|c| c.t <= t) {
+            maquina.chavear(chaveamento);
+            chaveou = true;
+        }
+        if chaveou {
+            let definitiva = pendentes.peek().is_none_or(|c| c.t > t_max);
+            trajetoria
+                .classificador
+                .rede(t, &maquina.curva(&x), maquina.pm(t, &x), definitiva);
+        }
+
+        if t >= t_max || trajetoria.classificador.encerrar() {
             break;
         }
     }
 
-    // println!("\n=== Fim da simulação ===");
-    (tempos, angulos, velocidades, delta_cra, delta_crr)
+    Ok(ResultadoSimulacao {
+        tempos: trajetoria.tempos,
+        angulos: trajetoria.angulos,
+        velocidades: trajetoria.velocidades,
+        fem_transitoria: trajetoria.fem_transitoria,
+        tensao_terminal: trajetoria.tensao_terminal,
+        tensao_campo: trajetoria.tensao_campo,
+        potencia_mecanica: trajetoria.potencia_mecanica,
+        cra: delta_cra,
+        crr: delta_crr,
+        estabilidade: trajetoria.classificador.concluir(),
+    })
+}
+
+// Pontos registrados da trajetória, classificados à medida que são registrados
+struct Trajetoria {
+    tempos: Vec<f64>,
+    angulos: Vec<f64>,
+    velocidades: Vec<f64>,
+    fem_transitoria: Vec<f64>,
+    tensao_terminal: Vec<f64>,
+    tensao_campo: Vec<f64>,
+    potencia_mecanica: Vec<f64>,
+    classificador: Classificador,
+}
+
+// Registro da trajetória com os sinais do gerador, que dependem da rede em
+// vigor no trecho integrado
+struct RegistroMaquina<'a> {
+    maquina: &'a dyn MachineModel,
+    trajetoria: &'a mut Trajetoria,
+}
+
+impl Registro for RegistroMaquina<'_> {
+    fn registrar(&mut self, t: f64, x: &[f64]) {
+        self.trajetoria.registrar(t, x);
+        self.trajetoria.sinais(self.maquina, t, x);
+    }
+
+    fn encerrar(&self) -> bool {
+        self.trajetoria.encerrar()
+    }
+}
+
+// Destino dos pontos calculados durante a integração
+pub(crate) trait Registro {
+    // Registra o estado `x` no instante `t`
+    fn registrar(&mut self, t: f64, x: &[f64]);
+
+    // `true` quando a simulação pode parar antes de `t_max`
+    fn encerrar(&self) -> bool;
+}
+
+impl Trajetoria {
+    fn new(t: f64, x: &[f64], classificador: Classificador) -> Self {
+        Trajetoria {
+            tempos: vec![t],
+            angulos: vec![x[DELTA]],
+            velocidades: vec![x[DELTA_W]],
+            fem_transitoria: Vec::new(),
+            tensao_terminal: Vec::new(),
+            tensao_campo: Vec::new(),
+            potencia_mecanica: Vec::new(),
+            classificador,
+        }
+    }
+
+    // Registra `E'q`, a tensão terminal, a tensão de campo e a potência
+    // mecânica de `maquina` no instante `t` e no estado `x`. Os modelos com
+    // terminal têm `E'q` logo após `[δ, Δω]`; no clássico, seguem-se os
+    // estados do regulador
+    fn sinais(&mut self, maquina: &dyn MachineModel, t: f64, x: &[f64]) {
+        if let Some(vt) = maquina.tensao_terminal(x) {
+            self.fem_transitoria.push(x[E_Q_TRANSITORIA]);
+            self.tensao_terminal.push(vt);
+        }
+        self.tensao_campo.extend(maquina.tensao_campo(x));
+        self.potencia_mecanica.push(maquina.pm(t, x));
+    }
+}
+
+impl Registro for Trajetoria {
+    fn registrar(&mut self, t: f64, x: &[f64]) {
+        self.tempos.push(t);
+        self.angulos.push(x[DELTA]);
+        self.velocidades.push(x[DELTA_W]);
+        self.classificador.observar(t, x[DELTA], x[DELTA_W]);
+    }
+
+    fn encerrar(&self) -> bool {
+        self.classificador.encerrar()
+    }
+}
+
+// Primeiro instante em que `g(x)` passa de negativo a não negativo na
+// trajetória de `maquina` a partir do estado `x` em t = 0, com os
+// chaveamentos de instante finito de `chaveamentos`, até `integrador.t_max`.
+// O cruzamento é localizado entre dois pontos registrados do mesmo trecho e
+// refinado por bisseção no instante (ver `refinar_cruzamento`), então não
+// depende do espaçamento dos pontos; uma trajetória que já parte com
+// `g ≥ 0` só cruza depois de voltar a `g < 0`. Retorna o instante e o estado
+// nele
+pub(crate) fn primeiro_cruzamento(
+    maquina: &mut dyn MachineModel,
+    mut x: Vec<f64>,
+    chaveamentos: &[Chaveamento],
+    integrador: &IntegratorSettings,
+    g: impl Fn(&[f64]) -> f64,
+) -> Option<(f64, Vec<f64>)> {
+    let mut t = 0.0;
+    let mut cruzamento = Cruzamento {
+        g: &g,
+        anterior: None,
+        encontrado: None,
+    };
+    let mut avanco = Avanco::new(integrador);
+    let mut pendentes = chaveamentos.iter().filter(|c| c.t.is_finite()).peekable();
+    loop {
+        while let Some(chaveamento) = pendentes.next_if(|c| c.t <= t) {
+            maquina.chavear(chaveamento);
+        }
+        // Ponto inicial do trecho, na rede em vigor nele
+        cruzamento.registrar(t, &x);
+        if cruzamento.encontrado.is_some() || t >= integrador.t_max {
+            break;
+        }
+        let t_parada = pendentes
+            .peek()
+            .map_or(integrador.t_max, |c| c.t.min(integrador.t_max));
+        avanco.integrar_ate(maquina, &mut t, &mut x, t_parada, &mut cruzamento);
+        // Estado exato no fim do trecho (a saída densa pode não amostrá-lo)
+        cruzamento.registrar(t, &x);
+        if cruzamento.encontrado.is_some() {
+            break;
+        }
+    }
+
+    let (t0, x0, t1) = cruzamento.encontrado?;
+    Some(refinar_cruzamento(
+        maquina,
+        t0,
+        &x0,
+        t1,
+        integrador.delta_t,
+        &g,
+    ))
+}
+
+// Cruzamento de `g` com zero entre o ponto `(t0, x0)`, com `g(x0) < 0`, e o
+// instante `t1`, com `g ≥ 0`: bisseção no instante, com a trajetória
+// recalculada a partir de `x0` por passos RK4 de no máximo `passo`
+fn refinar_cruzamento(
+    sistema: &dyn OdeSystem,
+    t0: f64,
+    x0: &[f64],
+    t1: f64,
+    passo: f64,
+    g: impl Fn(&[f64]) -> f64,
+) -> (f64, Vec<f64>) {
+    let mut integrador = Rk4::new();
+    let mut estado = |t: f64| {
+        let mut x = x0.to_vec();
+        let n = ((t - t0) / passo).ceil().max(1.0);
+        let h = (t - t0) / n;
+        for k in 0..n as usize {
+            integrador.passo(sistema, t0 + k as f64 * h, &mut x, h);
+        }
+        x
+    };
+    let (mut antes, mut depois) = (t0, t1);
+    let mut x_depois = estado(t1);
+    while depois - antes > 1e-12 * depois.abs().max(1.0) {
+        let meio = 0.5 * (antes + depois);
+        let x_meio = estado(meio);
+        if g(&x_meio) >= 0.0 {
+            depois = meio;
+            x_depois = x_meio;
+        } else {
+            antes = meio;
+        }
+    }
+    (depois, x_depois)
+}
+
+// Último ponto com `g < 0` e, quando `g` volta a ser não negativo depois
+// dele, o intervalo `(t0, x0, t1)` que contém o cruzamento
+struct Cruzamento<'a, G: Fn(&[f64]) -> f64> {
+    g: &'a G,
+    anterior: Option<(f64, Vec<f64>)>,
+    encontrado: Option<(f64, Vec<f64>, f64)>,
+}
+
+impl<G: Fn(&[f64]) -> f64> Registro for Cruzamento<'_, G> {
+    fn registrar(&mut self, t: f64, x: &[f64]) {
+        if self.encontrado.is_some() {
+            return;
+        }
+        if (self.g)(x) < 0.0 {
+            self.anterior = Some((t, x.to_vec()));
+        } else if let Some((t0, x0)) = self.anterior.take() {
+            self.encontrado = Some((t0, x0, t));
+        }
+    }
+
+    fn encerrar(&self) -> bool {
+        self.encontrado.is_some()
+    }
+}
+
+// Integração de um trecho entre chaveamentos com o método escolhido no caso
+pub(crate) enum Avanco {
+    // RK4 na grade k·delta_t; o passo que contém um chaveamento é dividido nele
+    Fixo {
+        integrador: Rk4,
+        delta_t: f64,
+        proximo: u64, // índice do próximo ponto da grade
+    },
+    // RK45 com passos terminando exatamente nos chaveamentos
+    Adaptativo {
+        integrador: Box<Rk45>,
+        intervalo_saida: Option<f64>,
+        amostra: u64, // índice da próxima amostra da saída densa
+        x_amostra: Vec<f64>,
+    },
+}
+
+impl Avanco {
+    pub(crate) fn new(config: &IntegratorSettings) -> Self {
+        match config.metodo {
+            MetodoIntegracao::Rk4 => Avanco::Fixo {
+                integrador: Rk4::new(),
+                delta_t: config.delta_t,
+                proximo: 1,
+            },
+            MetodoIntegracao::Rk45 => Avanco::Adaptativo {
+                integrador: Box::new(Rk45::new(
+                    config.tolerancia_abs,
+                    config.tolerancia_rel,
+                    config.delta_t,
+                    config.passo_max,
+                )),
+                intervalo_saida: config.intervalo_saida,
+                amostra: 1,
+                x_amostra: Vec::new(),
+            },
+        }
+    }
+
+    // Integra de t até exatamente t_fim, registrando a trajetória
+    pub(crate) fn integrar_ate(
+        &mut self,
+        sistema: &dyn OdeSystem,
+        t: &mut f64,
+        x: &mut [f64],
+        t_fim: f64,
+        trajetoria: &mut dyn Registro,
+    ) {
+        match self {
+            Avanco::Fixo {
+                integrador,
+                delta_t,
+                proximo,
+            } => {
+                while *t < t_fim && !trajetoria.encerrar() {
+                    let t_grade = *proximo as f64 * *delta_t;
+                    // Chaveamento a menos de 1e-9·delta_t da grade coincide com ela
+                    let t_novo = if t_grade >= t_fim - 1e-9 * *delta_t {
+                        t_fim
+                    } else {
+                        t_grade
+                    };
+                    integrador.passo(sistema, *t, x, t_novo - *t);
+                    if t_novo >= t_grade - 1e-9 * *delta_t {
+                        *proximo += 1;
+                    }
+                    *t = t_novo;
+                    trajetoria.registrar(*t, x);
+                }
+            }
+            Avanco::Adaptativo {
+                integrador,
+                intervalo_saida,
+                amostra,
+                x_amostra,
+            } => {
+                x_amostra.resize(x.len(), 0.0);
+                while *t < t_fim && !trajetoria.encerrar() {
+                    *t = integrador.passo_adaptativo(sistema, *t, x, t_fim);
+                    match intervalo_saida {
+                        Some(intervalo) => {
+                            while *amostra as f64 * *intervalo <= *t {
+                                let t_amostra = *amostra as f64 * *intervalo;
+                                integrador.interpolar(t_amostra, x_amostra);
+                                trajetoria.registrar(t_amostra, x_amostra);
+                                *amostra += 1;
+                            }
+                        }
+                        None => trajetoria.registrar(*t, x),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::case::ModeloMaquina;
+    use crate::error::Error;
+
+    #[test]
+    fn modelo_invalido_retorna_erro() {
+        // Modelo com dinâmica de fluxo sem a seção [rede]
+        let mut case = Case::default();
+        case.maquina.modelo = ModeloMaquina::UmEixo;
+        assert!(matches!(
+            sim_pet_time(&case, 0.1, 0.3),
+            Err(Error::Configuracao(_))
+        ));
+    }
+
+    #[test]
+    fn angulos_nao_atingidos_ficam_ausentes() {
+        let mut case = Case::default();
+        case.integrador.t_max = 0.2;
+        let sim = sim_pet_time(&case, 0.1, 0.3).unwrap();
+        assert!(sim.cra.is_some());
+        assert_eq!(sim.crr, None);
+        let sim = sim_pet_time(&case, 0.5, 0.6).unwrap();
+        assert_eq!((sim.cra, sim.crr), (None, None));
+    }
 }
@@ -0,0 +1,213 @@
+//! Modelo de sexta ordem (subtransitório) do gerador contra barra infinita,
+//! semelhante ao GENROU sem saturação.
+//!
+//! Além de δ e Δω, as FEMs transitórias `E'q`, `E'd` e subtransitórias
+//! `E''q`, `E''d` são estados:
+//!
+//! `T'd0·dE'q/dt = Efd - E'q - (Xd - x'd)·Id`,
+//! `T'q0·dE'd/dt = -E'd + (Xq - x'q)·Iq`,
+//! `T''d0·dE''q/dt = E'q - E''q - (x'd - x''d)·Id`,
+//! `T''q0·dE''d/dt = E'd - E''d + (x'q - x''q)·Iq`,
+//!
+//! com a tensão de campo `Efd` constante no valor pré-falta. O gerador é
+//! visto pela rede atrás de `x''d`, e a saliência subtransitória `x''q - x''d`
+//! entra nas correntes e na potência elétrica (ver [`crate::maquina`]).
+
+use std::f64::consts::PI;
+
+use crate::case::{Case, SystemParams};
+use crate::curva::PowerAngleCurve;
+use crate::dois_eixos::E_D_TRANSITORIA;
+use crate::error::Result;
+use crate::eventos::Chaveamento;
+use crate::maquina::{self, MachineModel, RedeGerador, potencia_eletrica};
+use crate::ode::OdeSystem;
+use crate::swing::{DELTA, DELTA_W};
+use crate::um_eixo::E_Q_TRANSITORIA;
+
+/// Índice da FEM subtransitória `E''q` (p.u.) no vetor de estado.
+pub const E_Q_SUBTRANSITORIA: usize = 4;
+/// Índice da FEM subtransitória `E''d` (p.u.) no vetor de estado.
+pub const E_D_SUBTRANSITORIA: usize = 5;
+
+/// Equações do modelo de sexta ordem, com estado
+/// `[δ, Δω, E'q, E'd, E''q, E''d]`.
+#[derive(Debug, Clone)]
+pub struct SixthOrder {
+    /// Coeficiente de inércia `M = H / (π·f)`.
+    pub m: f64,
+    /// Potência mecânica (p.u.).
+    pub pm: f64,
+    /// Coeficiente de amortecimento.
+    pub d: f64,
+    /// Diferença `Xd - x'd` (p.u.).
+    pub xd_menos_xd_transitoria: f64,
+    /// Diferença `Xq - x'q` (p.u.).
+    pub xq_menos_xq_transitoria: f64,
+    /// Diferença `x'd - x''d` (p.u.).
+    pub xd_transitoria_menos_subtransitoria: f64,
+    /// Diferença `x'q - x''q` (p.u.).
+    pub xq_transitoria_menos_subtransitoria: f64,
+    /// Saliência subtransitória `x''q - x''d` (p.u.).
+    pub saliencia: f64,
+    /// Constante de tempo `T'd0` (s).
+    pub td0_transitoria: f64,
+    /// Constante de tempo `T'q0` (s).
+    pub tq0_transitoria: f64,
+    /// Constante de tempo `T''d0` (s).
+    pub td0_subtransitoria: f64,
+    /// Constante de tempo `T''q0` (s).
+    pub tq0_subtransitoria: f64,
+    /// Tensão de campo `Efd` (p.u.), constante.
+    pub efd: f64,
+    /// Rede reduzida ao nó atrás de `x''d`.
+    pub rede: RedeGerador,
+    /// Ângulo do rotor no equilíbrio pré-falta (rad).
+    pub delta_0: f64,
+    /// FEMs `[E'q, E'd, E''q, E''d]` no equilíbrio pré-falta (p.u.).
+    pub fem_inicial: [f64; 4],
+}
+
+impl SixthOrder {
+    /// Modelo de sexta ordem do caso, no equilíbrio pré-falta. Requer a seção
+    /// `[rede]` e os parâmetros de `[maquina]` (ver [`Case::validar_maquina`]).
+    pub fn new(case: &Case) -> Result<Self> {
+        case.validar_maquina()?;
+        let sistema = &case.sistema;
+        let maquina = &case.maquina;
+        let smib = maquina::rede_smib(case)?;
+        let xd_transitoria = smib.xd_transitoria;
+        let xd = maquina::parametro(case, maquina.xd, "xd")?;
+        let td0 = maquina::parametro(case, maquina.td0_transitoria, "td0_transitoria")?;
+        let xq = maquina::parametro(case, maquina.xq, "xq")?;
+        let xq_transitoria = maquina::parametro(case, maquina.xq_transitoria, "xq_transitoria")?;
+        let tq0 = maquina::parametro(case, maquina.tq0_transitoria, "tq0_transitoria")?;
+        let xd_sub = maquina::parametro(case, maquina.xd_subtransitoria, "xd_subtransitoria")?;
+        let xq_sub = maquina.xq_subtransitoria.unwrap_or(xd_sub);
+        let td0_sub = maquina::parametro(case, maquina.td0_subtransitoria, "td0_subtransitoria")?;
+        let tq0_sub = maquina::parametro(case, maquina.tq0_subtransitoria, "tq0_subtransitoria")?;
+
+        let resultado = smib.calcular(sistema.pm)?;
+        let ponto = maquina::ponto_operacao(smib, &resultado, xq);
+        let e_q_sub = ponto.vq + xd_sub * ponto.id;
+        let e_d_sub = ponto.vd - xq_sub * ponto.iq;
+        let e_q = e_q_sub + (xd_transitoria - xd_sub) * ponto.id;
+        let e_d = ponto.vd - xq_transitoria * ponto.iq;
+        Ok(SixthOrder {
+            m: sistema.h / (PI * sistema.f),
+            pm: sistema.pm,
+            d: sistema.d,
+            xd_menos_xd_transitoria: xd - xd_transitoria,
+            xq_menos_xq_transitoria: xq - xq_transitoria,
+            xd_transitoria_menos_subtransitoria: xd_transitoria - xd_sub,
+            xq_transitoria_menos_subtransitoria: xq_transitoria - xq_sub,
+            saliencia: xq_sub - xd_sub,
+            td0_transitoria: td0,
+            tq0_transitoria: tq0,
+            td0_subtransitoria: td0_sub,
+            tq0_subtransitoria: tq0_sub,
+            efd: e_q + (xd - xd_transitoria) * ponto.id,
+            rede: RedeGerador::new(smib, &resultado, xd_sub)?,
+            delta_0: ponto.delta,
+            fem_inicial: [e_q, e_d, e_q_sub, e_d_sub],
+        })
+    }
+}
+
+impl MachineModel for SixthOrder {
+    fn estado_inicial(&self, sistema: &SystemParams) -> Vec<f64> {
+        let mut x = vec![0.0; 6];
+        x[DELTA] = self.delta_0;
+        x[DELTA_W] = sistema.delta_w_ini;
+        x[E_Q_TRANSITORIA..].copy_from_slice(&self.fem_inicial);
+        x
+    }
+
+    fn pm(&self, _t: f64, _x: &[f64]) -> f64 {
+        self.pm
+    }
+
+    fn curva(&self, x: &[f64]) -> PowerAngleCurve {
+        self.rede
+            .curva(x[E_D_SUBTRANSITORIA], x[E_Q_SUBTRANSITORIA])
+    }
+
+    fn chavear(&mut self, chaveamento: &Chaveamento) {
+        self.rede.chavear(chaveamento.configuracao, &chaveamento.pe);
+        if let Some(pm) = chaveamento.pm {
+            self.pm = pm;
+        }
+    }
+
+    fn tensao_terminal(&self, x: &[f64]) -> Option<f64> {
+        Some(self.rede.tensao_terminal(
+            x[DELTA],
+            x[E_D_SUBTRANSITORIA],
+            x[E_Q_SUBTRANSITORIA],
+            self.saliencia,
+        ))
+    }
+
+    fn tensao_campo(&self, _x: &[f64]) -> Option<f64> {
+        Some(self.efd)
+    }
+
+    fn potencia_eletrica(&self, x: &[f64]) -> f64 {
+        let (e_q_sub, e_d_sub) = (x[E_Q_SUBTRANSITORIA], x[E_D_SUBTRANSITORIA]);
+        let (id, iq) = self
+            .rede
+            .correntes(x[DELTA], e_d_sub, e_q_sub, self.saliencia);
+        potencia_eletrica(e_d_sub, e_q_sub, self.saliencia, id, iq)
+    }
+
+    fn derivada_entradas(&self, x: &[f64], efd: f64, pm: f64, dxdt: &mut [f64]) {
+        let (delta, delta_w) = (x[DELTA], x[DELTA_W]);
+        let (e_q, e_d) = (x[E_Q_TRANSITORIA], x[E_D_TRANSITORIA]);
+        let (e_q_sub, e_d_sub) = (x[E_Q_SUBTRANSITORIA], x[E_D_SUBTRANSITORIA]);
+        let (id, iq) = self.rede.correntes(delta, e_d_sub, e_q_sub, self.saliencia);
+        let pe = potencia_eletrica(e_d_sub, e_q_sub, self.saliencia, id, iq);
+        dxdt[DELTA] = delta_w;
+        dxdt[DELTA_W] = (pm - pe - self.d * delta_w) / self.m;
+        dxdt[E_Q_TRANSITORIA] =
+            (efd - e_q - self.xd_menos_xd_transitoria * id) / self.td0_transitoria;
+        dxdt[E_D_TRANSITORIA] = (-e_d + self.xq_menos_xq_transitoria * iq) / self.tq0_transitoria;
+        dxdt[E_Q_SUBTRANSITORIA] = (e_q - e_q_sub - self.xd_transitoria_menos_subtransitoria * id)
+            / self.td0_subtransitoria;
+        dxdt[E_D_SUBTRANSITORIA] = (e_d - e_d_sub + self.xq_transitoria_menos_subtransitoria * iq)
+            / self.tq0_subtransitoria;
+    }
+}
+
+impl OdeSystem for SixthOrder {
+    fn dimensao(&self) -> usize {
+        6
+    }
+
+    fn derivada(&self, _t: f64, x: &[f64], dxdt: &mut [f64]) {
+        self.derivada_entradas(x, self.efd, self.pm, dxdt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::case::caso_com_rede;
+    use crate::maquina::verificar_equilibrio;
+
+    #[test]
+    fn estado_inicial_e_o_equilibrio_pre_falta() {
+        let case = caso_com_rede(
+            "[maquina]\n\
+             modelo = \"sexta_ordem\"\n\
+             xd = 1.8\n\
+             td0_transitoria = 6.0\n\
+             xq = 1.7\n\
+             xq_transitoria = 0.55\n\
+             tq0_transitoria = 0.4\n\
+             xd_subtransitoria = 0.22\n\
+             td0_subtransitoria = 0.03\n\
+             tq0_subtransitoria = 0.05\n",
+        );
+        verificar_equilibrio(&SixthOrder::new(&case).unwrap(), &case);
+    }
+}
@@ -0,0 +1,96 @@
+//! Equação de oscilação (swing) da máquina contra barra infinita.
+
+use std::f64::consts::PI;
+
+use crate::case::SystemParams;
+use crate::curva::PowerAngleCurve;
+use crate::eventos::Chaveamento;
+use crate::maquina::MachineModel;
+use crate::ode::OdeSystem;
+
+/// Índice do ângulo do rotor δ (rad) no vetor de estado.
+pub const DELTA: usize = 0;
+/// Índice do desvio de velocidade Δω (rad/s) no vetor de estado.
+pub const DELTA_W: usize = 1;
+
+/// Equação de oscilação com amortecimento:
+///
+/// `dδ/dt = Δω`, `M dΔω/dt = Pm - Pe(δ) - D·Δω`, com `M = H / (π·f)`.
+///
+/// Estado `[δ, Δω]`. A curva `pe` muda a cada chaveamento da rede.
+#[derive(Debug, Clone)]
+pub struct SwingEquation {
+    /// Coeficiente de inércia `M = H / (π·f)`.
+    pub m: f64,
+    /// Potência mecânica (p.u.).
+    pub pm: f64,
+    /// Curva de potência elétrica.
+    pub pe: PowerAngleCurve,
+    /// Coeficiente de amortecimento.
+    pub d: f64,
+}
+
+impl SwingEquation {
+    /// Equação de oscilação dos parâmetros do sistema com a curva elétrica `pe`.
+    pub fn new(sistema: &SystemParams, pe: PowerAngleCurve) -> Self {
+        SwingEquation {
+            m: sistema.h / (PI * sistema.f), // Mantendo a inércia como H
+            pm: sistema.pm,
+            pe,
+            d: sistema.d,
+        }
+    }
+}
+
+impl MachineModel for SwingEquation {
+    fn estado_inicial(&self, sistema: &SystemParams) -> Vec<f64> {
+        let mut x = vec![0.0; 2];
+        x[DELTA] = sistema.delta_n_ini();
+        x[DELTA_W] = sistema.delta_w_ini;
+        x
+    }
+
+    fn pm(&self, _t: f64, _x: &[f64]) -> f64 {
+        self.pm
+    }
+
+    fn curva(&self, _x: &[f64]) -> PowerAngleCurve {
+        self.pe
+    }
+
+    fn chavear(&mut self, chaveamento: &Chaveamento) {
+        self.pe = chaveamento.pe;
+        if let Some(pm) = chaveamento.pm {
+            self.pm = pm;
+        }
+    }
+
+    fn tensao_terminal(&self, _x: &[f64]) -> Option<f64> {
+        None
+    }
+
+    fn tensao_campo(&self, _x: &[f64]) -> Option<f64> {
+        None
+    }
+
+    fn potencia_eletrica(&self, x: &[f64]) -> f64 {
+        self.pe.potencia(x[DELTA])
+    }
+
+    fn derivada_entradas(&self, x: &[f64], _efd: f64, pm: f64, dxdt: &mut [f64]) {
+        let (delta_n, delta_w) = (x[DELTA], x[DELTA_W]);
+        dxdt[DELTA] = delta_w;
+        dxdt[DELTA_W] =
+            (1.0 / self.m) * (pm - self.pe.potencia(delta_n)) - (self.d / self.m) * delta_w;
+    }
+}
+
+impl OdeSystem for SwingEquation {
+    fn dimensao(&self) -> usize {
+        2
+    }
+
+    fn derivada(&self, _t: f64, x: &[f64], dxdt: &mut [f64]) {
+        self.derivada_entradas(x, 0.0, self.pm, dxdt);
+    }
+}
@@ -0,0 +1,579 @@
+//! Reguladores de velocidade e turbinas: a potência mecânica `Pm` deixa de
+//! ser constante e passa a depender do desvio de velocidade.
+//!
+//! O regulador recebe o desvio de velocidade `Δω` (p.u. da velocidade
+//! síncrona) e fornece `Pm` ao modelo do gerador (ver
+//! [`crate::maquina::ControlledMachine`]). A referência de carga `Pref` é
+//! ajustada na inicialização ao `pm` do caso, e os degraus `pm` dos eventos
+//! passam a alterá-la.
+//!
+//! Modelos (seção `[maquina.regulador]`, escolhidos por `modelo`):
+//!
+//! - `"tgov1"`: turbina a vapor sem reaquecimento com regulador de estatismo
+//!   `R`, válvula `1/(1 + s·T1)` entre `VMIN` e `VMAX`, turbina
+//!   `(1 + s·T2)/(1 + s·T3)` e amortecimento `Dt·Δω`;
+//! - `"reaquecimento"`: turbina a vapor com reaquecimento, com regulador
+//!   `1/(1 + s·TG)` entre `PMIN` e `PMAX`, caixa de vapor `TCH` e
+//!   reaquecedor `TRH`, com a fração `FHP` da potência na turbina de alta
+//!   pressão;
+//! - `"hidraulica"`: turbina hidráulica com golpe de aríete
+//!   `(1 - s·TW)/(1 + 0,5·s·TW)`, regulador com estatismo permanente `R` e
+//!   transitório `RT` (reset `TR`) e servomotor `TG` do distribuidor entre
+//!   `GMIN` e `GMAX`;
+//! - `"ieeeg1"`: IEEEG1 simplificado (um eixo), com ganho `K = 1/R`,
+//!   avanço-atraso `T2/T1`, servomotor `T3` entre `PMIN` e `PMAX` e quatro
+//!   estágios `T4` a `T7` com as frações `K1`, `K3`, `K5` e `K7` da potência.
+//!
+//! A válvula rápida (seção `[maquina.valvula_rapida]`, ver [`FastValving`])
+//! fecha as válvulas de interceptação das turbinas a vapor, reduzindo a
+//! parcela da potência após o reaquecedor (toda a potência no TGOV1 e sem
+//! regulador).
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::blocos::{avanco_atraso, limite, sem_acumulo, um};
+use crate::error::{Error, Result};
+
+/// Modelo de regulador de velocidade e turbina acoplado ao gerador.
+pub trait GovernorModel: fmt::Debug {
+    /// Número de estados.
+    fn dimensao(&self) -> usize;
+
+    /// Ajusta `Pref` ao equilíbrio com potência mecânica `pm` e retorna o
+    /// estado inicial. Falha se `pm` estiver fora dos limites.
+    fn inicializar(&mut self, pm: f64) -> Result<Vec<f64>>;
+
+    /// Altera a referência de carga para a potência mecânica `pm` em regime.
+    fn definir_referencia(&mut self, pm: f64);
+
+    /// Potência mecânica (p.u.) no estado `x`, com desvio de velocidade
+    /// `velocidade` (p.u.) e abertura `abertura` das válvulas de
+    /// interceptação (1: abertas).
+    fn pm(&self, x: &[f64], velocidade: f64, abertura: f64) -> f64;
+
+    /// Derivadas do estado `x` com desvio de velocidade `velocidade` (p.u.).
+    fn derivada(&self, x: &[f64], velocidade: f64, dxdt: &mut [f64]);
+}
+
+/// Regulador de velocidade de `[maquina.regulador]`, escolhido por `modelo`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "modelo", rename_all = "snake_case")]
+pub enum GovernorSettings {
+    /// Turbina a vapor sem reaquecimento TGOV1 (`"tgov1"`).
+    Tgov1(Tgov1),
+    /// Turbina a vapor com reaquecimento (`"reaquecimento"`).
+    Reaquecimento(SteamReheat),
+    /// Turbina hidráulica com golpe de aríete (`"hidraulica"`).
+    Hidraulica(HydroTurbine),
+    /// IEEEG1 simplificado (`"ieeeg1"`).
+    Ieeeg1(Ieeeg1),
+}
+
+impl GovernorSettings {
+    /// Nome do modelo para os relatórios.
+    pub fn nome(&self) -> &'static str {
+        match self {
+            GovernorSettings::Tgov1(_) => "TGOV1",
+            GovernorSettings::Reaquecimento(_) => "vapor com reaquecimento",
+            GovernorSettings::Hidraulica(_) => "hidráulica",
+            GovernorSettings::Ieeeg1(_) => "IEEEG1",
+        }
+    }
+
+    /// `true` nas turbinas a vapor, que admitem válvula rápida.
+    pub fn vapor(&self) -> bool {
+        !matches!(self, GovernorSettings::Hidraulica(_))
+    }
+
+    /// Verifica os estatismos, constantes de tempo e limites do modelo.
+    pub fn validar(&self) -> Result<()> {
+        let (valido, condicao) = match self {
+            GovernorSettings::Tgov1(g) => (
+                g.r > 0.0 && g.t1 > 0.0 && g.t3 > 0.0 && g.t2 >= 0.0 && g.vmin < g.vmax,
+                "r, t1 e t3 positivos, t2 não negativo e vmin < vmax",
+            ),
+            GovernorSettings::Reaquecimento(g) => (
+                g.r > 0.0
+                    && g.tg > 0.0
+                    && g.tch > 0.0
+                    && g.trh > 0.0
+                    && (0.0..=1.0).contains(&g.fhp)
+                    && g.pmin < g.pmax,
+                "r, tg, tch e trh positivos, fhp entre 0 e 1 e pmin < pmax",
+            ),
+            GovernorSettings::Hidraulica(g) => (
+                g.r > 0.0
+                    && g.rt >= 0.0
+                    && g.tr > 0.0
+                    && g.tg > 0.0
+                    && g.tw > 0.0
+                    && g.gmin < g.gmax,
+                "r, tr, tg e tw positivos, rt não negativo e gmin < gmax",
+            ),
+            GovernorSettings::Ieeeg1(g) => (
+                g.k > 0.0
+                    && g.t1 >= 0.0
+                    && g.t2 >= 0.0
+                    && g.t3 > 0.0
+                    && g.t4 > 0.0
+                    && [g.t5, g.t6, g.t7].iter().all(|&t| t >= 0.0)
+                    && g.soma_fracoes() > 0.0
+                    && g.pmin < g.pmax,
+                "k, t3 e t4 positivos, t1, t2, t5, t6 e t7 não negativos, soma de k1, k3, k5 e \
+                 k7 positiva e pmin < pmax",
+            ),
+        };
+        if valido {
+            Ok(())
+        } else {
+            Err(Error::Configuracao(format!(
+                "o regulador {} requer {}",
+                self.nome(),
+                condicao
+            )))
+        }
+    }
+
+    /// Modelo do regulador, ainda não inicializado.
+    pub fn modelo(&self) -> Box<dyn GovernorModel> {
+        match self {
+            GovernorSettings::Tgov1(g) => Box::new(g.clone()),
+            GovernorSettings::Reaquecimento(g) => Box::new(g.clone()),
+            GovernorSettings::Hidraulica(g) => Box::new(g.clone()),
+            GovernorSettings::Ieeeg1(g) => Box::new(g.clone()),
+        }
+    }
+}
+
+/// TGOV1 com estado `[válvula, turbina]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Tgov1 {
+    /// Estatismo `R` (p.u.).
+    pub r: f64,
+    /// Constante de tempo da válvula `T1` (s).
+    pub t1: f64,
+    /// Abertura máxima da válvula `VMAX` (p.u.).
+    pub vmax: f64,
+    /// Abertura mínima da válvula `VMIN` (p.u.).
+    pub vmin: f64,
+    /// Constante de tempo de avanço da turbina `T2` (s).
+    pub t2: f64,
+    /// Constante de tempo de atraso da turbina `T3` (s).
+    pub t3: f64,
+    /// Coeficiente de amortecimento da turbina `Dt` (p.u.; padrão: 0).
+    #[serde(default)]
+    pub dt: f64,
+    /// Referência de carga, ajustada na inicialização.
+    #[serde(skip)]
+    pub pref: f64,
+}
+
+impl GovernorModel for Tgov1 {
+    fn dimensao(&self) -> usize {
+        2
+    }
+
+    fn inicializar(&mut self, pm: f64) -> Result<Vec<f64>> {
+        limite(
+            "abertura da válvula",
+            pm,
+            self.vmin,
+            self.vmax,
+            "do regulador",
+        )?;
+        self.pref = pm;
+        Ok(vec![pm, pm])
+    }
+
+    fn definir_referencia(&mut self, pm: f64) {
+        self.pref = pm;
+    }
+
+    fn pm(&self, x: &[f64], velocidade: f64, abertura: f64) -> f64 {
+        let (valvula, turbina) = (x[0], x[1]);
+        let saida = turbina + self.t2 / self.t3 * (valvula - turbina);
+        saida * abertura - self.dt * velocidade
+    }
+
+    fn derivada(&self, x: &[f64], velocidade: f64, dxdt: &mut [f64]) {
+        let (valvula, turbina) = (x[0], x[1]);
+        let derivada = (self.pref - velocidade / self.r - valvula) / self.t1;
+        dxdt[0] = sem_acumulo(valvula, derivada, self.vmin, self.vmax);
+        dxdt[1] = (valvula - turbina) / self.t3;
+    }
+}
+
+/// Turbina a vapor com reaquecimento, com estado
+/// `[regulador, caixa de vapor, reaquecedor]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SteamReheat {
+    /// Estatismo `R` (p.u.).
+    pub r: f64,
+    /// Constante de tempo do regulador `TG` (s).
+    pub tg: f64,
+    /// Limite superior da abertura `PMAX` (p.u.).
+    pub pmax: f64,
+    /// Limite inferior da abertura `PMIN` (p.u.; padrão: 0).
+    #[serde(default)]
+    pub pmin: f64,
+    /// Constante de tempo da caixa de vapor `TCH` (s).
+    pub tch: f64,
+    /// Constante de tempo do reaquecedor `TRH` (s).
+    pub trh: f64,
+    /// Fração da potência na turbina de alta pressão `FHP`.
+    pub fhp: f64,
+    /// Referência de carga, ajustada na inicialização.
+    #[serde(skip)]
+    pub pref: f64,
+}
+
+impl GovernorModel for SteamReheat {
+    fn dimensao(&self) -> usize {
+        3
+    }
+
+    fn inicializar(&mut self, pm: f64) -> Result<Vec<f64>> {
+        limite(
+            "abertura da válvula",
+            pm,
+            self.pmin,
+            self.pmax,
+            "do regulador",
+        )?;
+        self.pref = pm;
+        Ok(vec![pm, pm, pm])
+    }
+
+    fn definir_referencia(&mut self, pm: f64) {
+        self.pref = pm;
+    }
+
+    fn pm(&self, x: &[f64], _velocidade: f64, abertura: f64) -> f64 {
+        self.fhp * x[1] + (1.0 - self.fhp) * x[2] * abertura
+    }
+
+    fn derivada(&self, x: &[f64], velocidade: f64, dxdt: &mut [f64]) {
+        let (valvula, caixa, reaquecedor) = (x[0], x[1], x[2]);
+        let derivada = (self.pref - velocidade / self.r - valvula) / self.tg;
+        dxdt[0] = sem_acumulo(valvula, derivada, self.pmin, self.pmax);
+        dxdt[1] = (valvula - caixa) / self.tch;
+        dxdt[2] = (caixa - reaquecedor) / self.trh;
+    }
+}
+
+/// Turbina hidráulica com golpe de aríete, com estado
+/// `[estatismo transitório, distribuidor, conduto]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HydroTurbine {
+    /// Estatismo permanente `R` (p.u.).
+    pub r: f64,
+    /// Estatismo transitório `RT` (p.u.).
+    pub rt: f64,
+    /// Tempo de reset do estatismo transitório `TR` (s).
+    pub tr: f64,
+    /// Constante de tempo do servomotor do distribuidor `TG` (s).
+    pub tg: f64,
+    /// Abertura máxima do distribuidor `GMAX` (p.u.; padrão: 1).
+    #[serde(default = "um")]
+    pub gmax: f64,
+    /// Abertura mínima do distribuidor `GMIN` (p.u.; padrão: 0).
+    #[serde(default)]
+    pub gmin: f64,
+    /// Constante de tempo da água `TW` (s).
+    pub tw: f64,
+    /// Referência de carga, ajustada na inicialização.
+    #[serde(skip)]
+    pub pref: f64,
+}
+
+impl GovernorModel for HydroTurbine {
+    fn dimensao(&self) -> usize {
+        3
+    }
+
+    fn inicializar(&mut self, pm: f64) -> Result<Vec<f64>> {
+        limite(
+            "abertura do distribuidor",
+            pm,
+            self.gmin,
+            self.gmax,
+            "do regulador",
+        )?;
+        self.pref = pm;
+        Ok(vec![0.0, pm, pm])
+    }
+
+    fn definir_referencia(&mut self, pm: f64) {
+        self.pref = pm;
+    }
+
+    // (1 - s·TW)/(1 + 0,5·s·TW) = -2 + 3/(1 + 0,5·s·TW)
+    fn pm(&self, x: &[f64], _velocidade: f64, _abertura: f64) -> f64 {
+        3.0 * x[2] - 2.0 * x[1]
+    }
+
+    fn derivada(&self, x: &[f64], velocidade: f64, dxdt: &mut [f64]) {
+        let (transitorio, distribuidor, conduto) = (x[0], x[1], x[2]);
+        // Estatismo transitório (1 + s·TR)/(1 + s·(RT/R)·TR) sobre -Δω/R
+        let (correcao, derivada) = avanco_atraso(
+            -velocidade / self.r,
+            transitorio,
+            self.tr,
+            self.rt / self.r * self.tr,
+        );
+        dxdt[0] = derivada;
+        let derivada = (self.pref + correcao - distribuidor) / self.tg;
+        dxdt[1] = sem_acumulo(distribuidor, derivada, self.gmin, self.gmax);
+        dxdt[2] = (distribuidor - conduto) / (0.5 * self.tw);
+    }
+}
+
+/// IEEEG1 simplificado, com estado
+/// `[avanço-atraso, válvula, estágios T4 a T7]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Ieeeg1 {
+    /// Ganho `K` (inverso do estatismo, p.u.).
+    pub k: f64,
+    /// Constante de tempo de atraso `T1` (s; padrão: 0).
+    #[serde(default)]
+    pub t1: f64,
+    /// Constante de tempo de avanço `T2` (s; padrão: 0).
+    #[serde(default)]
+    pub t2: f64,
+    /// Constante de tempo do servomotor `T3` (s).
+    pub t3: f64,
+    /// Limite superior da abertura `PMAX` (p.u.).
+    pub pmax: f64,
+    /// Limite inferior da abertura `PMIN` (p.u.; padrão: 0).
+    #[serde(default)]
+    pub pmin: f64,
+    /// Constante de tempo da caixa de vapor `T4` (s).
+    pub t4: f64,
+    /// Fração da potência do primeiro estágio `K1`.
+    pub k1: f64,
+    /// Constante de tempo do segundo estágio (reaquecedor) `T5` (s; padrão:
+    /// 0).
+    #[serde(default)]
+    pub t5: f64,
+    /// Fração da potência do segundo estágio `K3` (padrão: 0).
+    #[serde(default)]
+    pub k3: f64,
+    /// Constante de tempo do terceiro estágio `T6` (s; padrão: 0).
+    #[serde(default)]
+    pub t6: f64,
+    /// Fração da potência do terceiro estágio `K5` (padrão: 0).
+    #[serde(default)]
+    pub k5: f64,
+    /// Constante de tempo do quarto estágio `T7` (s; padrão: 0).
+    #[serde(default)]
+    pub t7: f64,
+    /// Fração da potência do quarto estágio `K7` (padrão: 0).
+    #[serde(default)]
+    pub k7: f64,
+    /// Referência de abertura, ajustada na inicialização.
+    #[serde(skip)]
+    pub pref: f64,
+}
+
+impl Ieeeg1 {
+    fn soma_fracoes(&self) -> f64 {
+        self.k1 + self.k3 + self.k5 + self.k7
+    }
+
+    // Saídas dos quatro estágios: com constante de tempo nula, o estágio
+    // repete a entrada
+    fn estagios(&self, x: &[f64]) -> [f64; 4] {
+        let mut saidas = [0.0; 4];
+        let mut entrada = x[1];
+        for (i, t) in [self.t4, self.t5, self.t6, self.t7].into_iter().enumerate() {
+            saidas[i] = if t > 0.0 { x[2 + i] } else { entrada };
+            entrada = saidas[i];
+        }
+        saidas
+    }
+}
+
+impl GovernorModel for Ieeeg1 {
+    fn dimensao(&self) -> usize {
+        6
+    }
+
+    fn inicializar(&mut self, pm: f64) -> Result<Vec<f64>> {
+        let abertura = pm / self.soma_fracoes();
+        limite(
+            "abertura da válvula",
+            abertura,
+            self.pmin,
+            self.pmax,
+            "do regulador",
+        )?;
+        self.pref = abertura;
+        Ok(vec![0.0, abertura, abertura, abertura, abertura, abertura])
+    }
+
+    fn definir_referencia(&mut self, pm: f64) {
+        self.pref = pm / self.soma_fracoes();
+    }
+
+    fn pm(&self, x: &[f64], _velocidade: f64, abertura: f64) -> f64 {
+        let [s4, s5, s6, s7] = self.estagios(x);
+        self.k1 * s4 + (self.k3 * s5 + self.k5 * s6 + self.k7 * s7) * abertura
+    }
+
+    fn derivada(&self, x: &[f64], velocidade: f64, dxdt: &mut [f64]) {
+        let (correcao, derivada) = avanco_atraso(-self.k * velocidade, x[0], self.t2, self.t1);
+        dxdt[0] = derivada;
+        let derivada = (self.pref + correcao - x[1]) / self.t3;
+        dxdt[1] = sem_acumulo(x[1], derivada, self.pmin, self.pmax);
+        let saidas = self.estagios(x);
+        let mut entrada = x[1];
+        for (i, t) in [self.t4, self.t5, self.t6, self.t7].into_iter().enumerate() {
+            dxdt[2 + i] = if t > 0.0 {
+                (entrada - x[2 + i]) / t
+            } else {
+                0.0
+            };
+            entrada = saidas[i];
+        }
+    }
+}
+
+/// Válvula rápida: fechamento temporário das válvulas de interceptação das
+/// turbinas a vapor para reduzir a potência acelerante após a falta.
+///
+/// A abertura cai linearmente de 1 a `nivel` em `fechamento` segundos a
+/// partir de `inicio`, permanece em `nivel` por `duracao` segundos e volta a 1
+/// linearmente em `reabertura` segundos.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FastValving {
+    /// Instante de início do fechamento (s).
+    pub inicio: f64,
+    /// Abertura mínima das válvulas de interceptação (p.u.).
+    pub nivel: f64,
+    /// Tempo de fechamento (s; padrão: 0,1).
+    #[serde(default = "fechamento_padrao")]
+    pub fechamento: f64,
+    /// Tempo em que as válvulas permanecem fechadas (s; padrão: 0).
+    #[serde(default)]
+    pub duracao: f64,
+    /// Tempo de reabertura (s; padrão: 1).
+    #[serde(default = "um")]
+    pub reabertura: f64,
+}
+
+impl FastValving {
+    /// Verifica o nível e os tempos da manobra.
+    pub fn validar(&self) -> Result<()> {
+        if (0.0..1.0).contains(&self.nivel)
+            && self.inicio >= 0.0
+            && self.fechamento > 0.0
+            && self.duracao >= 0.0
+            && self.reabertura > 0.0
+        {
+            Ok(())
+        } else {
+            Err(Error::Configuracao(
+                "a válvula rápida requer 0 <= nivel < 1, inicio e duracao não negativos e \
+                 fechamento e reabertura positivos"
+                    .to_string(),
+            ))
+        }
+    }
+
+    /// Abertura das válvulas de interceptação no instante `t`.
+    pub fn abertura(&self, t: f64) -> f64 {
+        let fechada = self.inicio + self.fechamento;
+        let reabrindo = fechada + self.duracao;
+        let aberta = reabrindo + self.reabertura;
+        if t <= self.inicio || t >= aberta {
+            1.0
+        } else if t < fechada {
+            1.0 - (1.0 - self.nivel) * (t - self.inicio) / self.fechamento
+        } else if t < reabrindo {
+            self.nivel
+        } else {
+            self.nivel + (1.0 - self.nivel) * (t - reabrindo) / self.reabertura
+        }
+    }
+}
+
+fn fechamento_padrao() -> f64 {
+    0.1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reguladores com abertura máxima 1 e o índice do estado limitado (válvula
+    // ou distribuidor)
+    fn reguladores() -> Vec<(GovernorSettings, usize)> {
+        [
+            ("modelo = \"tgov1\"\nr = 0.05\nt1 = 0.5\nvmax = 1.0\nvmin = 0.0\nt2 = 1.0\nt3 = 5.0\n", 0),
+            (
+                "modelo = \"reaquecimento\"\nr = 0.05\ntg = 0.2\npmax = 1.0\ntch = 0.3\n\
+                 trh = 7.0\nfhp = 0.3\n",
+                0,
+            ),
+            ("modelo = \"hidraulica\"\nr = 0.05\nrt = 0.38\ntr = 5.0\ntg = 0.5\ntw = 1.0\n", 1),
+            (
+                "modelo = \"ieeeg1\"\nk = 20.0\nt1 = 0.2\nt2 = 1.0\nt3 = 0.1\npmax = 1.0\n\
+                 t4 = 0.3\nk1 = 0.3\nt5 = 7.0\nk3 = 0.7\n",
+                1,
+            ),
+        ]
+        .into_iter()
+        .map(|(toml, estado)| (toml::from_str(toml).unwrap(), estado))
+        .collect()
+    }
+
+    #[test]
+    fn inicializacao_fica_em_equilibrio() {
+        for (configuracao, _) in reguladores() {
+            configuracao.validar().unwrap();
+            let mut regulador = configuracao.modelo();
+            let x = regulador.inicializar(0.8).unwrap();
+            let mut dxdt = vec![f64::NAN; regulador.dimensao()];
+            regulador.derivada(&x, 0.0, &mut dxdt);
+            assert!(
+                dxdt.iter().all(|&d| d == 0.0),
+                "{}: {:?}",
+                configuracao.nome(),
+                dxdt
+            );
+            assert!(
+                (regulador.pm(&x, 0.0, 1.0) - 0.8).abs() < 1e-12,
+                "{}",
+                configuracao.nome()
+            );
+        }
+    }
+
+    #[test]
+    fn limites_sem_acumulo() {
+        for (configuracao, estado) in reguladores() {
+            let nome = configuracao.nome();
+            let mut regulador = configuracao.modelo();
+            assert!(
+                matches!(regulador.inicializar(1.2), Err(Error::Configuracao(_))),
+                "{}",
+                nome
+            );
+            let mut x = regulador.inicializar(0.8).unwrap();
+            x[estado] = 1.0;
+            let mut dxdt = vec![f64::NAN; regulador.dimensao()];
+            // Na abertura máxima, a subvelocidade não abre mais a válvula, e a
+            // sobrevelocidade a fecha
+            regulador.derivada(&x, -0.1, &mut dxdt);
+            assert_eq!(dxdt[estado], 0.0, "{}", nome);
+            regulador.derivada(&x, 0.1, &mut dxdt);
+            assert!(dxdt[estado] < 0.0, "{}", nome);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! Modelo de um eixo (decaimento de fluxo) do gerador contra barra infinita.
+//!
+//! Além de δ e Δω, a FEM transitória de eixo quadratura `E'q` é um estado,
+//! governado pelo enrolamento de campo:
+//!
+//! `T'd0·dE'q/dt = Efd - E'q - (Xd - x'd)·Id`
+//!
+//! com a tensão de campo `Efd` constante no valor pré-falta. A saliência
+//! transitória é desprezada (`x'q = x'd`), de modo que o gerador é visto pela
+//! rede como `E'q` atrás de `x'd`, como no modelo clássico, e a corrente de
+//! eixo direto vem da rede reduzida ([`RedeGerador`]) da configuração em
+//! vigor:
+//!
+//! `Id = b_propria·E'q + b_transferencia·V·cos(δ)`,
+//! `Pe = -b_transferencia·E'q·V·sin(δ)`.
+//!
+//! Durante a falta e o tempo morto `Id` cresce e `E'q` decai, reduzindo a
+//! potência sincronizante em relação ao modelo de FEM constante.
+
+use std::f64::consts::PI;
+
+use crate::case::{Case, SystemParams};
+use crate::curva::PowerAngleCurve;
+use crate::error::Result;
+use crate::eventos::Chaveamento;
+use crate::maquina::{self, MachineModel, RedeGerador};
+use crate::ode::OdeSystem;
+use crate::swing::{DELTA, DELTA_W};
+
+/// Índice da FEM transitória `E'q` (p.u.) no vetor de estado.
+pub const E_Q_TRANSITORIA: usize = 2;
+
+/// Equações do modelo de um eixo, com estado `[δ, Δω, E'q]`.
+#[derive(Debug, Clone)]
+pub struct FluxDecay {
+    /// Coeficiente de inércia `M = H / (π·f)`.
+    pub m: f64,
+    /// Potência mecânica (p.u.).
+    pub pm: f64,
+    /// Coeficiente de amortecimento.
+    pub d: f64,
+    /// Diferença `Xd - x'd` (p.u.).
+    pub xd_menos_xd_transitoria: f64,
+    /// Constante de tempo `T'd0` (s).
+    pub td0_transitoria: f64,
+    /// Tensão de campo `Efd` (p.u.), constante.
+    pub efd: f64,
+    /// Rede reduzida ao nó atrás de `x'd`.
+    pub rede: RedeGerador,
+}
+
+impl FluxDecay {
+    /// Modelo de um eixo do caso, no equilíbrio pré-falta. Requer a seção
+    /// `[rede]` e os parâmetros de `[maquina]` (ver [`Case::validar_maquina`]).
+    pub fn new(case: &Case) -> Result<Self> {
+        case.validar_maquina()?;
+        let sistema = &case.sistema;
+        let smib = maquina::rede_smib(case)?;
+        let resultado = smib.calcular(sistema.pm)?;
+        let xd = case.maquina.xd.unwrap_or(smib.xd_transitoria);
+        let mut modelo = FluxDecay {
+            m: sistema.h / (PI * sistema.f),
+            pm: sistema.pm,
+            d: sistema.d,
+            xd_menos_xd_transitoria: xd - smib.xd_transitoria,
+            td0_transitoria: case.maquina.td0_transitoria.unwrap_or(f64::INFINITY),
+            efd: 0.0,
+            rede: RedeGerador::new(smib, &resultado, smib.xd_transitoria)?,
+        };
+        // Efd que mantém E'q constante no equilíbrio pré-falta
+        let e_q = resultado.e_interna;
+        modelo.efd = e_q + modelo.xd_menos_xd_transitoria * modelo.id(sistema.delta_n_ini(), e_q);
+        Ok(modelo)
+    }
+
+    /// Corrente de eixo direto `Id` (p.u.).
+    pub fn id(&self, delta: f64, e_q: f64) -> f64 {
+        self.rede.correntes(delta, 0.0, e_q, 0.0).0
+    }
+}
+
+impl MachineModel for FluxDecay {
+    fn estado_inicial(&self, sistema: &SystemParams) -> Vec<f64> {
+        let mut x = vec![0.0; 3];
+        x[DELTA] = sistema.delta_n_ini();
+        x[DELTA_W] = sistema.delta_w_ini;
+        x[E_Q_TRANSITORIA] = self.rede.e_inicial;
+        x
+    }
+
+    fn pm(&self, _t: f64, _x: &[f64]) -> f64 {
+        self.pm
+    }
+
+    fn curva(&self, x: &[f64]) -> PowerAngleCurve {
+        self.rede.curva(0.0, x[E_Q_TRANSITORIA])
+    }
+
+    fn chavear(&mut self, chaveamento: &Chaveamento) {
+        self.rede.chavear(chaveamento.configuracao, &chaveamento.pe);
+        if let Some(pm) = chaveamento.pm {
+            self.pm = pm;
+        }
+    }
+
+    fn tensao_terminal(&self, x: &[f64]) -> Option<f64> {
+        Some(
+            self.rede
+                .tensao_terminal(x[DELTA], 0.0, x[E_Q_TRANSITORIA], 0.0),
+        )
+    }
+
+    fn tensao_campo(&self, _x: &[f64]) -> Option<f64> {
+        Some(self.efd)
+    }
+
+    fn potencia_eletrica(&self, x: &[f64]) -> f64 {
+        self.curva(x).potencia(x[DELTA])
+    }
+
+    fn derivada_entradas(&self, x: &[f64], efd: f64, pm: f64, dxdt: &mut [f64]) {
+        let (delta, delta_w, e_q) = (x[DELTA], x[DELTA_W], x[E_Q_TRANSITORIA]);
+        let pe = self.potencia_eletrica(x);
+        dxdt[DELTA] = delta_w;
+        dxdt[DELTA_W] = (pm - pe - self.d * delta_w) / self.m;
+        dxdt[E_Q_TRANSITORIA] =
+            (efd - e_q - self.xd_menos_xd_transitoria * self.id(delta, e_q)) / self.td0_transitoria;
+    }
+}
+
+impl OdeSystem for FluxDecay {
+    fn dimensao(&self) -> usize {
+        3
+    }
+
+    fn derivada(&self, _t: f64, x: &[f64], dxdt: &mut [f64]) {
+        self.derivada_entradas(x, self.efd, self.pm, dxdt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::case::caso_com_rede;
+    use crate::maquina::verificar_equilibrio;
+
+    #[test]
+    fn estado_inicial_e_o_equilibrio_pre_falta() {
+        let case = caso_com_rede(
+            "[maquina]\n\
+             modelo = \"um_eixo\"\n\
+             xd = 1.8\n\
+             td0_transitoria = 6.0\n",
+        );
+        verificar_equilibrio(&FluxDecay::new(&case).unwrap(), &case);
+    }
+}
@@ -1,12 +1,24 @@
+//! Valores padrão dos parâmetros, usados quando o arquivo de caso não os define (ver [`crate::case`]).
+
+/// Potência elétrica máxima pré-falta.
 pub const PE1: f64 = 1.83333;
+/// Potência elétrica máxima durante falta.
 pub const PE2: f64 = 1.13491;
+/// Potência elétrica máxima abertura mono/bifásica.
 pub const PE3: f64 = 1.22222;
 
-pub const PM: f64 = 1.44; // Potência mecânica constante
-pub const F: f64 = 60.0; // Frequência em Hz
-pub const H: f64 = 9.0; // Constante de inércia do gerador
-pub const D: f64 = 0.15; // Coeficiente de amortecimento
+/// Potência mecânica constante.
+pub const PM: f64 = 1.44;
+/// Frequência em Hz.
+pub const F: f64 = 60.0;
+/// Constante de inércia do gerador.
+pub const H: f64 = 9.0;
+/// Coeficiente de amortecimento.
+pub const D: f64 = 0.15;
 
-pub const DELTA_W_INI: f64 = 0.0; // Velocidade angular inicial
-pub const T_MAX: f64 = 5.0; // Tempo máximo de simulação (s)
-pub const DELTA_T: f64 = 5e-4 as f64; // Passo de integração (s)
+/// Velocidade angular inicial.
+pub const DELTA_W_INI: f64 = 0.0;
+/// Tempo máximo de simulação (s).
+pub const T_MAX: f64 = 5.0;
+/// Passo de integração (s).
+pub const DELTA_T: f64 = 5e-4;